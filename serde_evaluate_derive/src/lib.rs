@@ -0,0 +1,298 @@
+//! `#[derive(Evaluate)]` for [`serde_evaluate`](https://docs.rs/serde_evaluate).
+//!
+//! Generates a `paths()` function returning a builder with one method per serialized
+//! field, so paths are checked by the compiler instead of failing at runtime with
+//! `FieldNotFound`. Use it through the `derive` feature of `serde_evaluate`; see the
+//! `serde_evaluate::typed` module for the generated API.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Derives typed paths for a struct with named fields.
+///
+/// Reads `#[serde(rename = "..")]`, `#[serde(rename_all = "..")]` and
+/// `#[serde(skip)]` / `#[serde(skip_serializing)]`. Fields whose type has no typed path
+/// (maps, enums, ...) must be marked `#[evaluate(skip)]`.
+#[proc_macro_derive(Evaluate, attributes(evaluate))]
+pub fn derive_evaluate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "#[derive(Evaluate)] does not support generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "#[derive(Evaluate)] requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "#[derive(Evaluate)] only supports structs",
+            ))
+        }
+    };
+
+    let container = SerdeAttrs::parse(&input.attrs)?;
+    let rename_all = match &container.rename_all {
+        Some(rule) => Some(RenameRule::parse(rule)?),
+        None => None,
+    };
+
+    let mut methods = Vec::new();
+    for field in fields {
+        let serde = SerdeAttrs::parse(&field.attrs)?;
+        if serde.flatten {
+            return Err(syn::Error::new_spanned(
+                field,
+                "#[derive(Evaluate)] does not support #[serde(flatten)]; mark the field #[evaluate(skip)]",
+            ));
+        }
+        if serde.skip || evaluate_skip(&field.attrs)? {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named field");
+        let key = match &serde.rename {
+            Some(rename) => rename.value(),
+            None => {
+                let raw = ident.to_string();
+                let raw = raw.strip_prefix("r#").unwrap_or(&raw).to_string();
+                match rename_all {
+                    Some(rule) => rule.apply(&raw),
+                    None => raw,
+                }
+            }
+        };
+        if key.is_empty() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "#[derive(Evaluate)] cannot address a field serialized with an empty name",
+            ));
+        }
+
+        let ty = &field.ty;
+        let doc = format!("Path to the `{}` field.", key);
+        methods.push(quote! {
+            #[doc = #doc]
+            pub fn #ident(&self) -> <#ty as ::serde_evaluate::typed::PathField>::Path<R> {
+                <#ty as ::serde_evaluate::typed::PathField>::path(
+                    ::serde_evaluate::typed::child_segments(&self.segments, #key),
+                )
+            }
+        });
+    }
+
+    let paths = format_ident!("{}Paths", name);
+    let paths_doc = format!(
+        "Typed paths into [`{}`], generated by `#[derive(Evaluate)]`.",
+        name
+    );
+    let root_doc = format!("Returns the typed paths of the fields of `{}`.", name);
+
+    Ok(quote! {
+        #[doc = #paths_doc]
+        #vis struct #paths<R> {
+            segments: ::std::vec::Vec<&'static str>,
+            root: ::core::marker::PhantomData<fn() -> R>,
+        }
+
+        impl<R> #paths<R> {
+            #(#methods)*
+        }
+
+        impl<R> ::core::clone::Clone for #paths<R> {
+            fn clone(&self) -> Self {
+                #paths {
+                    segments: self.segments.clone(),
+                    root: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl<R> ::core::fmt::Debug for #paths<R> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(stringify!(#paths)).field(&self.segments).finish()
+            }
+        }
+
+        impl #name {
+            #[doc = #root_doc]
+            #vis fn paths() -> #paths<#name> {
+                <#name as ::serde_evaluate::typed::Evaluate>::paths_at(::std::vec::Vec::new())
+            }
+        }
+
+        impl ::serde_evaluate::typed::Evaluate for #name {
+            type Paths<R> = #paths<R>;
+
+            fn paths_at<R>(segments: ::std::vec::Vec<&'static str>) -> #paths<R> {
+                #paths {
+                    segments,
+                    root: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl ::serde_evaluate::typed::PathField for #name {
+            type Path<R> = #paths<R>;
+
+            fn path<R>(segments: ::std::vec::Vec<&'static str>) -> #paths<R> {
+                <#name as ::serde_evaluate::typed::Evaluate>::paths_at(segments)
+            }
+        }
+    })
+}
+
+/// The `#[serde(...)]` attributes that affect serialized field names.
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<LitStr>,
+    rename_all: Option<LitStr>,
+    skip: bool,
+    flatten: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut parsed = SerdeAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") || meta.path.is_ident("rename_all") {
+                    let target = if meta.path.is_ident("rename") {
+                        &mut parsed.rename
+                    } else {
+                        &mut parsed.rename_all
+                    };
+                    if meta.input.peek(syn::Token![=]) {
+                        *target = Some(meta.value()?.parse()?);
+                    } else {
+                        // `rename(serialize = "..", deserialize = "..")`
+                        meta.parse_nested_meta(|inner| {
+                            if inner.path.is_ident("serialize") {
+                                *target = Some(inner.value()?.parse()?);
+                            } else {
+                                inner.value()?.parse::<LitStr>()?;
+                            }
+                            Ok(())
+                        })?;
+                    }
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                    parsed.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    parsed.flatten = true;
+                } else {
+                    skip_meta_value(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Consumes the value of a serde attribute this macro does not interpret.
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|inner| skip_meta_value(&inner))?;
+    }
+    Ok(())
+}
+
+/// Returns true for a field marked `#[evaluate(skip)]`.
+fn evaluate_skip(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("evaluate")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown evaluate attribute, expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+/// The case conventions of `#[serde(rename_all = "..")]`.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(rule: &LitStr) -> syn::Result<Self> {
+        Ok(match rule.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return Err(syn::Error::new(rule.span(), "unknown rename_all rule")),
+        })
+    }
+
+    /// Renames a snake_case field name, as serde does.
+    fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use crate::error::EvaluateError;
+use crate::expr::unwrap_null;
+use crate::extractor::{CompositeFieldExtractor, NestedFieldExtractor};
+use crate::value::FieldScalarValue;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An aggregate function applied to one path of every record in a group.
+///
+/// Paths use the same syntax as [expressions](crate::expr). `None` and JSON `null`
+/// values are skipped, as in SQL; a path missing from a record is an error.
+#[derive(Debug, Clone)]
+pub struct Aggregate {
+    function: AggregateFunction,
+    extractor: NestedFieldExtractor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AggregateFunction {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg,
+    CountDistinct,
+}
+
+impl Aggregate {
+    /// Counts the non-null values at `path`, as a `U64`.
+    ///
+    /// The number of records in a group, nulls included, is always available as
+    /// [`Aggregates::count`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::ExpressionSyntax` or `EvaluateError::InvalidPath` if
+    /// `path` cannot be parsed.
+    pub fn count(path: &str) -> Result<Self, EvaluateError> {
+        Self::new(AggregateFunction::Count, path)
+    }
+
+    /// Sums the numbers at `path`: an `I128` if every value is an integer, otherwise an
+    /// `F64`. `null` if there are no values.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Aggregate::count`].
+    pub fn sum(path: &str) -> Result<Self, EvaluateError> {
+        Self::new(AggregateFunction::Sum, path)
+    }
+
+    /// The smallest value at `path` by [`FieldScalarValue::total_cmp`], or `null`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Aggregate::count`].
+    pub fn min(path: &str) -> Result<Self, EvaluateError> {
+        Self::new(AggregateFunction::Min, path)
+    }
+
+    /// The largest value at `path` by [`FieldScalarValue::total_cmp`], or `null`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Aggregate::count`].
+    pub fn max(path: &str) -> Result<Self, EvaluateError> {
+        Self::new(AggregateFunction::Max, path)
+    }
+
+    /// The mean of the numbers at `path` as an `F64`, or `null`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Aggregate::count`].
+    pub fn avg(path: &str) -> Result<Self, EvaluateError> {
+        Self::new(AggregateFunction::Avg, path)
+    }
+
+    /// The exact number of distinct non-null values at `path`, as a `U64`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Aggregate::count`].
+    pub fn count_distinct(path: &str) -> Result<Self, EvaluateError> {
+        Self::new(AggregateFunction::CountDistinct, path)
+    }
+
+    fn new(function: AggregateFunction, path: &str) -> Result<Self, EvaluateError> {
+        Ok(Aggregate {
+            function,
+            extractor: NestedFieldExtractor::new_from_dotted(path)?,
+        })
+    }
+}
+
+/// The aggregates of one group, as returned by [`group_by`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregates {
+    /// Number of records in the group.
+    pub count: u64,
+    /// One value per [`Aggregate`], in the order they were passed to [`group_by`].
+    pub values: Vec<FieldScalarValue>,
+}
+
+/// Groups `records` by the values at `key_paths` and computes `aggregates` for each
+/// group. Groups are returned as `(key, aggregates)` pairs ordered by key.
+///
+/// Keys are extracted with a [`CompositeFieldExtractor`], so `Some(..)` is part of the
+/// key: a group for `Some("eu")` is distinct from one for `"eu"`. Keys are grouped and
+/// ordered by [`FieldScalarValue::total_cmp`], so `NaN` keys share a group while `-0.0`
+/// and `0.0` do not. With no key paths, every record falls into a single group with an
+/// empty key.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{group_by, Aggregate, EvaluateError, FieldScalarValue};
+///
+/// #[derive(Serialize)]
+/// struct Order {
+///     region: String,
+///     customer: u32,
+///     total: u64,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let orders = vec![
+///         Order { region: "eu".to_string(), customer: 1, total: 10 },
+///         Order { region: "eu".to_string(), customer: 1, total: 30 },
+///         Order { region: "us".to_string(), customer: 2, total: 5 },
+///     ];
+///
+///     let groups = group_by(
+///         &orders,
+///         &["region"],
+///         &[Aggregate::sum("total")?, Aggregate::count_distinct("customer")?],
+///     )?;
+///
+///     let (key, eu) = &groups[0];
+///     assert_eq!(key, &vec![FieldScalarValue::String("eu".to_string())]);
+///     assert_eq!(eu.count, 2);
+///     assert_eq!(eu.values, vec![FieldScalarValue::I128(40), FieldScalarValue::U64(1)]);
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns `EvaluateError::ExpressionSyntax` or `EvaluateError::InvalidPath` if a key
+/// path cannot be parsed, the first extraction error for any record, or
+/// `EvaluateError::TypeMismatch` if `sum` or `avg` meets a value that is not a number
+/// or an integer sum overflows.
+pub fn group_by<'a, T, I, S>(
+    records: I,
+    key_paths: &[S],
+    aggregates: &[Aggregate],
+) -> Result<Vec<(Vec<FieldScalarValue>, Aggregates)>, EvaluateError>
+where
+    T: Serialize + 'a,
+    I: IntoIterator<Item = &'a T>,
+    S: AsRef<str>,
+{
+    let keys = if key_paths.is_empty() {
+        None
+    } else {
+        Some(CompositeFieldExtractor::new_from_dotted(key_paths)?)
+    };
+
+    let mut groups: BTreeMap<Vec<TotalOrd>, Group> = BTreeMap::new();
+    for record in records {
+        let key = match &keys {
+            Some(keys) => keys.evaluate(record)?.into_iter().map(TotalOrd).collect(),
+            None => Vec::new(),
+        };
+        let group = groups.entry(key).or_insert_with(|| Group::new(aggregates));
+        group.count += 1;
+        for (aggregate, accumulator) in aggregates.iter().zip(&mut group.accumulators) {
+            let value = aggregate.extractor.evaluate(record)?;
+            if let Some(value) = unwrap_null(&value) {
+                accumulator.add(value)?;
+            }
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(key, group)| {
+            let key = key.into_iter().map(|TotalOrd(value)| value).collect();
+            (key, group.finish())
+        })
+        .collect())
+}
+
+/// Orders values by [`FieldScalarValue::total_cmp`], for use as `BTreeMap` keys.
+struct TotalOrd(FieldScalarValue);
+
+impl PartialEq for TotalOrd {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for TotalOrd {}
+
+impl PartialOrd for TotalOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TotalOrd {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+struct Group {
+    count: u64,
+    accumulators: Vec<Accumulator>,
+}
+
+impl Group {
+    fn new(aggregates: &[Aggregate]) -> Self {
+        Group {
+            count: 0,
+            accumulators: aggregates
+                .iter()
+                .map(|aggregate| Accumulator::new(aggregate.function))
+                .collect(),
+        }
+    }
+
+    fn finish(self) -> Aggregates {
+        Aggregates {
+            count: self.count,
+            values: self
+                .accumulators
+                .into_iter()
+                .map(Accumulator::finish)
+                .collect(),
+        }
+    }
+}
+
+enum Accumulator {
+    Count(u64),
+    Sum(Option<Sum>),
+    Min(Option<FieldScalarValue>),
+    Max(Option<FieldScalarValue>),
+    Avg { sum: f64, count: u64 },
+    CountDistinct(BTreeSet<TotalOrd>),
+}
+
+/// A running sum that stays exact while every value is an integer.
+enum Sum {
+    Int(i128),
+    Float(f64),
+}
+
+impl Accumulator {
+    fn new(function: AggregateFunction) -> Self {
+        match function {
+            AggregateFunction::Count => Accumulator::Count(0),
+            AggregateFunction::Sum => Accumulator::Sum(None),
+            AggregateFunction::Min => Accumulator::Min(None),
+            AggregateFunction::Max => Accumulator::Max(None),
+            AggregateFunction::Avg => Accumulator::Avg { sum: 0.0, count: 0 },
+            AggregateFunction::CountDistinct => Accumulator::CountDistinct(BTreeSet::new()),
+        }
+    }
+
+    /// Adds a non-null value.
+    fn add(&mut self, value: &FieldScalarValue) -> Result<(), EvaluateError> {
+        match self {
+            Accumulator::Count(count) => *count += 1,
+            Accumulator::Sum(sum) => {
+                let next = match (sum.take(), to_number(value)?) {
+                    (None, number) => number,
+                    (Some(Sum::Int(a)), Sum::Int(b)) => Sum::Int(a.checked_add(b).ok_or_else(
+                        || EvaluateError::TypeMismatch {
+                            message: "integer sum overflowed".to_string(),
+                        },
+                    )?),
+                    (Some(a), b) => Sum::Float(a.as_f64() + b.as_f64()),
+                };
+                *sum = Some(next);
+            }
+            Accumulator::Min(min) => {
+                if min.as_ref().is_none_or(|min| value.total_cmp(min).is_lt()) {
+                    *min = Some(value.clone());
+                }
+            }
+            Accumulator::Max(max) => {
+                if max.as_ref().is_none_or(|max| value.total_cmp(max).is_gt()) {
+                    *max = Some(value.clone());
+                }
+            }
+            Accumulator::Avg { sum, count } => {
+                *sum += to_number(value)?.as_f64();
+                *count += 1;
+            }
+            Accumulator::CountDistinct(seen) => {
+                seen.insert(TotalOrd(value.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> FieldScalarValue {
+        let null = FieldScalarValue::Option(None);
+        match self {
+            Accumulator::Count(count) => FieldScalarValue::U64(count),
+            Accumulator::Sum(None) => null,
+            Accumulator::Sum(Some(Sum::Int(sum))) => FieldScalarValue::I128(sum),
+            Accumulator::Sum(Some(Sum::Float(sum))) => FieldScalarValue::F64(sum),
+            Accumulator::Min(value) | Accumulator::Max(value) => value.unwrap_or(null),
+            Accumulator::Avg { count: 0, .. } => null,
+            Accumulator::Avg { sum, count } => FieldScalarValue::F64(sum / count as f64),
+            Accumulator::CountDistinct(seen) => FieldScalarValue::U64(seen.len() as u64),
+        }
+    }
+}
+
+impl Sum {
+    fn as_f64(&self) -> f64 {
+        match *self {
+            Sum::Int(v) => v as f64,
+            Sum::Float(v) => v,
+        }
+    }
+}
+
+fn to_number(value: &FieldScalarValue) -> Result<Sum, EvaluateError> {
+    Ok(match *value {
+        FieldScalarValue::I8(v) => Sum::Int(v.into()),
+        FieldScalarValue::I16(v) => Sum::Int(v.into()),
+        FieldScalarValue::I32(v) => Sum::Int(v.into()),
+        FieldScalarValue::I64(v) => Sum::Int(v.into()),
+        FieldScalarValue::I128(v) => Sum::Int(v),
+        FieldScalarValue::U8(v) => Sum::Int(v.into()),
+        FieldScalarValue::U16(v) => Sum::Int(v.into()),
+        FieldScalarValue::U32(v) => Sum::Int(v.into()),
+        FieldScalarValue::U64(v) => Sum::Int(v.into()),
+        FieldScalarValue::U128(v) => match i128::try_from(v) {
+            Ok(v) => Sum::Int(v),
+            Err(_) => Sum::Float(v as f64),
+        },
+        FieldScalarValue::F32(v) => Sum::Float(v.into()),
+        FieldScalarValue::F64(v) => Sum::Float(v),
+        ref other => {
+            return Err(EvaluateError::TypeMismatch {
+                message: format!("cannot aggregate {:?} as a number", other.kind()),
+            })
+        }
+    })
+}
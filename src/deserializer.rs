@@ -0,0 +1,377 @@
+//! Deserializer-side extraction, for records that are still encoded.
+//!
+//! [`PathSeed`] is a `DeserializeSeed` that walks any self-describing
+//! `serde::Deserializer` (JSON, CBOR, MessagePack, ...), follows a path of map keys and
+//! skips everything else with [`IgnoredAny`], so the record is never materialized.
+//! [`NestedFieldExtractor::evaluate_from`](crate::NestedFieldExtractor::evaluate_from)
+//! and [`FieldExtractor::evaluate_from`](crate::FieldExtractor::evaluate_from) are the
+//! usual entry points.
+//!
+//! The value is reported with the type the format provides, which is not necessarily
+//! the type of the Rust field: JSON, for example, reports every non-negative integer
+//! as `U64` and every struct as a map. Formats that are not self-describing, such as
+//! bincode, cannot be walked without the schema and return a deserialization error.
+
+use crate::error::{EvaluateError, NotFoundReason};
+use crate::extractor::DuplicateKeyPolicy;
+use crate::path::{aliases_of, KeyMatching};
+use crate::value::FieldScalarValue;
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserializer;
+use std::fmt;
+
+/// A `DeserializeSeed` that extracts the scalar at a path from an encoded record.
+///
+/// The seed itself only fails with the deserializer's error (malformed input, or a
+/// format that does not support `deserialize_any`). Extraction failures are returned
+/// as the inner `Result`, after the rest of the input has been skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::de::DeserializeSeed;
+/// use serde_evaluate::{FieldScalarValue, PathSeed};
+///
+/// let path = vec!["user".to_string(), "name".to_string()];
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"{"user": {"name": "ada"}}"#);
+///
+/// let value = PathSeed::new(&path).deserialize(&mut deserializer).unwrap();
+/// assert_eq!(value, Ok(FieldScalarValue::String("ada".to_string())));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PathSeed<'p> {
+    path: &'p [String],
+    matching: KeyMatching,
+    aliases: &'p [Vec<String>],
+    duplicates: DuplicateKeyPolicy,
+}
+
+impl<'p> PathSeed<'p> {
+    /// Creates a seed that extracts the value at `path`. Each segment is a map key
+    /// (or struct field name).
+    pub fn new(path: &'p [String]) -> Self {
+        PathSeed {
+            path,
+            matching: KeyMatching::Exact,
+            aliases: &[],
+            duplicates: DuplicateKeyPolicy::FirstWins,
+        }
+    }
+
+    /// Sets how segments are compared with map keys. Defaults to [`KeyMatching::Exact`].
+    pub fn with_key_matching(mut self, matching: KeyMatching) -> Self {
+        self.matching = matching;
+        self
+    }
+
+    /// Sets how a map with two keys matching the same segment is handled. Defaults to
+    /// [`DuplicateKeyPolicy::FirstWins`].
+    pub fn with_duplicate_keys(mut self, policy: DuplicateKeyPolicy) -> Self {
+        self.duplicates = policy;
+        self
+    }
+
+    /// Also accepts the names in `aliases`, indexed by segment.
+    pub(crate) fn with_aliases(mut self, aliases: &'p [Vec<String>]) -> Self {
+        self.aliases = aliases;
+        self
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for PathSeed<'_> {
+    type Value = Result<FieldScalarValue, EvaluateError>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let visitor = PathVisitor {
+            path: self.path,
+            matching: self.matching,
+            aliases: self.aliases,
+            duplicates: self.duplicates,
+            depth: 0,
+        };
+        Ok(match deserializer.deserialize_any(visitor)? {
+            Outcome::Found(value) => Ok(value),
+            Outcome::NotFound(depth, reason) => Err(EvaluateError::NestedFieldNotFound {
+                path: self.path.to_vec(),
+                failed_at_index: depth,
+                reason,
+            }),
+            Outcome::Failed(err) => Err(err),
+        })
+    }
+}
+
+/// Extracts the value at `path`, reporting the deserializer's own errors as
+/// [`EvaluateError::DeserializationError`].
+pub(crate) fn extract_from<'de, D: Deserializer<'de>>(
+    seed: PathSeed<'_>,
+    deserializer: D,
+) -> Result<FieldScalarValue, EvaluateError> {
+    seed.deserialize(deserializer)
+        .map_err(|err| EvaluateError::DeserializationError {
+            message: err.to_string(),
+        })?
+}
+
+enum Outcome {
+    Found(FieldScalarValue),
+    /// The segment at this index could not be followed.
+    NotFound(usize, NotFoundReason),
+    Failed(EvaluateError),
+}
+
+/// Visits the value reached after `depth` segments of `path`.
+#[derive(Clone, Copy)]
+struct PathVisitor<'p> {
+    path: &'p [String],
+    matching: KeyMatching,
+    aliases: &'p [Vec<String>],
+    duplicates: DuplicateKeyPolicy,
+    depth: usize,
+}
+
+impl PathVisitor<'_> {
+    fn is_target(&self) -> bool {
+        self.depth == self.path.len()
+    }
+
+    fn scalar(self, value: FieldScalarValue) -> Outcome {
+        if self.is_target() {
+            Outcome::Found(value)
+        } else if let FieldScalarValue::Unit | FieldScalarValue::Option(None) = value {
+            Outcome::NotFound(self.depth, NotFoundReason::Null)
+        } else {
+            self.not_traversable("scalar")
+        }
+    }
+
+    /// Fails the target with `err`, or the traversal if the value is before the end
+    /// of the path.
+    fn unsupported(self, err: EvaluateError, type_name: &'static str) -> Outcome {
+        if self.is_target() {
+            Outcome::Failed(err.at_path(self.path))
+        } else {
+            self.not_traversable(type_name)
+        }
+    }
+
+    fn not_traversable(self, type_name: &'static str) -> Outcome {
+        Outcome::NotFound(self.depth, NotFoundReason::NotTraversable { type_name })
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for PathVisitor<'_> {
+    type Value = Outcome;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Outcome, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+macro_rules! impl_visit_scalar {
+    ($($method:ident($ty:ty) => $variant:ident),* $(,)?) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<Outcome, E> {
+                Ok(self.scalar(FieldScalarValue::$variant(v.into())))
+            }
+        )*
+    };
+}
+
+impl<'de> Visitor<'de> for PathVisitor<'_> {
+    type Value = Outcome;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+
+    impl_visit_scalar! {
+        visit_bool(bool) => Bool,
+        visit_i8(i8) => I8,
+        visit_i16(i16) => I16,
+        visit_i32(i32) => I32,
+        visit_i64(i64) => I64,
+        visit_i128(i128) => I128,
+        visit_u8(u8) => U8,
+        visit_u16(u16) => U16,
+        visit_u32(u32) => U32,
+        visit_u64(u64) => U64,
+        visit_u128(u128) => U128,
+        visit_f32(f32) => F32,
+        visit_f64(f64) => F64,
+        visit_char(char) => Char,
+        visit_str(&str) => String,
+        visit_string(String) => String,
+        visit_bytes(&[u8]) => Bytes,
+        visit_byte_buf(Vec<u8>) => Bytes,
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Outcome, E> {
+        Ok(self.scalar(FieldScalarValue::Unit))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Outcome, E> {
+        Ok(self.scalar(FieldScalarValue::Option(None)))
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Outcome, D::Error> {
+        let outcome = deserializer.deserialize_any(self)?;
+        Ok(match outcome {
+            Outcome::Found(value) if self.is_target() => {
+                Outcome::Found(FieldScalarValue::Option(Some(Box::new(value))))
+            }
+            outcome => outcome,
+        })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Outcome, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Outcome, A::Error> {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(self.unsupported(
+            EvaluateError::unsupported_type("sequence", None, "scalar"),
+            "sequence",
+        ))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Outcome, A::Error> {
+        if self.is_target() {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            return Ok(Outcome::Failed(
+                EvaluateError::unsupported_type("map", None, "scalar").at_path(self.path),
+            ));
+        }
+
+        let matcher = KeyMatcher {
+            segment: &self.path[self.depth],
+            aliases: aliases_of(self.aliases, self.depth),
+            matching: self.matching,
+        };
+        let mut outcome = Outcome::NotFound(self.depth, NotFoundReason::MissingKey);
+        let mut first_entry = None;
+        let mut entry = 0;
+        while let Some(matched) = map.next_key_seed(matcher)? {
+            match (matched, first_entry) {
+                (true, None) => {
+                    first_entry = Some(entry);
+                    outcome = map.next_value_seed(PathVisitor {
+                        depth: self.depth + 1,
+                        ..self
+                    })?;
+                }
+                // A later duplicate fails the path under `DuplicateKeyPolicy::Error`,
+                // unless it already failed; otherwise the first matching key wins.
+                (true, Some(first_entry))
+                    if self.duplicates == DuplicateKeyPolicy::Error
+                        && !matches!(outcome, Outcome::Failed(_)) =>
+                {
+                    map.next_value::<IgnoredAny>()?;
+                    outcome = Outcome::Failed(EvaluateError::DuplicateKey {
+                        path: self.path.to_vec(),
+                        failed_at_index: self.depth,
+                        first_entry,
+                        second_entry: entry,
+                    });
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+            entry += 1;
+        }
+        Ok(outcome)
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Outcome, A::Error> {
+        IgnoredAny.visit_enum(data)?;
+        Ok(self.unsupported(
+            EvaluateError::unsupported_variant("enum", None, None, "scalar"),
+            "enum",
+        ))
+    }
+}
+
+/// Deserializes a map key and reports whether it matches `segment` or one of its
+/// `aliases`. Integer and bool keys match by their `to_string`, like map keys of
+/// serialized records; other keys never match.
+#[derive(Clone, Copy)]
+struct KeyMatcher<'s> {
+    segment: &'s str,
+    aliases: &'s [String],
+    matching: KeyMatching,
+}
+
+impl<'de> DeserializeSeed<'de> for KeyMatcher<'_> {
+    type Value = bool;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeyMatcher<'_> {
+    type Value = bool;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map key")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<bool, E> {
+        Ok(self.matching.matches_any(self.segment, self.aliases, v))
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<bool, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<bool, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<bool, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<bool, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<bool, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<bool, E> {
+        Ok(false)
+    }
+
+    fn visit_bytes<E: de::Error>(self, _: &[u8]) -> Result<bool, E> {
+        Ok(false)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<bool, E> {
+        Ok(false)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<bool, E> {
+        Ok(false)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<bool, A::Error> {
+        IgnoredAny.visit_seq(seq)?;
+        Ok(false)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<bool, A::Error> {
+        IgnoredAny.visit_map(map)?;
+        Ok(false)
+    }
+}
@@ -0,0 +1,97 @@
+//! Order-preserving binary encoding of [`FieldScalarValue`] tuples.
+//!
+//! Each value is written as a one-byte type tag followed by a payload whose byte order
+//! matches the value order, so comparing two encoded tuples with `memcmp` gives the same
+//! result as comparing them element by element with [`FieldScalarValue::total_cmp`].
+//! This makes the output usable as a key in any ordered key-value store.
+//!
+//! * Integers are fixed-width big-endian; signed integers have their sign bit flipped.
+//! * Floats have their sign bit flipped, or every bit flipped if negative.
+//! * Strings and bytes escape `0x00` as `0x00 0xFF` and end with `0x00`. The terminator
+//!   sorts before any continuation: a content byte is either above `0x00` or followed by
+//!   `0xFF`, while the terminator is followed by the next value's type tag or nothing.
+//! * `Option` writes `0x00` for `None`, or `0x01` followed by the inner value.
+
+use crate::value::{FieldScalarValue, ScalarKind};
+
+/// Encodes a tuple of values as an order-preserving key.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_evaluate::{encode_key, FieldScalarValue};
+///
+/// let a = encode_key(&[FieldScalarValue::String("a".to_string()), FieldScalarValue::I32(10)]);
+/// let b = encode_key(&[FieldScalarValue::String("a".to_string()), FieldScalarValue::I32(-1)]);
+/// let c = encode_key(&[FieldScalarValue::String("b".to_string()), FieldScalarValue::I32(-5)]);
+/// assert!(b < a && a < c);
+/// ```
+pub fn encode_key(values: &[FieldScalarValue]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for value in values {
+        encode_value(&mut out, value);
+    }
+    out
+}
+
+/// Appends the encoding of a single value to `out`.
+pub fn encode_value(out: &mut Vec<u8>, value: &FieldScalarValue) {
+    out.push(tag(value.kind()));
+    match value {
+        FieldScalarValue::Unit => {}
+        FieldScalarValue::Bool(v) => out.push(u8::from(*v)),
+        FieldScalarValue::I8(v) => out.push((*v as u8) ^ 0x80),
+        FieldScalarValue::I16(v) => out.extend_from_slice(&((*v as u16) ^ (1 << 15)).to_be_bytes()),
+        FieldScalarValue::I32(v) => out.extend_from_slice(&((*v as u32) ^ (1 << 31)).to_be_bytes()),
+        FieldScalarValue::I64(v) => out.extend_from_slice(&((*v as u64) ^ (1 << 63)).to_be_bytes()),
+        FieldScalarValue::I128(v) => {
+            out.extend_from_slice(&((*v as u128) ^ (1 << 127)).to_be_bytes())
+        }
+        FieldScalarValue::U8(v) => out.push(*v),
+        FieldScalarValue::U16(v) => out.extend_from_slice(&v.to_be_bytes()),
+        FieldScalarValue::U32(v) => out.extend_from_slice(&v.to_be_bytes()),
+        FieldScalarValue::U64(v) => out.extend_from_slice(&v.to_be_bytes()),
+        FieldScalarValue::U128(v) => out.extend_from_slice(&v.to_be_bytes()),
+        FieldScalarValue::F32(v) => {
+            let bits = v.to_bits();
+            let ordered = if bits >> 31 == 1 {
+                !bits
+            } else {
+                bits ^ (1 << 31)
+            };
+            out.extend_from_slice(&ordered.to_be_bytes());
+        }
+        FieldScalarValue::F64(v) => {
+            let bits = v.to_bits();
+            let ordered = if bits >> 63 == 1 {
+                !bits
+            } else {
+                bits ^ (1 << 63)
+            };
+            out.extend_from_slice(&ordered.to_be_bytes());
+        }
+        FieldScalarValue::Char(v) => out.extend_from_slice(&u32::from(*v).to_be_bytes()),
+        FieldScalarValue::String(v) => encode_escaped(out, v.as_bytes()),
+        FieldScalarValue::Bytes(v) => encode_escaped(out, v),
+        FieldScalarValue::Option(None) => out.push(0x00),
+        FieldScalarValue::Option(Some(inner)) => {
+            out.push(0x01);
+            encode_value(out, inner);
+        }
+    }
+}
+
+/// Type tags follow [`ScalarKind`] order, so values of different kinds sort by kind.
+fn tag(kind: ScalarKind) -> u8 {
+    kind as u8 + 1
+}
+
+fn encode_escaped(out: &mut Vec<u8>, bytes: &[u8]) {
+    for &byte in bytes {
+        out.push(byte);
+        if byte == 0x00 {
+            out.push(0xFF);
+        }
+    }
+    out.push(0x00);
+}
@@ -1,22 +1,135 @@
 use serde::ser::Error as SerdeError;
+use std::fmt;
 use thiserror::Error;
 
 /// Helper to format the NestedFieldNotFound error message.
-fn format_nested_field_error(path: &[String], failed_at_index: Option<usize>) -> String {
+fn format_nested_field_error(
+    path: &[String],
+    failed_at_index: usize,
+    reason: &NotFoundReason,
+) -> String {
     let path_str = path.join(".");
-    match failed_at_index {
-        Some(idx) if idx < path.len() => {
-            format!(
-                "Nested field path '{}' not found (failed at segment {}: '{}')",
-                path_str, idx, path[idx]
-            )
+    match path.get(failed_at_index) {
+        Some(segment) => format!(
+            "Nested field path '{}' not found (failed at segment {}: '{}', {})",
+            path_str, failed_at_index, segment, reason
+        ),
+        None => format!("Nested field path '{}' not found ({})", path_str, reason),
+    }
+}
+
+/// Renders where a value was found: the traversed path, or the record itself.
+fn format_location(path: &[String]) -> String {
+    if path.is_empty() {
+        "the record root".to_string()
+    } else {
+        format!("'{}'", path.join("."))
+    }
+}
+
+/// Helper to format the UnsupportedType error message.
+fn format_unsupported_type(
+    type_name: &str,
+    rust_type: Option<&str>,
+    expected: &str,
+    path: &[String],
+) -> String {
+    let found = match rust_type {
+        Some(rust_type) => format!("{} `{}`", type_name, rust_type),
+        None => type_name.to_string(),
+    };
+    format!(
+        "Unsupported type at {}: expected {}, found {}",
+        format_location(path),
+        expected,
+        found
+    )
+}
+
+/// Helper to format the UnsupportedVariant error message.
+fn format_unsupported_variant(
+    variant_type: &str,
+    rust_type: Option<&str>,
+    variant: Option<&str>,
+    expected: &str,
+    path: &[String],
+) -> String {
+    let found = match (rust_type, variant) {
+        (Some(rust_type), Some(variant)) => {
+            format!("{} variant `{}::{}`", variant_type, rust_type, variant)
+        }
+        (None, Some(variant)) => format!("{} variant `{}`", variant_type, variant),
+        _ => format!("{} variant", variant_type),
+    };
+    format!(
+        "Unsupported variant at {}: expected {}, found {}",
+        format_location(path),
+        expected,
+        found
+    )
+}
+
+/// Helper to format the DuplicateKey error message.
+fn format_duplicate_key(
+    path: &[String],
+    failed_at_index: usize,
+    first_entry: usize,
+    second_entry: usize,
+) -> String {
+    format!(
+        "Duplicate key in path '{}' (segment {}: '{}' matches entries {} and {} of the same struct or map)",
+        path.join("."),
+        failed_at_index,
+        path.get(failed_at_index).map_or("", String::as_str),
+        first_entry,
+        second_entry
+    )
+}
+
+/// Why a path segment could not be followed, as reported by
+/// [`EvaluateError::NestedFieldNotFound`]. New reasons may be added in later releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NotFoundReason {
+    /// The struct reached before the segment has no field with that name.
+    MissingField,
+    /// The struct reached before the segment declares the field, but skipped it while
+    /// serializing, e.g. with `#[serde(skip_serializing_if = "Option::is_none")]`.
+    Skipped,
+    /// The map reached before the segment has no key equal to it.
+    MissingKey,
+    /// The value reached before the segment is null: `None`, `()` or JSON `null`.
+    Null,
+    /// The value reached before the segment cannot be traversed.
+    NotTraversable {
+        /// What was found instead: `"scalar"`, `"sequence"`, `"tuple"`,
+        /// `"tuple struct"` or `"enum"`.
+        type_name: &'static str,
+    },
+}
+
+impl fmt::Display for NotFoundReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotFoundReason::MissingField => f.write_str("no such struct field"),
+            NotFoundReason::Skipped => f.write_str("field skipped during serialization"),
+            NotFoundReason::MissingKey => f.write_str("no such map key"),
+            NotFoundReason::Null => f.write_str("cannot traverse null"),
+            NotFoundReason::NotTraversable { type_name } => {
+                write!(f, "cannot traverse {}", type_name)
+            }
         }
-        _ => format!("Nested field path '{}' not found", path_str),
     }
 }
 
 /// Errors that can occur during evaluation.
-#[derive(Error, Debug, PartialEq)]
+///
+/// New variants may be added in later releases, and variants that only this crate
+/// reports may gain fields, so match them with `..`. Custom
+/// [functions](crate::expr::FunctionRegistry) can still build `FunctionError` and
+/// `TypeMismatch`.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum EvaluateError {
     /// The target field specified for extraction was not found in the serialized data.
     #[error("Field '{field_name}' not found in the struct")]
@@ -26,12 +139,15 @@ pub enum EvaluateError {
     },
 
     /// The target nested field specified by the path was not found.
-    #[error("{}", format_nested_field_error(path, *failed_at_index))]
+    #[error("{}", format_nested_field_error(path, *failed_at_index, reason))]
+    #[non_exhaustive]
     NestedFieldNotFound {
         /// The full path segments that were not found.
         path: Vec<String>,
-        /// The index of the path segment where traversal failed (if known).
-        failed_at_index: Option<usize>,
+        /// The index of the first path segment that could not be followed.
+        failed_at_index: usize,
+        /// Why that segment could not be followed.
+        reason: NotFoundReason,
     },
 
     /// An intermediate part of the path pointed to a non-struct type.
@@ -45,29 +161,158 @@ pub enum EvaluateError {
 
     /// The value of the target field has a type that is not supported for scalar extraction.
     /// (e.g., nested structs, sequences other than `Vec<u8>`, maps).
-    #[error("Unsupported type for scalar extraction: {type_name}")]
+    #[error("{}", format_unsupported_type(type_name, *rust_type, expected, path))]
+    #[non_exhaustive]
     UnsupportedType {
-        /// A string representation of the unsupported type name.
+        /// The kind of value found, such as `"struct"`, `"map"` or `"sequence"`.
         type_name: &'static str,
+        /// The Rust type name reported by the value's `Serialize` impl, if any.
+        /// Structs, tuple structs and newtype structs have one; maps and sequences do not.
+        rust_type: Option<&'static str>,
+        /// The kind of value that was expected, such as `"scalar"` or `"list"`.
+        expected: &'static str,
+        /// The path segments traversed to reach the value.
+        path: Vec<String>,
     },
 
     /// The value of the target field has a variant type that is not supported for scalar extraction.
-    #[error("Unsupported variant type for scalar extraction: {variant_type}")]
+    #[error(
+        "{}",
+        format_unsupported_variant(variant_type, *rust_type, *variant, expected, path)
+    )]
+    #[non_exhaustive]
     UnsupportedVariant {
-        /// A string representation of the unsupported variant type.
+        /// The shape of the variant: `"newtype"`, `"tuple"`, `"struct"`, or `"enum"`
+        /// when the shape is unknown.
         variant_type: &'static str,
+        /// The name of the enum, if the value's `Serialize` impl reports it.
+        rust_type: Option<&'static str>,
+        /// The name of the variant, if the value's `Serialize` impl reports it.
+        variant: Option<&'static str>,
+        /// The kind of value that was expected, such as `"scalar"` or `"struct or map"`
+        /// for a variant in the middle of a path.
+        expected: &'static str,
+        /// The path segments traversed to reach the value.
+        path: Vec<String>,
+    },
+
+    /// A path segment matched two entries of the same struct or map, e.g. a key written
+    /// both by a `#[serde(flatten)]` field and by the enclosing struct. Only reported
+    /// with [`DuplicateKeyPolicy::Error`](crate::DuplicateKeyPolicy::Error).
+    #[error(
+        "{}",
+        format_duplicate_key(path, *failed_at_index, *first_entry, *second_entry)
+    )]
+    #[non_exhaustive]
+    DuplicateKey {
+        /// The full path segments being extracted.
+        path: Vec<String>,
+        /// The index of the path segment that matched twice.
+        failed_at_index: usize,
+        /// The position of the first matching entry among the entries of the struct
+        /// or map, counting from 0.
+        first_entry: usize,
+        /// The position of the second matching entry.
+        second_entry: usize,
     },
 
     /// The provided path string or segments were invalid (e.g., empty or contained empty segments).
     #[error("Invalid field path provided: {0}")]
     InvalidPath(String),
 
+    /// An expression could not be parsed.
+    #[error("Invalid expression at position {position}: {message}")]
+    ExpressionSyntax {
+        /// What was expected or found.
+        message: String,
+        /// The byte offset in the expression where parsing failed.
+        position: usize,
+    },
+
+    /// A sub-expression failed while being evaluated against a record.
+    #[error("Expression '{expression}' failed: {source}")]
+    #[non_exhaustive]
+    ExpressionFailed {
+        /// The text of the innermost sub-expression that failed.
+        expression: String,
+        /// The underlying error.
+        source: Box<EvaluateError>,
+    },
+
+    /// Values of incompatible types were combined in an expression.
+    #[error("Type mismatch: {message}")]
+    TypeMismatch {
+        /// A description of the mismatch.
+        message: String,
+    },
+
+    /// A function called from an expression rejected its arguments.
+    #[error("Function '{function}' failed: {message}")]
+    FunctionError {
+        /// The name of the function.
+        function: String,
+        /// A description of the failure.
+        message: String,
+    },
+
     /// An underlying Serde serialization error occurred.
     #[error("Serialization error: {message}")]
     SerializationError {
         /// A custom error message from serde::ser::Error::custom.
         message: String,
     },
+
+    /// The deserializer failed while an encoded record was being walked.
+    #[error("Deserialization error: {message}")]
+    DeserializationError {
+        /// The deserializer's error message.
+        message: String,
+    },
+}
+
+impl EvaluateError {
+    /// Builds an `UnsupportedType` error with an empty path, to be filled by
+    /// [`EvaluateError::at_path`].
+    pub(crate) fn unsupported_type(
+        type_name: &'static str,
+        rust_type: Option<&'static str>,
+        expected: &'static str,
+    ) -> Self {
+        EvaluateError::UnsupportedType {
+            type_name,
+            rust_type,
+            expected,
+            path: Vec::new(),
+        }
+    }
+
+    /// Builds an `UnsupportedVariant` error with an empty path, to be filled by
+    /// [`EvaluateError::at_path`].
+    pub(crate) fn unsupported_variant(
+        variant_type: &'static str,
+        rust_type: Option<&'static str>,
+        variant: Option<&'static str>,
+        expected: &'static str,
+    ) -> Self {
+        EvaluateError::UnsupportedVariant {
+            variant_type,
+            rust_type,
+            variant,
+            expected,
+            path: Vec::new(),
+        }
+    }
+
+    /// Sets the traversed path of an `UnsupportedType` or `UnsupportedVariant` error.
+    /// Other errors are returned unchanged.
+    pub(crate) fn at_path(mut self, traversed: &[String]) -> Self {
+        if let EvaluateError::UnsupportedType { path, .. }
+        | EvaluateError::UnsupportedVariant { path, .. } = &mut self
+        {
+            *path = traversed.to_vec();
+        }
+        self
+    }
 }
 
 impl SerdeError for EvaluateError {
@@ -0,0 +1,74 @@
+use super::functions::ScalarFunction;
+use crate::extractor::{NestedFieldExtractor, NestedListFieldExtractor};
+use crate::value::FieldScalarValue;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// A node of a parsed expression, with the byte range it was parsed from.
+#[derive(Debug, Clone)]
+pub(crate) struct Expr {
+    pub(crate) kind: ExprKind,
+    pub(crate) span: Range<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ExprKind {
+    /// A string, number, boolean or `null` literal.
+    Literal(FieldScalarValue),
+    /// A list literal: `[a, b, c]`.
+    List(Vec<Expr>),
+    /// A path into the record, such as `customer.address["zip code"]`.
+    Path(PathExpr),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+    /// `value in list`
+    In(Box<Expr>, Box<Expr>),
+    /// `list contains value` or `string contains substring`
+    Contains(Box<Expr>, Box<Expr>),
+    /// `exists(path)`
+    Exists(NestedFieldExtractor),
+    /// `is_null(path)`
+    IsNull(NestedFieldExtractor),
+    /// A call to a function from the [`FunctionRegistry`](super::FunctionRegistry).
+    Call(Call),
+}
+
+/// A resolved function call.
+#[derive(Clone)]
+pub(crate) struct Call {
+    pub(crate) name: String,
+    pub(crate) function: Arc<ScalarFunction>,
+    /// Whether path arguments missing from the record are passed as `null`.
+    pub(crate) missing_as_null: bool,
+    pub(crate) args: Vec<Expr>,
+}
+
+impl fmt::Debug for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Call")
+            .field("name", &self.name)
+            .field("args", &self.args)
+            .finish()
+    }
+}
+
+/// A path operand. Both extractors are built up front; the list one is used when the
+/// path turns out to hold a sequence.
+#[derive(Debug, Clone)]
+pub(crate) struct PathExpr {
+    pub(crate) scalar: NestedFieldExtractor,
+    pub(crate) list: NestedListFieldExtractor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
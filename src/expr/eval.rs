@@ -0,0 +1,411 @@
+use super::ast::{CompareOp, Expr, ExprKind, PathExpr};
+use super::ExprValue as Value;
+use crate::error::EvaluateError;
+use crate::extractor::is_missing;
+use crate::probe::{FieldKind, PathProbe};
+use crate::value::{FieldScalarValue, ScalarKind};
+use serde::Serialize;
+use std::cmp::Ordering;
+
+impl Expr {
+    /// Evaluates this node against `record`.
+    ///
+    /// Errors raised by this node are wrapped in [`EvaluateError::ExpressionFailed`]
+    /// with the text of the node; errors from child nodes are already wrapped and are
+    /// passed through unchanged.
+    pub(crate) fn eval<T: Serialize>(
+        &self,
+        record: &T,
+        source: &str,
+    ) -> Result<Value, EvaluateError> {
+        match &self.kind {
+            ExprKind::Literal(value) => Ok(Value::Scalar(value.clone())),
+            ExprKind::List(items) => items
+                .iter()
+                .map(|item| self.expect_scalar(item.eval(record, source)?, source))
+                .collect::<Result<_, _>>()
+                .map(Value::List),
+            ExprKind::Path(path) => self.eval_path(path, record, source, false),
+            ExprKind::Not(inner) => Ok(bool_value(!inner.eval_bool(record, source)?)),
+            ExprKind::And(left, right) => Ok(bool_value(
+                left.eval_bool(record, source)? && right.eval_bool(record, source)?,
+            )),
+            ExprKind::Or(left, right) => Ok(bool_value(
+                left.eval_bool(record, source)? || right.eval_bool(record, source)?,
+            )),
+            ExprKind::Compare(op, left, right) => {
+                let left = self.expect_scalar(left.eval(record, source)?, source)?;
+                let right = self.expect_scalar(right.eval(record, source)?, source)?;
+                compare(*op, &left, &right)
+                    .map(bool_value)
+                    .map_err(|err| self.fail(source, err))
+            }
+            ExprKind::In(value, list) => {
+                let value = self.expect_scalar(value.eval(record, source)?, source)?;
+                let list = self.expect_list(list.eval(record, source)?, source)?;
+                self.any_equal(&list, &value, source).map(bool_value)
+            }
+            ExprKind::Contains(haystack, needle) => {
+                let haystack = haystack.eval(record, source)?;
+                let needle = self.expect_scalar(needle.eval(record, source)?, source)?;
+                match haystack {
+                    Value::List(list) => self.any_equal(&list, &needle, source).map(bool_value),
+                    Value::Scalar(haystack) => match (unwrap_null(&haystack), unwrap_null(&needle))
+                    {
+                        (None, _) => Ok(bool_value(false)),
+                        (
+                            Some(FieldScalarValue::String(haystack)),
+                            Some(FieldScalarValue::String(needle)),
+                        ) => Ok(bool_value(haystack.contains(needle.as_str()))),
+                        (Some(haystack), _) => Err(self.fail(
+                            source,
+                            type_mismatch(format!(
+                                "'contains' needs a list or a string, found {:?}",
+                                haystack.kind()
+                            )),
+                        )),
+                    },
+                }
+            }
+            ExprKind::Exists(extractor) => extractor
+                .exists(record)
+                .map(|probe| bool_value(probe != PathProbe::Missing))
+                .map_err(|err| self.fail(source, err)),
+            ExprKind::IsNull(extractor) => extractor
+                .exists(record)
+                .map(|probe| {
+                    bool_value(matches!(
+                        probe,
+                        PathProbe::Null
+                            | PathProbe::Present {
+                                kind: FieldKind::Scalar(ScalarKind::Unit)
+                            }
+                    ))
+                })
+                .map_err(|err| self.fail(source, err)),
+            ExprKind::Call(call) => {
+                let args = call
+                    .args
+                    .iter()
+                    .map(|arg| match &arg.kind {
+                        ExprKind::Path(path) => {
+                            arg.eval_path(path, record, source, call.missing_as_null)
+                        }
+                        _ => arg.eval(record, source),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                (call.function)(&args)
+                    .map(Value::Scalar)
+                    .map_err(|err| self.fail(source, err))
+            }
+        }
+    }
+
+    /// Evaluates this node to a single value.
+    pub(crate) fn eval_scalar<T: Serialize>(
+        &self,
+        record: &T,
+        source: &str,
+    ) -> Result<FieldScalarValue, EvaluateError> {
+        self.expect_scalar(self.eval(record, source)?, source)
+    }
+
+    /// Evaluates this node as a condition. `null` counts as `false`.
+    pub(crate) fn eval_bool<T: Serialize>(
+        &self,
+        record: &T,
+        source: &str,
+    ) -> Result<bool, EvaluateError> {
+        let value = self.eval_scalar(record, source)?;
+        match unwrap_null(&value) {
+            None => Ok(false),
+            Some(FieldScalarValue::Bool(value)) => Ok(*value),
+            Some(other) => Err(self.fail(
+                source,
+                type_mismatch(format!("expected a boolean, found {:?}", other.kind())),
+            )),
+        }
+    }
+
+    /// Evaluates a path operand. With `missing_as_null`, a path missing from the record
+    /// evaluates to `null`, as with
+    /// [`MissingPolicy::ReturnNone`](crate::MissingPolicy::ReturnNone).
+    fn eval_path<T: Serialize>(
+        &self,
+        path: &PathExpr,
+        record: &T,
+        source: &str,
+        missing_as_null: bool,
+    ) -> Result<Value, EvaluateError> {
+        match path.scalar.evaluate(record) {
+            Ok(value) if is_unit(&value) => self.name_unit_variant(path, record, value, source),
+            Ok(value) => Ok(Value::Scalar(value)),
+            Err(err @ EvaluateError::UnsupportedType { .. }) => {
+                self.eval_list(path, record, err, source)
+            }
+            Err(err) if missing_as_null && is_missing(&err) => {
+                Ok(Value::Scalar(FieldScalarValue::Option(None)))
+            }
+            Err(err) => Err(self.fail(source, err)),
+        }
+    }
+
+    /// Paths to a sequence, possibly inside `Some(..)`, evaluate to a list. Any other
+    /// value the scalar extractor rejected fails with its error.
+    fn eval_list<T: Serialize>(
+        &self,
+        path: &PathExpr,
+        record: &T,
+        err: EvaluateError,
+        source: &str,
+    ) -> Result<Value, EvaluateError> {
+        let mut kind = path
+            .scalar
+            .describe(record)
+            .map_err(|err| self.fail(source, err))?;
+        while let FieldKind::Option(Some(inner)) = kind {
+            kind = *inner;
+        }
+        if !matches!(kind, FieldKind::Seq { .. }) {
+            return Err(self.fail(source, err));
+        }
+        path.list
+            .evaluate(record)
+            .map(Value::List)
+            .map_err(|err| self.fail(source, err))
+    }
+
+    /// Unit enum variants extract as `Unit`, like `()` and JSON `null`. Probes the path
+    /// to tell them apart, and replaces a unit variant with its name, so that
+    /// `status == "Active"` compares by variant name instead of as `null`.
+    fn name_unit_variant<T: Serialize>(
+        &self,
+        path: &PathExpr,
+        record: &T,
+        value: FieldScalarValue,
+        source: &str,
+    ) -> Result<Value, EvaluateError> {
+        let probe = path
+            .scalar
+            .exists(record)
+            .map_err(|err| self.fail(source, err))?;
+        Ok(Value::Scalar(match probe {
+            PathProbe::Present {
+                kind: FieldKind::Variant { variant, .. },
+            } => replace_unit(value, variant),
+            _ => value,
+        }))
+    }
+
+    fn any_equal(
+        &self,
+        list: &[FieldScalarValue],
+        value: &FieldScalarValue,
+        source: &str,
+    ) -> Result<bool, EvaluateError> {
+        for element in list {
+            if compare(CompareOp::Eq, element, value).map_err(|err| self.fail(source, err))? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn expect_scalar(&self, value: Value, source: &str) -> Result<FieldScalarValue, EvaluateError> {
+        match value {
+            Value::Scalar(value) => Ok(value),
+            Value::List(_) => Err(self.fail(
+                source,
+                type_mismatch("expected a scalar, found a list".to_string()),
+            )),
+        }
+    }
+
+    fn expect_list(
+        &self,
+        value: Value,
+        source: &str,
+    ) -> Result<Vec<FieldScalarValue>, EvaluateError> {
+        match value {
+            Value::List(list) => Ok(list),
+            // A null list contains nothing.
+            Value::Scalar(value) if unwrap_null(&value).is_none() => Ok(Vec::new()),
+            Value::Scalar(value) => Err(self.fail(
+                source,
+                type_mismatch(format!("expected a list, found {:?}", value.kind())),
+            )),
+        }
+    }
+
+    fn fail(&self, source: &str, err: EvaluateError) -> EvaluateError {
+        EvaluateError::ExpressionFailed {
+            expression: source[self.span.clone()].to_string(),
+            source: Box::new(err),
+        }
+    }
+}
+
+fn bool_value(value: bool) -> Value {
+    Value::Scalar(FieldScalarValue::Bool(value))
+}
+
+fn type_mismatch(message: String) -> EvaluateError {
+    EvaluateError::TypeMismatch { message }
+}
+
+/// Peels `Some(..)` layers. Returns `None` for `Option::None` and for unit, which is
+/// how `serde_json::Value::Null` serializes. Unit enum variants are never unit here:
+/// paths evaluate them to their variant name.
+pub(crate) fn unwrap_null(value: &FieldScalarValue) -> Option<&FieldScalarValue> {
+    match value {
+        FieldScalarValue::Option(None) | FieldScalarValue::Unit => None,
+        FieldScalarValue::Option(Some(inner)) => unwrap_null(inner),
+        value => Some(value),
+    }
+}
+
+/// True if `value` is `Unit`, possibly wrapped in `Some(..)`.
+fn is_unit(value: &FieldScalarValue) -> bool {
+    match value {
+        FieldScalarValue::Unit => true,
+        FieldScalarValue::Option(Some(inner)) => is_unit(inner),
+        _ => false,
+    }
+}
+
+/// Replaces the `Unit` inside `value` with the string `name`, keeping `Some(..)` layers.
+fn replace_unit(value: FieldScalarValue, name: &str) -> FieldScalarValue {
+    match value {
+        FieldScalarValue::Unit => FieldScalarValue::String(name.to_string()),
+        FieldScalarValue::Option(Some(inner)) => {
+            FieldScalarValue::Option(Some(Box::new(replace_unit(*inner, name))))
+        }
+        value => value,
+    }
+}
+
+/// Compares two values.
+///
+/// Numbers compare by value across integer and float types, and chars compare with
+/// strings. `null` equals only `null`, and ordering comparisons involving `null` are
+/// `false`. Other combinations of types are an error.
+fn compare(
+    op: CompareOp,
+    left: &FieldScalarValue,
+    right: &FieldScalarValue,
+) -> Result<bool, EvaluateError> {
+    let (left, right) = match (unwrap_null(left), unwrap_null(right)) {
+        (None, None) => return Ok(op == CompareOp::Eq),
+        (None, _) | (_, None) => return Ok(op == CompareOp::Ne),
+        (Some(left), Some(right)) => (left, right),
+    };
+
+    let ordering = match (left, right) {
+        (FieldScalarValue::Bool(l), FieldScalarValue::Bool(r)) => Some(l.cmp(r)),
+        (FieldScalarValue::Bytes(l), FieldScalarValue::Bytes(r)) => Some(l.cmp(r)),
+        (FieldScalarValue::String(_) | FieldScalarValue::Char(_), _) => {
+            match (as_text(left), as_text(right)) {
+                (Some(l), Some(r)) => Some(l.cmp(&r)),
+                _ => return Err(incomparable(left, right)),
+            }
+        }
+        _ => match (Number::from_value(left), Number::from_value(right)) {
+            (Some(l), Some(r)) => l.partial_cmp(&r),
+            _ => return Err(incomparable(left, right)),
+        },
+    };
+
+    // `None` only for NaN, which compares unequal to everything.
+    Ok(match (op, ordering) {
+        (CompareOp::Ne, None) => true,
+        (_, None) => false,
+        (CompareOp::Eq, Some(ordering)) => ordering == Ordering::Equal,
+        (CompareOp::Ne, Some(ordering)) => ordering != Ordering::Equal,
+        (CompareOp::Lt, Some(ordering)) => ordering == Ordering::Less,
+        (CompareOp::Le, Some(ordering)) => ordering != Ordering::Greater,
+        (CompareOp::Gt, Some(ordering)) => ordering == Ordering::Greater,
+        (CompareOp::Ge, Some(ordering)) => ordering != Ordering::Less,
+    })
+}
+
+fn incomparable(left: &FieldScalarValue, right: &FieldScalarValue) -> EvaluateError {
+    type_mismatch(format!(
+        "cannot compare {:?} with {:?}",
+        left.kind(),
+        right.kind()
+    ))
+}
+
+fn as_text(value: &FieldScalarValue) -> Option<String> {
+    match value {
+        FieldScalarValue::String(value) => Some(value.clone()),
+        FieldScalarValue::Char(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Compares two numbers by value across integer and float types, or returns `None` if
+/// either is not a number. `NaN` is ordered as by [`f64::total_cmp`], so the result is
+/// a total order; values that are equal by value, such as `3` and `3.0`, are `Equal`.
+pub(crate) fn compare_numbers(
+    left: &FieldScalarValue,
+    right: &FieldScalarValue,
+) -> Option<Ordering> {
+    let (left, right) = (Number::from_value(left)?, Number::from_value(right)?);
+    Some(
+        left.partial_cmp(&right)
+            .unwrap_or_else(|| left.as_f64().total_cmp(&right.as_f64())),
+    )
+}
+
+/// A numeric value widened for cross-type comparison.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Int(i128),
+    /// An unsigned value above `i128::MAX`.
+    BigUint(u128),
+    Float(f64),
+}
+
+impl Number {
+    fn from_value(value: &FieldScalarValue) -> Option<Self> {
+        Some(match *value {
+            FieldScalarValue::I8(v) => Number::Int(v.into()),
+            FieldScalarValue::I16(v) => Number::Int(v.into()),
+            FieldScalarValue::I32(v) => Number::Int(v.into()),
+            FieldScalarValue::I64(v) => Number::Int(v.into()),
+            FieldScalarValue::I128(v) => Number::Int(v),
+            FieldScalarValue::U8(v) => Number::Int(v.into()),
+            FieldScalarValue::U16(v) => Number::Int(v.into()),
+            FieldScalarValue::U32(v) => Number::Int(v.into()),
+            FieldScalarValue::U64(v) => Number::Int(v.into()),
+            FieldScalarValue::U128(v) => match i128::try_from(v) {
+                Ok(v) => Number::Int(v),
+                Err(_) => Number::BigUint(v),
+            },
+            FieldScalarValue::F32(v) => Number::Float(v.into()),
+            FieldScalarValue::F64(v) => Number::Float(v),
+            _ => return None,
+        })
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(v) => v as f64,
+            Number::BigUint(v) => v as f64,
+            Number::Float(v) => v,
+        }
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Int(l), Number::Int(r)) => Some(l.cmp(r)),
+            (Number::BigUint(l), Number::BigUint(r)) => Some(l.cmp(r)),
+            (Number::Int(_), Number::BigUint(_)) => Some(Ordering::Less),
+            (Number::BigUint(_), Number::Int(_)) => Some(Ordering::Greater),
+            _ => self.as_f64().partial_cmp(&other.as_f64()),
+        }
+    }
+}
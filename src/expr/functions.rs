@@ -0,0 +1,368 @@
+use super::eval::unwrap_null;
+use super::ExprValue;
+use crate::error::EvaluateError;
+use crate::hash;
+use crate::value::FieldScalarValue;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// The signature of a function callable from an expression.
+///
+/// Arguments are evaluated before the call; a path holding a `Vec<T>` of scalars is
+/// passed as [`ExprValue::List`].
+pub type ScalarFunction =
+    dyn Fn(&[ExprValue]) -> Result<FieldScalarValue, EvaluateError> + Send + Sync;
+
+/// The functions available to [`Expression`](super::Expression) and
+/// [`Predicate`](super::Predicate), looked up by name when an expression is parsed.
+///
+/// [`FunctionRegistry::new`] starts with the built-in functions. `null` arguments
+/// (`None`, JSON `null`) produce `null` unless noted otherwise.
+///
+/// | function                  | result                                                       |
+/// |---------------------------|--------------------------------------------------------------|
+/// | `lower(s)`, `upper(s)`    | `s` in lower or upper case                                   |
+/// | `truncate(n, step)`       | `n` rounded down to a multiple of `step`, keeping `n`'s type |
+/// | `len(x)`                  | elements of a list, characters of a string, bytes of bytes   |
+/// | `concat(a, b, ..)`        | the arguments' text joined together                          |
+/// | `coalesce(a, b, ..)`      | the first non-null argument, or `null` (see below)           |
+/// | `hash64(x)`               | a stable 64-bit FNV-1a hash of `x`'s key encoding            |
+///
+/// A path passed to the built-in `coalesce` that is missing from the record, such as a
+/// field skipped with `skip_serializing_if`, counts as `null` instead of failing the
+/// expression, so `coalesce(nick, name)` falls back to `name`. A path that does not fit
+/// the record's shape still fails, as with [`MissingPolicy`](crate::MissingPolicy).
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{EvaluateError, Expression, ExprValue, FieldScalarValue, FunctionRegistry};
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let mut registry = FunctionRegistry::new();
+///     registry.register("initial", |args: &[ExprValue]| match args {
+///         [ExprValue::Scalar(FieldScalarValue::String(s))] => {
+///             Ok(FieldScalarValue::Char(s.chars().next().unwrap_or(' ')))
+///         }
+///         _ => Err(EvaluateError::FunctionError {
+///             function: "initial".to_string(),
+///             message: "expected one string".to_string(),
+///         }),
+///     });
+///
+///     let expression = Expression::with_registry("initial(name)", &registry)?;
+///     let user = User { name: "alice".to_string() };
+///     assert_eq!(expression.evaluate(&user)?, FieldScalarValue::Char('a'));
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct FunctionRegistry {
+    functions: HashMap<String, Function>,
+}
+
+/// A registered function.
+#[derive(Clone)]
+pub(crate) struct Function {
+    pub(crate) body: Arc<ScalarFunction>,
+    /// Whether path arguments missing from the record are passed as `null`.
+    pub(crate) missing_as_null: bool,
+}
+
+impl fmt::Debug for FunctionRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&String> = self.functions.keys().collect();
+        names.sort();
+        f.debug_struct("FunctionRegistry")
+            .field("functions", &names)
+            .finish()
+    }
+}
+
+impl Default for FunctionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FunctionRegistry {
+    /// Creates a registry holding the built-in functions.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register("lower", lower);
+        registry.register("upper", upper);
+        registry.register("truncate", truncate);
+        registry.register("len", len);
+        registry.register("concat", concat);
+        registry.register("coalesce", coalesce);
+        registry.register("hash64", hash64);
+        if let Some(coalesce) = registry.functions.get_mut("coalesce") {
+            coalesce.missing_as_null = true;
+        }
+        registry
+    }
+
+    /// Creates a registry with no functions.
+    pub(crate) fn empty() -> Self {
+        FunctionRegistry {
+            functions: HashMap::new(),
+        }
+    }
+
+    /// Registers `function` under `name`, replacing any function with the same name.
+    ///
+    /// Functions should report bad arguments with [`EvaluateError::FunctionError`].
+    pub fn register<S, F>(&mut self, name: S, function: F)
+    where
+        S: Into<String>,
+        F: Fn(&[ExprValue]) -> Result<FieldScalarValue, EvaluateError> + Send + Sync + 'static,
+    {
+        let function = Function {
+            body: Arc::new(function),
+            missing_as_null: false,
+        };
+        self.functions.insert(name.into(), function);
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<Function> {
+        self.functions.get(name).cloned()
+    }
+}
+
+// =============================================================================
+// Built-in functions
+// =============================================================================
+
+fn function_error(function: &str, message: impl Into<String>) -> EvaluateError {
+    EvaluateError::FunctionError {
+        function: function.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Checks the argument count and unwraps `Some(..)` around scalar arguments.
+/// `None` (or JSON `null`) becomes `Option::None` in the returned slice.
+fn scalar_args<'a>(
+    function: &str,
+    args: &'a [ExprValue],
+    count: usize,
+) -> Result<Vec<Option<&'a FieldScalarValue>>, EvaluateError> {
+    if args.len() != count {
+        return Err(function_error(
+            function,
+            format!("expected {} argument(s), got {}", count, args.len()),
+        ));
+    }
+    args.iter()
+        .map(|arg| match arg {
+            ExprValue::Scalar(value) => Ok(unwrap_null(value)),
+            ExprValue::List(_) => Err(function_error(function, "expected a scalar, found a list")),
+        })
+        .collect()
+}
+
+fn null() -> FieldScalarValue {
+    FieldScalarValue::Option(None)
+}
+
+fn lower(args: &[ExprValue]) -> Result<FieldScalarValue, EvaluateError> {
+    map_string("lower", args, str::to_lowercase)
+}
+
+fn upper(args: &[ExprValue]) -> Result<FieldScalarValue, EvaluateError> {
+    map_string("upper", args, str::to_uppercase)
+}
+
+fn map_string(
+    function: &str,
+    args: &[ExprValue],
+    map: fn(&str) -> String,
+) -> Result<FieldScalarValue, EvaluateError> {
+    match scalar_args(function, args, 1)?[0] {
+        None => Ok(null()),
+        Some(FieldScalarValue::String(s)) => Ok(FieldScalarValue::String(map(s))),
+        Some(FieldScalarValue::Char(c)) => Ok(FieldScalarValue::String(map(&c.to_string()))),
+        Some(other) => Err(function_error(
+            function,
+            format!("expected a string, found {:?}", other.kind()),
+        )),
+    }
+}
+
+fn truncate(args: &[ExprValue]) -> Result<FieldScalarValue, EvaluateError> {
+    let args = scalar_args("truncate", args, 2)?;
+    let (value, step) = match (args[0], args[1]) {
+        (None, _) => return Ok(null()),
+        (Some(value), Some(step)) => (value, step),
+        (Some(_), None) => return Err(function_error("truncate", "step must not be null")),
+    };
+
+    macro_rules! floor_int {
+        ($variant:ident, $ty:ty, $v:expr) => {{
+            let step = int_step::<$ty>(step)?;
+            $v.checked_sub($v.rem_euclid(step))
+                .map(FieldScalarValue::$variant)
+                .ok_or_else(|| function_error("truncate", "result out of range"))
+        }};
+    }
+
+    match *value {
+        FieldScalarValue::I8(v) => floor_int!(I8, i8, v),
+        FieldScalarValue::I16(v) => floor_int!(I16, i16, v),
+        FieldScalarValue::I32(v) => floor_int!(I32, i32, v),
+        FieldScalarValue::I64(v) => floor_int!(I64, i64, v),
+        FieldScalarValue::I128(v) => floor_int!(I128, i128, v),
+        FieldScalarValue::U8(v) => floor_int!(U8, u8, v),
+        FieldScalarValue::U16(v) => floor_int!(U16, u16, v),
+        FieldScalarValue::U32(v) => floor_int!(U32, u32, v),
+        FieldScalarValue::U64(v) => floor_int!(U64, u64, v),
+        FieldScalarValue::U128(v) => floor_int!(U128, u128, v),
+        FieldScalarValue::F32(v) => {
+            let step = float_step(step)? as f32;
+            Ok(FieldScalarValue::F32((v / step).floor() * step))
+        }
+        FieldScalarValue::F64(v) => {
+            let step = float_step(step)?;
+            Ok(FieldScalarValue::F64((v / step).floor() * step))
+        }
+        ref other => Err(function_error(
+            "truncate",
+            format!("expected a number, found {:?}", other.kind()),
+        )),
+    }
+}
+
+/// Converts `step` to the integer type of the truncated value; it must be positive.
+fn int_step<T>(step: &FieldScalarValue) -> Result<T, EvaluateError>
+where
+    T: TryFrom<i128> + TryFrom<u128> + Default + PartialOrd,
+{
+    let converted = match *step {
+        FieldScalarValue::I8(v) => <T as TryFrom<i128>>::try_from(i128::from(v)).ok(),
+        FieldScalarValue::I16(v) => <T as TryFrom<i128>>::try_from(i128::from(v)).ok(),
+        FieldScalarValue::I32(v) => <T as TryFrom<i128>>::try_from(i128::from(v)).ok(),
+        FieldScalarValue::I64(v) => <T as TryFrom<i128>>::try_from(i128::from(v)).ok(),
+        FieldScalarValue::I128(v) => <T as TryFrom<i128>>::try_from(v).ok(),
+        FieldScalarValue::U8(v) => <T as TryFrom<u128>>::try_from(u128::from(v)).ok(),
+        FieldScalarValue::U16(v) => <T as TryFrom<u128>>::try_from(u128::from(v)).ok(),
+        FieldScalarValue::U32(v) => <T as TryFrom<u128>>::try_from(u128::from(v)).ok(),
+        FieldScalarValue::U64(v) => <T as TryFrom<u128>>::try_from(u128::from(v)).ok(),
+        FieldScalarValue::U128(v) => <T as TryFrom<u128>>::try_from(v).ok(),
+        _ => None,
+    };
+    converted
+        .filter(|step| *step > T::default())
+        .ok_or_else(|| {
+            function_error(
+                "truncate",
+                "step must be a positive integer that fits the value's type",
+            )
+        })
+}
+
+fn float_step(step: &FieldScalarValue) -> Result<f64, EvaluateError> {
+    let step = match *step {
+        FieldScalarValue::F32(v) => f64::from(v),
+        FieldScalarValue::F64(v) => v,
+        FieldScalarValue::I8(v) => f64::from(v),
+        FieldScalarValue::I16(v) => f64::from(v),
+        FieldScalarValue::I32(v) => f64::from(v),
+        FieldScalarValue::I64(v) => v as f64,
+        FieldScalarValue::I128(v) => v as f64,
+        FieldScalarValue::U8(v) => f64::from(v),
+        FieldScalarValue::U16(v) => f64::from(v),
+        FieldScalarValue::U32(v) => f64::from(v),
+        FieldScalarValue::U64(v) => v as f64,
+        FieldScalarValue::U128(v) => v as f64,
+        _ => f64::NAN,
+    };
+    if step > 0.0 {
+        Ok(step)
+    } else {
+        Err(function_error("truncate", "step must be a positive number"))
+    }
+}
+
+fn len(args: &[ExprValue]) -> Result<FieldScalarValue, EvaluateError> {
+    let length = match args {
+        [ExprValue::List(list)] => list.len(),
+        [ExprValue::Scalar(value)] => match unwrap_null(value) {
+            None => return Ok(null()),
+            Some(FieldScalarValue::String(s)) => s.chars().count(),
+            Some(FieldScalarValue::Bytes(b)) => b.len(),
+            Some(other) => {
+                return Err(function_error(
+                    "len",
+                    format!("expected a list, string or bytes, found {:?}", other.kind()),
+                ))
+            }
+        },
+        _ => {
+            return Err(function_error(
+                "len",
+                format!("expected 1 argument(s), got {}", args.len()),
+            ))
+        }
+    };
+    Ok(FieldScalarValue::U64(length as u64))
+}
+
+fn concat(args: &[ExprValue]) -> Result<FieldScalarValue, EvaluateError> {
+    let args = scalar_args("concat", args, args.len())?;
+    let mut out = String::new();
+    for arg in args {
+        let Some(value) = arg else {
+            return Ok(null());
+        };
+        match value {
+            FieldScalarValue::String(s) => out.push_str(s),
+            FieldScalarValue::Char(c) => out.push(*c),
+            FieldScalarValue::Bool(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::I8(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::I16(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::I32(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::I64(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::I128(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::U8(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::U16(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::U32(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::U64(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::U128(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::F32(v) => out.push_str(&v.to_string()),
+            FieldScalarValue::F64(v) => out.push_str(&v.to_string()),
+            other => {
+                return Err(function_error(
+                    "concat",
+                    format!("cannot convert {:?} to text", other.kind()),
+                ))
+            }
+        }
+    }
+    Ok(FieldScalarValue::String(out))
+}
+
+fn coalesce(args: &[ExprValue]) -> Result<FieldScalarValue, EvaluateError> {
+    let args = scalar_args("coalesce", args, args.len())?;
+    Ok(args
+        .into_iter()
+        .flatten()
+        .next()
+        .cloned()
+        .unwrap_or_else(null))
+}
+
+fn hash64(args: &[ExprValue]) -> Result<FieldScalarValue, EvaluateError> {
+    match scalar_args("hash64", args, 1)?[0] {
+        None => Ok(null()),
+        Some(value) => Ok(FieldScalarValue::U64(hash::hash64(std::slice::from_ref(
+            value,
+        )))),
+    }
+}
@@ -0,0 +1,186 @@
+use crate::error::EvaluateError;
+use std::ops::Range;
+
+/// A lexical token of the expression language.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Ident(String),
+    Str(String),
+    Int(i128),
+    Float(f64),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Dot,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+    Not,
+}
+
+/// A token and the byte range it covers in the source.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Spanned {
+    pub(crate) token: Token,
+    pub(crate) span: Range<usize>,
+}
+
+/// Splits an expression into tokens.
+pub(crate) fn tokenize(source: &str) -> Result<Vec<Spanned>, EvaluateError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let start = pos;
+        let c = bytes[pos];
+        let token = match c {
+            b' ' | b'\t' | b'\n' | b'\r' => {
+                pos += 1;
+                continue;
+            }
+            b'(' => single(&mut pos, Token::LParen),
+            b')' => single(&mut pos, Token::RParen),
+            b'[' => single(&mut pos, Token::LBracket),
+            b']' => single(&mut pos, Token::RBracket),
+            b',' => single(&mut pos, Token::Comma),
+            b'.' => single(&mut pos, Token::Dot),
+            b'=' | b'!' | b'<' | b'>' | b'&' | b'|' => lex_operator(bytes, &mut pos)?,
+            b'"' => lex_string(source, &mut pos)?,
+            b'-' | b'0'..=b'9' => lex_number(source, &mut pos)?,
+            c if c == b'_' || c.is_ascii_alphabetic() => {
+                while pos < bytes.len()
+                    && (bytes[pos] == b'_' || bytes[pos].is_ascii_alphanumeric())
+                {
+                    pos += 1;
+                }
+                Token::Ident(source[start..pos].to_string())
+            }
+            _ => {
+                let found = source[start..].chars().next().unwrap_or_default();
+                return Err(syntax_error(
+                    format!("unexpected character '{}'", found),
+                    start,
+                ));
+            }
+        };
+        tokens.push(Spanned {
+            token,
+            span: start..pos,
+        });
+    }
+    Ok(tokens)
+}
+
+pub(crate) fn syntax_error(message: String, position: usize) -> EvaluateError {
+    EvaluateError::ExpressionSyntax { message, position }
+}
+
+fn single(pos: &mut usize, token: Token) -> Token {
+    *pos += 1;
+    token
+}
+
+fn lex_operator(bytes: &[u8], pos: &mut usize) -> Result<Token, EvaluateError> {
+    let start = *pos;
+    let next = bytes.get(start + 1).copied();
+    let (token, len) = match (bytes[start], next) {
+        (b'=', Some(b'=')) => (Token::Eq, 2),
+        (b'!', Some(b'=')) => (Token::Ne, 2),
+        (b'!', _) => (Token::Not, 1),
+        (b'<', Some(b'=')) => (Token::Le, 2),
+        (b'<', _) => (Token::Lt, 1),
+        (b'>', Some(b'=')) => (Token::Ge, 2),
+        (b'>', _) => (Token::Gt, 1),
+        (b'&', Some(b'&')) => (Token::And, 2),
+        (b'|', Some(b'|')) => (Token::Or, 2),
+        (c, _) => {
+            return Err(syntax_error(
+                format!("unexpected character '{}'", c as char),
+                start,
+            ))
+        }
+    };
+    *pos += len;
+    Ok(token)
+}
+
+fn lex_string(source: &str, pos: &mut usize) -> Result<Token, EvaluateError> {
+    let start = *pos;
+    let mut value = String::new();
+    let mut chars = source[start + 1..].char_indices();
+
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '"' => {
+                *pos = start + 1 + offset + 1;
+                return Ok(Token::Str(value));
+            }
+            '\\' => match chars.next() {
+                Some((_, '"')) => value.push('"'),
+                Some((_, '\\')) => value.push('\\'),
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((escape_offset, other)) => {
+                    return Err(syntax_error(
+                        format!("unknown escape '\\{}'", other),
+                        start + 1 + escape_offset,
+                    ))
+                }
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+    Err(syntax_error("unterminated string".to_string(), start))
+}
+
+fn lex_number(source: &str, pos: &mut usize) -> Result<Token, EvaluateError> {
+    let bytes = source.as_bytes();
+    let start = *pos;
+    let mut end = start;
+    if bytes[end] == b'-' {
+        end += 1;
+    }
+    let digits_start = end;
+    let mut is_float = false;
+    while end < bytes.len() {
+        match bytes[end] {
+            b'0'..=b'9' => end += 1,
+            b'.' if !is_float && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) => {
+                is_float = true;
+                end += 1;
+            }
+            b'e' | b'E' => {
+                is_float = true;
+                end += 1;
+                if matches!(bytes.get(end), Some(b'+' | b'-')) {
+                    end += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+    if end == digits_start {
+        return Err(syntax_error(
+            "expected a number after '-'".to_string(),
+            start,
+        ));
+    }
+
+    let text = &source[start..end];
+    let token = if is_float {
+        text.parse().map(Token::Float).ok()
+    } else {
+        text.parse().map(Token::Int).ok()
+    };
+    *pos = end;
+    token.ok_or_else(|| syntax_error(format!("invalid number '{}'", text), start))
+}
@@ -0,0 +1,214 @@
+//! A small expression language evaluated against `Serialize` records.
+//!
+//! Expressions reference record fields by path and are evaluated with the same
+//! extractors as the rest of the crate, so anything a [`NestedFieldExtractor`](crate::NestedFieldExtractor)
+//! can reach can be filtered on.
+//!
+//! ## Syntax
+//!
+//! * **Paths:** `status`, `customer.region`, `labels["team name"]`. Paths cannot index
+//!   into sequences, so `scores[2]` is a syntax error; a path holding a `Vec<T>` of
+//!   scalars evaluates to a list instead.
+//! * **Literals:** `"text"` (with `\"`, `\\`, `\n`, `\t` escapes), `42`, `-1`, `2.5`,
+//!   `1e3`, `true`, `false`, `null`, and lists `["a", "b"]`.
+//! * **Comparisons:** `==`, `!=`, `<`, `<=`, `>`, `>=`. Numbers compare by value across
+//!   integer and float types, and chars compare with strings. Comparing other mixed
+//!   types is an error.
+//! * **Membership:** `value in list`, and `list contains value` or `string contains substring`.
+//! * **Presence:** `exists(path)` is `false` for missing paths; `is_null(path)` is `true`
+//!   for `None` (and JSON `null`), and `false` for missing paths.
+//! * **Functions:** `lower(email)`, `truncate(ts, 3600)`, `len(tags)`, `concat(first, " ", last)`,
+//!   `coalesce(nick, name)`, `hash64(user_id)`, plus any function added to a
+//!   [`FunctionRegistry`].
+//! * **Logic:** `&&`, `||`, `!` and parentheses. `&&` and `||` short-circuit, so
+//!   `exists(score) && score > 10` never evaluates a missing `score`.
+//!
+//! `Some(..)` is looked through, and unit enum variants evaluate to their variant name,
+//! so `status == "Active"` works on an enum field. `null` equals only `null`, ordering
+//! comparisons against `null` are `false`, and a `null` condition counts as `false`.
+//!
+//! A [`Predicate`] evaluates an expression to a boolean, for filtering. An [`Expression`]
+//! evaluates it to a [`FieldScalarValue`], for computed fields such as derived index keys.
+
+mod ast;
+mod eval;
+mod functions;
+mod lexer;
+mod parser;
+
+pub use functions::{FunctionRegistry, ScalarFunction};
+
+pub(crate) use eval::{compare_numbers, unwrap_null};
+pub(crate) use parser::parse_path;
+
+use crate::error::EvaluateError;
+use crate::value::FieldScalarValue;
+use ast::Expr;
+use serde::Serialize;
+
+/// A value passed to an expression function.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprValue {
+    /// A single value, possibly `Option`-wrapped.
+    Scalar(FieldScalarValue),
+    /// The elements of a `Vec<T>` of scalars, or of a list literal.
+    List(Vec<FieldScalarValue>),
+}
+
+/// A compiled boolean expression that filters `Serialize` records.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{EvaluateError, Predicate};
+///
+/// #[derive(Serialize)]
+/// struct Customer {
+///     status: String,
+///     score: f64,
+///     tags: Vec<String>,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let predicate =
+///         Predicate::new(r#"status == "active" && score >= 10.0 && tags contains "vip""#)?;
+///
+///     let customer = Customer {
+///         status: "active".to_string(),
+///         score: 12.5,
+///         tags: vec!["vip".to_string()],
+///     };
+///     assert!(predicate.evaluate(&customer)?);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Predicate {
+    source: String,
+    root: Expr,
+}
+
+impl Predicate {
+    /// Parses `expression` into a predicate, with the built-in functions.
+    /// See the [module documentation](self) for the syntax.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::ExpressionSyntax` with the byte offset of the problem if
+    /// the expression cannot be parsed or calls an unknown function, or
+    /// `EvaluateError::InvalidPath` if a path has an empty segment.
+    pub fn new<S: Into<String>>(expression: S) -> Result<Self, EvaluateError> {
+        Self::with_registry(expression, &FunctionRegistry::new())
+    }
+
+    /// Parses `expression` into a predicate, resolving functions in `registry`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Predicate::new`].
+    pub fn with_registry<S: Into<String>>(
+        expression: S,
+        registry: &FunctionRegistry,
+    ) -> Result<Self, EvaluateError> {
+        let source = expression.into();
+        let root = parser::parse(&source, registry)?;
+        Ok(Predicate { source, root })
+    }
+
+    /// Returns the expression this predicate was parsed from.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the predicate against `record`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::ExpressionFailed` naming the innermost sub-expression that
+    /// failed, with the underlying error as its source. Typical causes are a path that
+    /// does not exist ([`EvaluateError::NestedFieldNotFound`]) or operands of
+    /// incompatible types ([`EvaluateError::TypeMismatch`]).
+    pub fn evaluate<T: Serialize>(&self, record: &T) -> Result<bool, EvaluateError> {
+        self.root.eval_bool(record, &self.source)
+    }
+}
+
+/// A compiled expression that computes a [`FieldScalarValue`] from a `Serialize` record.
+///
+/// Uses the same syntax as [`Predicate`]; typically a function call over paths.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{EvaluateError, Expression, FieldScalarValue};
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     first: String,
+///     last: String,
+///     email: String,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let user = User {
+///         first: "Ada".to_string(),
+///         last: "Lovelace".to_string(),
+///         email: "Ada@Example.com".to_string(),
+///     };
+///
+///     let email = Expression::new("lower(email)")?;
+///     assert_eq!(email.evaluate(&user)?, FieldScalarValue::String("ada@example.com".to_string()));
+///
+///     let full_name = Expression::new(r#"concat(first, " ", last)"#)?;
+///     assert_eq!(full_name.evaluate(&user)?, FieldScalarValue::String("Ada Lovelace".to_string()));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Expression {
+    source: String,
+    root: Expr,
+}
+
+impl Expression {
+    /// Parses `expression`, with the built-in functions.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Predicate::new`].
+    pub fn new<S: Into<String>>(expression: S) -> Result<Self, EvaluateError> {
+        Self::with_registry(expression, &FunctionRegistry::new())
+    }
+
+    /// Parses `expression`, resolving functions in `registry`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Predicate::new`].
+    pub fn with_registry<S: Into<String>>(
+        expression: S,
+        registry: &FunctionRegistry,
+    ) -> Result<Self, EvaluateError> {
+        let source = expression.into();
+        let root = parser::parse(&source, registry)?;
+        Ok(Expression { source, root })
+    }
+
+    /// Returns the expression this was parsed from.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the expression against `record`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::ExpressionFailed` naming the innermost sub-expression that
+    /// failed, with the underlying error (such as [`EvaluateError::FunctionError`]) as its
+    /// source. An expression that evaluates to a list is a [`EvaluateError::TypeMismatch`].
+    pub fn evaluate<T: Serialize>(&self, record: &T) -> Result<FieldScalarValue, EvaluateError> {
+        self.root.eval_scalar(record, &self.source)
+    }
+}
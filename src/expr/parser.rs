@@ -0,0 +1,345 @@
+use super::ast::{Call, CompareOp, Expr, ExprKind, PathExpr};
+use super::functions::FunctionRegistry;
+use super::lexer::{syntax_error, tokenize, Spanned, Token};
+use crate::error::EvaluateError;
+use crate::extractor::{NestedFieldExtractor, NestedListFieldExtractor};
+use crate::value::FieldScalarValue;
+
+/// Parses a complete expression.
+///
+/// Grammar, from lowest to highest precedence:
+///
+/// ```text
+/// or         := and ("||" and)*
+/// and        := unary ("&&" unary)*
+/// unary      := "!" unary | comparison
+/// comparison := operand (("==" | "!=" | "<" | "<=" | ">" | ">=" | "in" | "contains") operand)?
+/// operand    := literal | "[" (operand ("," operand)*)? "]" | "(" or ")"
+///             | "exists" "(" path ")" | "is_null" "(" path ")"
+///             | ident "(" (or ("," or)*)? ")" | path
+/// path       := ident ("." ident | "[" (string | ident) "]")*
+/// ```
+///
+/// Function names are resolved against `registry` while parsing.
+pub(crate) fn parse(source: &str, registry: &FunctionRegistry) -> Result<Expr, EvaluateError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        end: source.len(),
+        registry,
+    };
+    let expr = parser.parse_or()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) => Err(syntax_error(
+            "unexpected token after expression".to_string(),
+            token.span.start,
+        )),
+    }
+}
+
+/// Parses a standalone path such as `customer.address["zip code"]` into its segments, using the same syntax as paths inside expressions.
+pub(crate) fn parse_path(source: &str) -> Result<Vec<String>, EvaluateError> {
+    let registry = FunctionRegistry::empty();
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        end: source.len(),
+        registry: &registry,
+    };
+    let (segments, _) = parser.parse_path_segments()?;
+    match parser.peek() {
+        None => Ok(segments),
+        Some(token) => Err(syntax_error(
+            "unexpected token after path".to_string(),
+            token.span.start,
+        )),
+    }
+}
+
+struct Parser<'r> {
+    tokens: Vec<Spanned>,
+    pos: usize,
+    /// Length of the source, reported as the position of errors at end of input.
+    end: usize,
+    registry: &'r FunctionRegistry,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Spanned> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_token(&self) -> Option<&Token> {
+        self.peek().map(|spanned| &spanned.token)
+    }
+
+    fn next(&mut self) -> Result<Spanned, EvaluateError> {
+        let spanned =
+            self.tokens.get(self.pos).cloned().ok_or_else(|| {
+                syntax_error("unexpected end of expression".to_string(), self.end)
+            })?;
+        self.pos += 1;
+        Ok(spanned)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek_token() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token, what: &str) -> Result<Spanned, EvaluateError> {
+        let spanned = self.next()?;
+        if spanned.token == token {
+            Ok(spanned)
+        } else {
+            Err(syntax_error(
+                format!("expected {}", what),
+                spanned.span.start,
+            ))
+        }
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek_token(), Some(Token::Ident(ident)) if ident == keyword)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, EvaluateError> {
+        let mut left = self.parse_and()?;
+        while self.eat(&Token::Or) {
+            let right = self.parse_and()?;
+            left = binary(left, right, ExprKind::Or);
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, EvaluateError> {
+        let mut left = self.parse_unary()?;
+        while self.eat(&Token::And) {
+            let right = self.parse_unary()?;
+            left = binary(left, right, ExprKind::And);
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, EvaluateError> {
+        if let Some(Spanned {
+            token: Token::Not,
+            span,
+        }) = self.peek()
+        {
+            let start = span.start;
+            self.pos += 1;
+            let inner = self.parse_unary()?;
+            let span = start..inner.span.end;
+            return Ok(Expr {
+                kind: ExprKind::Not(Box::new(inner)),
+                span,
+            });
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, EvaluateError> {
+        let left = self.parse_operand()?;
+
+        let op = match self.peek_token() {
+            Some(Token::Eq) => Some(CompareOp::Eq),
+            Some(Token::Ne) => Some(CompareOp::Ne),
+            Some(Token::Lt) => Some(CompareOp::Lt),
+            Some(Token::Le) => Some(CompareOp::Le),
+            Some(Token::Gt) => Some(CompareOp::Gt),
+            Some(Token::Ge) => Some(CompareOp::Ge),
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.pos += 1;
+            let right = self.parse_operand()?;
+            return Ok(binary(left, right, |l, r| ExprKind::Compare(op, l, r)));
+        }
+
+        if self.peek_keyword("in") {
+            self.pos += 1;
+            let right = self.parse_operand()?;
+            return Ok(binary(left, right, ExprKind::In));
+        }
+        if self.peek_keyword("contains") {
+            self.pos += 1;
+            let right = self.parse_operand()?;
+            return Ok(binary(left, right, ExprKind::Contains));
+        }
+        Ok(left)
+    }
+
+    fn parse_operand(&mut self) -> Result<Expr, EvaluateError> {
+        let Spanned { token, span } = self.next()?;
+        let kind = match token {
+            Token::Str(value) => ExprKind::Literal(FieldScalarValue::String(value)),
+            Token::Int(value) => ExprKind::Literal(int_literal(value)),
+            Token::Float(value) => ExprKind::Literal(FieldScalarValue::F64(value)),
+            Token::LParen => {
+                let inner = self.parse_or()?;
+                let close = self.expect(Token::RParen, "')'")?;
+                return Ok(Expr {
+                    kind: inner.kind,
+                    span: span.start..close.span.end,
+                });
+            }
+            Token::LBracket => {
+                let mut items = Vec::new();
+                if !self.eat(&Token::RBracket) {
+                    loop {
+                        items.push(self.parse_operand()?);
+                        if self.eat(&Token::Comma) {
+                            continue;
+                        }
+                        self.expect(Token::RBracket, "',' or ']'")?;
+                        break;
+                    }
+                }
+                let end = self.tokens[self.pos - 1].span.end;
+                return Ok(Expr {
+                    kind: ExprKind::List(items),
+                    span: span.start..end,
+                });
+            }
+            Token::Ident(ident) => match ident.as_str() {
+                "true" => ExprKind::Literal(FieldScalarValue::Bool(true)),
+                "false" => ExprKind::Literal(FieldScalarValue::Bool(false)),
+                "null" => ExprKind::Literal(FieldScalarValue::Option(None)),
+                "exists" | "is_null" if self.peek_token() == Some(&Token::LParen) => {
+                    self.pos += 1;
+                    let (segments, _) = self.parse_path_segments()?;
+                    let close = self.expect(Token::RParen, "')'")?;
+                    let extractor = NestedFieldExtractor::new_from_path(&segments)?;
+                    let kind = if ident == "exists" {
+                        ExprKind::Exists(extractor)
+                    } else {
+                        ExprKind::IsNull(extractor)
+                    };
+                    return Ok(Expr {
+                        kind,
+                        span: span.start..close.span.end,
+                    });
+                }
+                _ if self.peek_token() == Some(&Token::LParen) => {
+                    let function = self.registry.get(&ident).ok_or_else(|| {
+                        syntax_error(format!("unknown function '{}'", ident), span.start)
+                    })?;
+                    self.pos += 1;
+                    let mut args = Vec::new();
+                    if !self.eat(&Token::RParen) {
+                        loop {
+                            args.push(self.parse_or()?);
+                            if self.eat(&Token::Comma) {
+                                continue;
+                            }
+                            self.expect(Token::RParen, "',' or ')'")?;
+                            break;
+                        }
+                    }
+                    let end = self.tokens[self.pos - 1].span.end;
+                    return Ok(Expr {
+                        kind: ExprKind::Call(Call {
+                            name: ident,
+                            function: function.body,
+                            missing_as_null: function.missing_as_null,
+                            args,
+                        }),
+                        span: span.start..end,
+                    });
+                }
+                _ => {
+                    // Re-read the identifier as the first segment of a path.
+                    self.pos -= 1;
+                    let (segments, end) = self.parse_path_segments()?;
+                    return Ok(Expr {
+                        kind: ExprKind::Path(PathExpr {
+                            scalar: NestedFieldExtractor::new_from_path(&segments)?,
+                            list: NestedListFieldExtractor::new_from_path(&segments)?,
+                        }),
+                        span: span.start..end,
+                    });
+                }
+            },
+            _ => {
+                return Err(syntax_error(
+                    "expected a value or path".to_string(),
+                    span.start,
+                ))
+            }
+        };
+        Ok(Expr { kind, span })
+    }
+
+    /// Parses a path and returns its segments and the end of its span.
+    fn parse_path_segments(&mut self) -> Result<(Vec<String>, usize), EvaluateError> {
+        let first = self.next()?;
+        let Token::Ident(name) = first.token else {
+            return Err(syntax_error(
+                "expected a path".to_string(),
+                first.span.start,
+            ));
+        };
+        let mut segments = vec![name];
+        let mut end = first.span.end;
+
+        loop {
+            if self.eat(&Token::Dot) {
+                let next = self.next()?;
+                let Token::Ident(name) = next.token else {
+                    return Err(syntax_error(
+                        "expected a field name after '.'".to_string(),
+                        next.span.start,
+                    ));
+                };
+                segments.push(name);
+                end = next.span.end;
+            } else if self.eat(&Token::LBracket) {
+                let key = self.next()?;
+                let segment = match key.token {
+                    Token::Str(key) | Token::Ident(key) => key,
+                    Token::Int(_) => {
+                        return Err(syntax_error(
+                            "sequence indexes are not supported in paths; quote a numeric map key as '[\"2\"]'".to_string(),
+                            key.span.start,
+                        ))
+                    }
+                    _ => {
+                        return Err(syntax_error(
+                            "expected a key inside '[...]'".to_string(),
+                            key.span.start,
+                        ))
+                    }
+                };
+                segments.push(segment);
+                end = self.expect(Token::RBracket, "']'")?.span.end;
+            } else {
+                return Ok((segments, end));
+            }
+        }
+    }
+}
+
+fn binary(left: Expr, right: Expr, kind: impl FnOnce(Box<Expr>, Box<Expr>) -> ExprKind) -> Expr {
+    let span = left.span.start..right.span.end;
+    Expr {
+        kind: kind(Box::new(left), Box::new(right)),
+        span,
+    }
+}
+
+/// Integer literals are `I64` when they fit, then `U64`, then `I128`.
+fn int_literal(value: i128) -> FieldScalarValue {
+    if let Ok(value) = i64::try_from(value) {
+        FieldScalarValue::I64(value)
+    } else if let Ok(value) = u64::try_from(value) {
+        FieldScalarValue::U64(value)
+    } else {
+        FieldScalarValue::I128(value)
+    }
+}
@@ -1,7 +1,14 @@
-use crate::error::EvaluateError;
-use crate::serializer::FieldValueExtractorSerializer;
-use crate::value::FieldScalarValue;
-use serde::Serialize;
+use crate::deserializer::{extract_from, PathSeed};
+use crate::error::{EvaluateError, NotFoundReason};
+use crate::expr::parse_path;
+use crate::hash;
+use crate::path::{aliases_of, KeyMatching};
+use crate::probe::{FieldKind, PathProbe};
+use crate::serializer::{
+    validate, FieldValueExtractorSerializer, MultiPathSerializer, PathNode, PathSlot, SegmentKey,
+};
+use crate::value::{FieldScalarRef, FieldScalarValue};
+use serde::{Deserializer, Serialize};
 
 // =============================================================================
 // Path Validation Helper
@@ -28,6 +35,341 @@ fn validate_path<S: AsRef<str>>(segments: &[S]) -> Result<Vec<String>, EvaluateE
     Ok(segments)
 }
 
+/// Validates and converts the aliases of one segment.
+///
+/// Returns an error if any alias is empty.
+fn validate_aliases<S: AsRef<str>>(aliases: &[S]) -> Result<Vec<String>, EvaluateError> {
+    let aliases: Vec<String> = aliases.iter().map(|s| s.as_ref().to_string()).collect();
+    if aliases.iter().any(|s| s.is_empty()) {
+        return Err(EvaluateError::InvalidPath(
+            "Path segment aliases cannot be empty".to_string(),
+        ));
+    }
+    Ok(aliases)
+}
+
+/// The error for a path whose traversal ended without reaching the target. The
+/// serializers report every miss with its segment and reason, so this is only reached
+/// for a `Serialize` impl that serializes nothing at all.
+fn not_reached(path: &[String]) -> EvaluateError {
+    EvaluateError::NestedFieldNotFound {
+        path: path.to_vec(),
+        failed_at_index: 0,
+        reason: NotFoundReason::MissingField,
+    }
+}
+
+/// Reports a missing one-segment path as `FieldNotFound`, for the top-level extractors.
+/// A skipped field keeps its `NestedFieldNotFound`, so it can be told apart from a typo.
+fn field_not_found(field_name: &str, err: EvaluateError) -> EvaluateError {
+    match err {
+        EvaluateError::NestedFieldNotFound {
+            reason: NotFoundReason::Skipped,
+            ..
+        } => err,
+        EvaluateError::NestedFieldNotFound { .. } => EvaluateError::FieldNotFound {
+            field_name: field_name.to_string(),
+        },
+        err => err,
+    }
+}
+
+// =============================================================================
+// Skipped Fields
+// =============================================================================
+
+/// How an extractor reports a target field that its struct skipped while serializing,
+/// such as an `Option` field with `#[serde(skip_serializing_if = "Option::is_none")]`.
+///
+/// Serde tells the serializer about such fields, so they are reported as
+/// [`NotFoundReason::Skipped`] rather than as a missing field. Only the last segment of
+/// a path is affected: a path through a skipped field is still not found.
+/// Encoded records read with `evaluate_from` carry no trace of skipped fields.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SkippedFieldPolicy {
+    /// Report the field as not found, with [`NotFoundReason::Skipped`].
+    #[default]
+    NotFound,
+    /// Extract the field as `FieldScalarValue::Option(None)`, as if it had been
+    /// serialized.
+    AsNone,
+}
+
+impl SkippedFieldPolicy {
+    /// Returns true if `err` is a skipped target that this policy extracts as `None`.
+    fn yields_none(self, err: &EvaluateError) -> bool {
+        match err {
+            EvaluateError::NestedFieldNotFound {
+                path,
+                failed_at_index,
+                reason: NotFoundReason::Skipped,
+            } => self == SkippedFieldPolicy::AsNone && failed_at_index + 1 == path.len(),
+            _ => false,
+        }
+    }
+}
+
+// =============================================================================
+// Missing Paths
+// =============================================================================
+
+/// What an extractor returns when its path is not found in a record.
+///
+/// A path is missing when it fails with [`EvaluateError::FieldNotFound`], or with
+/// [`EvaluateError::NestedFieldNotFound`] because of a missing field or map key, a
+/// skipped field, or a `None` (or JSON `null`) in the middle of the path: the
+/// [`NotFoundReason`]s `MissingField`, `MissingKey`, `Skipped` and `Null`. Other errors
+/// are always returned: a path through a scalar, sequence or enum variant, or an
+/// unsupported target type, points at a wrong path or schema rather than at absent data.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{
+///     CompositeFieldExtractor, EvaluateError, FieldScalarValue, MissingPolicy,
+///     NestedFieldExtractor,
+/// };
+///
+/// #[derive(Serialize)]
+/// struct Order {
+///     id: u64,
+///     shipping: Option<Shipping>,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Shipping {
+///     carrier: String,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let order = Order { id: 7, shipping: None };
+///
+///     let extractor = CompositeFieldExtractor::from_extractors(vec![
+///         NestedFieldExtractor::new_from_path(&["id"])?,
+///         NestedFieldExtractor::new_from_path(&["shipping", "carrier"])?
+///             .with_missing_paths(MissingPolicy::Default(FieldScalarValue::String(
+///                 "none".to_string(),
+///             ))),
+///     ])?;
+///
+///     assert_eq!(
+///         extractor.evaluate(&order)?,
+///         vec![FieldScalarValue::U64(7), FieldScalarValue::String("none".to_string())]
+///     );
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum MissingPolicy {
+    /// Return the not-found error.
+    #[default]
+    Error,
+    /// Return `FieldScalarValue::Option(None)`.
+    ReturnNone,
+    /// Return the given value.
+    Default(FieldScalarValue),
+}
+
+impl MissingPolicy {
+    /// Returns the value this policy substitutes for `err`, or `err` itself.
+    fn recover(&self, err: EvaluateError) -> Result<FieldScalarValue, EvaluateError> {
+        match self {
+            MissingPolicy::Error => Err(err),
+            _ if !is_missing(&err) => Err(err),
+            MissingPolicy::Default(value) => Ok(value.clone()),
+            MissingPolicy::ReturnNone => Ok(FieldScalarValue::Option(None)),
+        }
+    }
+}
+
+/// True if `err` reports a path that is absent from the record, rather than a path
+/// that does not fit the record's shape.
+pub(crate) fn is_missing(err: &EvaluateError) -> bool {
+    match err {
+        EvaluateError::FieldNotFound { .. } => true,
+        EvaluateError::NestedFieldNotFound { reason, .. } => matches!(
+            reason,
+            NotFoundReason::MissingField
+                | NotFoundReason::MissingKey
+                | NotFoundReason::Skipped
+                | NotFoundReason::Null
+        ),
+        _ => false,
+    }
+}
+
+// =============================================================================
+// Duplicate Keys
+// =============================================================================
+
+/// How an extractor handles a path segment that matches two entries of the same
+/// struct or map.
+///
+/// A well-formed record has unique keys, but `#[serde(flatten)]` and hand-written
+/// `Serialize` impls can emit the same key twice, e.g. when a flattened struct shadows
+/// a field of the enclosing one. Detecting duplicates means every entry of the structs
+/// and maps on the path is compared, even once the target is found. Encoded records read
+/// with `evaluate_from` are checked too, where duplicate keys most often occur.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{DuplicateKeyPolicy, EvaluateError, FieldScalarValue, FieldExtractor};
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     timeout: u32,
+///     #[serde(flatten)]
+///     defaults: Defaults,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Defaults {
+///     timeout: u32,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let config = Config { timeout: 5, defaults: Defaults { timeout: 30 } };
+///
+///     let extractor = FieldExtractor::new("timeout");
+///     assert_eq!(extractor.evaluate(&config)?, FieldScalarValue::U32(5));
+///
+///     let extractor = extractor.with_duplicate_keys(DuplicateKeyPolicy::Error);
+///     assert!(matches!(
+///         extractor.evaluate(&config),
+///         Err(EvaluateError::DuplicateKey { first_entry: 0, second_entry: 1, .. })
+///     ));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// Follow the first matching entry and ignore later ones.
+    #[default]
+    FirstWins,
+    /// Fail with [`EvaluateError::DuplicateKey`], naming the positions of both entries.
+    Error,
+}
+
+/// The skipped-field, missing-path and duplicate-key policies of one path, whether
+/// the whole record is validated before it is extracted, and how its segments are
+/// matched.
+#[derive(Debug, Clone, Default)]
+struct Policies {
+    skipped: SkippedFieldPolicy,
+    missing: MissingPolicy,
+    duplicates: DuplicateKeyPolicy,
+    strict: bool,
+    matching: KeyMatching,
+    /// Extra names accepted for each segment, by segment index; shorter than the path
+    /// if the last segments have none.
+    aliases: Vec<Vec<String>>,
+}
+
+impl Policies {
+    /// Configures `serializer` to match segments as configured, and to detect duplicate
+    /// keys if the policy asks for it.
+    fn configure<'p, 'f>(
+        &'p self,
+        serializer: FieldValueExtractorSerializer<'p, 'f>,
+    ) -> FieldValueExtractorSerializer<'p, 'f> {
+        let serializer = serializer.with_key_matching(self.matching, &self.aliases);
+        match self.duplicates {
+            DuplicateKeyPolicy::FirstWins => serializer,
+            DuplicateKeyPolicy::Error => serializer.with_duplicate_detection(),
+        }
+    }
+
+    /// In strict mode, serializes all of `record` to surface errors raised off the path.
+    fn check<T: Serialize>(&self, record: &T) -> Result<(), EvaluateError> {
+        if self.strict {
+            validate(record)?;
+        }
+        Ok(())
+    }
+
+    /// Creates the seed extracting `path` from encoded records, matching segments as
+    /// configured.
+    fn seed<'p>(&'p self, path: &'p [String]) -> PathSeed<'p> {
+        PathSeed::new(path)
+            .with_key_matching(self.matching)
+            .with_aliases(&self.aliases)
+            .with_duplicate_keys(self.duplicates)
+    }
+
+    /// Sets the aliases of segment `index`.
+    fn set_aliases(&mut self, index: usize, aliases: Vec<String>) {
+        if self.aliases.len() <= index {
+            self.aliases.resize_with(index + 1, Vec::new);
+        }
+        self.aliases[index] = aliases;
+    }
+
+    /// Returns the value the policies substitute for `err`, or `err` itself. The
+    /// skipped-field policy is applied first.
+    fn recover(&self, err: EvaluateError) -> Result<FieldScalarValue, EvaluateError> {
+        if self.skipped.yields_none(&err) {
+            return Ok(FieldScalarValue::Option(None));
+        }
+        self.missing.recover(err)
+    }
+
+    /// Applies the policies to the outcome of an extraction.
+    fn apply(
+        &self,
+        result: Result<FieldScalarValue, EvaluateError>,
+    ) -> Result<FieldScalarValue, EvaluateError> {
+        result.or_else(|err| self.recover(err))
+    }
+}
+
+// =============================================================================
+// Borrowed Evaluation Helper
+// =============================================================================
+
+/// Extracts the scalar at `path` from `record` and hands it to `f` by reference.
+///
+/// If the target is not found and `policies` substitute a value, that value is handed
+/// to `f` instead.
+fn evaluate_borrowed<T, F, R>(
+    path: &[String],
+    record: &T,
+    policies: &Policies,
+    f: F,
+) -> Result<R, EvaluateError>
+where
+    T: Serialize,
+    F: FnOnce(FieldScalarRef<'_>) -> R,
+{
+    policies.check(record)?;
+    let mut f = Some(f);
+    let mut output = None;
+    let mut callback = |value: FieldScalarRef<'_>| {
+        if let Some(f) = f.take() {
+            output = Some(f(value));
+        }
+    };
+
+    let mut serializer = policies
+        .configure(FieldValueExtractorSerializer::new_nested(path))
+        .with_callback(&mut callback);
+    let result = record.serialize(&mut serializer);
+    drop(serializer);
+
+    let fallback = match (result, output) {
+        (Ok(()), Some(output)) => return Ok(output),
+        (Ok(()), None) => policies.recover(not_reached(path))?,
+        (Err(err), _) => policies.recover(err)?,
+    };
+    // Only not-found errors are recovered, and those leave `f` uncalled.
+    let f = f
+        .take()
+        .expect("f is not called when the target is not found");
+    Ok(fallback.with_ref(f))
+}
+
 // =============================================================================
 // Scalar Extractors
 // =============================================================================
@@ -39,6 +381,7 @@ fn validate_path<S: AsRef<str>>(segments: &[S]) -> Result<Vec<String>, EvaluateE
 #[derive(Debug, Clone)]
 pub struct FieldExtractor {
     field_name: String,
+    policies: Policies,
 }
 
 impl FieldExtractor {
@@ -48,9 +391,55 @@ impl FieldExtractor {
     pub fn new<S: Into<String>>(field_name: S) -> Self {
         FieldExtractor {
             field_name: field_name.into(),
+            policies: Policies::default(),
         }
     }
 
+    /// Sets how a field skipped by the record's `Serialize` impl is reported.
+    /// Defaults to [`SkippedFieldPolicy::NotFound`].
+    pub fn with_skipped_fields(mut self, policy: SkippedFieldPolicy) -> Self {
+        self.policies.skipped = policy;
+        self
+    }
+
+    /// Sets what is returned when the field is not found. Defaults to
+    /// [`MissingPolicy::Error`].
+    pub fn with_missing_paths(mut self, policy: MissingPolicy) -> Self {
+        self.policies.missing = policy;
+        self
+    }
+
+    /// Enables strict mode. See [`NestedFieldExtractor::with_strict_mode`].
+    pub fn with_strict_mode(mut self, strict: bool) -> Self {
+        self.policies.strict = strict;
+        self
+    }
+
+    /// Sets how a field emitted twice by the record is handled. Defaults to
+    /// [`DuplicateKeyPolicy::FirstWins`].
+    pub fn with_duplicate_keys(mut self, policy: DuplicateKeyPolicy) -> Self {
+        self.policies.duplicates = policy;
+        self
+    }
+
+    /// Sets how the field name is compared with the record's field names and map keys.
+    /// Defaults to [`KeyMatching::Exact`].
+    pub fn with_key_matching(mut self, matching: KeyMatching) -> Self {
+        self.policies.matching = matching;
+        self
+    }
+
+    /// Also extracts the field under any of the names in `aliases`, compared in the
+    /// same way as the field name. Errors still name the field name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if any alias is empty.
+    pub fn with_aliases<S: AsRef<str>>(mut self, aliases: &[S]) -> Result<Self, EvaluateError> {
+        self.policies.set_aliases(0, validate_aliases(aliases)?);
+        Ok(self)
+    }
+
     /// Extracts the scalar value of the configured `field_name` from the given `record`.
     ///
     /// This method drives the custom serialization process to capture the field's value.
@@ -63,19 +452,106 @@ impl FieldExtractor {
     ///
     /// Returns `EvaluateError` if:
     /// * The `field_name` is not found in the `record` ([`EvaluateError::FieldNotFound`]).
+    /// * The `field_name` was skipped while serializing ([`EvaluateError::NestedFieldNotFound`]
+    ///   with [`NotFoundReason::Skipped`]), unless the [`SkippedFieldPolicy`] is `AsNone`.
     /// * The `field_name`'s value is not a supported scalar type ([`EvaluateError::UnsupportedType`]).
     /// * Any other Serde serialization error occurs.
+    ///
+    /// A [`MissingPolicy`] other than `Error` replaces the not-found errors with a value.
     pub fn evaluate<T: Serialize>(&self, record: &T) -> Result<FieldScalarValue, EvaluateError> {
-        let mut serializer = FieldValueExtractorSerializer::new(&self.field_name);
-        // Attempt to serialize the record using our custom serializer.
-        record.serialize(&mut serializer)?;
+        self.policies.check(record)?;
+        let path = std::slice::from_ref(&self.field_name);
+        let mut serializer = self
+            .policies
+            .configure(FieldValueExtractorSerializer::new_nested(path));
+        // Attempt to serialize the record using our custom serializer, then check if
+        // the serializer captured a result.
+        let result = record
+            .serialize(&mut serializer)
+            .and_then(|()| serializer.into_result().ok_or_else(|| not_reached(path)));
 
-        // After serialization, check if the serializer captured a result.
-        serializer
-            .into_result()
-            .ok_or_else(|| EvaluateError::FieldNotFound {
-                field_name: self.field_name.clone(),
-            })
+        self.policies
+            .apply(result)
+            .map_err(|err| field_not_found(&self.field_name, err))
+    }
+
+    /// Extracts the configured field and passes it to `f` as a borrowed [`FieldScalarRef`].
+    ///
+    /// Unlike [`FieldExtractor::evaluate`], string and byte values are not copied: `f`
+    /// sees them borrowed from `record` for the duration of the serialize call.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_evaluate::{FieldExtractor, FieldScalarRef, EvaluateError};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Record {
+    ///     status: String,
+    /// }
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let record = Record { status: "active".to_string() };
+    ///
+    ///     let is_active = FieldExtractor::new("status")
+    ///         .evaluate_with(&record, |v| v == FieldScalarRef::String("active"))?;
+    ///     assert!(is_active);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FieldExtractor::evaluate`].
+    pub fn evaluate_with<T, F, R>(&self, record: &T, f: F) -> Result<R, EvaluateError>
+    where
+        T: Serialize,
+        F: FnOnce(FieldScalarRef<'_>) -> R,
+    {
+        evaluate_borrowed(
+            std::slice::from_ref(&self.field_name),
+            record,
+            &self.policies,
+            f,
+        )
+        .map_err(|err| field_not_found(&self.field_name, err))
+    }
+
+    /// Extracts the configured field from an encoded record, without deserializing it.
+    ///
+    /// Walks `deserializer` with a [`PathSeed`]; see the
+    /// [`deserializer`](crate::deserializer) module for which formats are supported and
+    /// how value types are reported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_evaluate::{FieldExtractor, FieldScalarValue, EvaluateError};
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let bytes = br#"{"id": 7, "status": "active"}"#;
+    ///     let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    ///
+    ///     let status = FieldExtractor::new("status").evaluate_from(&mut deserializer)?;
+    ///     assert_eq!(status, FieldScalarValue::String("active".to_string()));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FieldExtractor::evaluate`], plus
+    /// [`EvaluateError::DeserializationError`] if the input is malformed or the format
+    /// is not self-describing.
+    pub fn evaluate_from<'de, D: Deserializer<'de>>(
+        &self,
+        deserializer: D,
+    ) -> Result<FieldScalarValue, EvaluateError> {
+        let path = std::slice::from_ref(&self.field_name);
+        self.policies
+            .apply(extract_from(self.policies.seed(path), deserializer))
+            .map_err(|err| field_not_found(&self.field_name, err))
     }
 }
 
@@ -87,6 +563,8 @@ impl FieldExtractor {
 pub struct NestedFieldExtractor {
     /// The sequence of field names representing the path to the target value.
     path_segments: Vec<String>,
+    /// How a skipped or missing target is reported.
+    policies: Policies,
 }
 
 impl NestedFieldExtractor {
@@ -106,37 +584,405 @@ impl NestedFieldExtractor {
     pub fn new_from_path<S: AsRef<str>>(path_segments: &[S]) -> Result<Self, EvaluateError> {
         Ok(NestedFieldExtractor {
             path_segments: validate_path(path_segments)?,
+            policies: Policies::default(),
         })
     }
 
+    /// Sets how a target field skipped by the record's `Serialize` impl is reported.
+    /// Defaults to [`SkippedFieldPolicy::NotFound`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_evaluate::{
+    ///     EvaluateError, FieldScalarValue, NestedFieldExtractor, NotFoundReason,
+    ///     SkippedFieldPolicy,
+    /// };
+    ///
+    /// #[derive(Serialize)]
+    /// struct User {
+    ///     #[serde(skip_serializing_if = "Option::is_none")]
+    ///     nickname: Option<String>,
+    /// }
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let user = User { nickname: None };
+    ///     let extractor = NestedFieldExtractor::new_from_path(&["nickname"])?;
+    ///
+    ///     assert!(matches!(
+    ///         extractor.evaluate(&user),
+    ///         Err(EvaluateError::NestedFieldNotFound { reason: NotFoundReason::Skipped, .. })
+    ///     ));
+    ///
+    ///     let extractor = extractor.with_skipped_fields(SkippedFieldPolicy::AsNone);
+    ///     assert_eq!(extractor.evaluate(&user)?, FieldScalarValue::Option(None));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_skipped_fields(mut self, policy: SkippedFieldPolicy) -> Self {
+        self.policies.skipped = policy;
+        self
+    }
+
+    /// Sets what is returned when the path is not found. Defaults to
+    /// [`MissingPolicy::Error`]. [`exists`](Self::exists) and
+    /// [`describe`](Self::describe) are not affected.
+    pub fn with_missing_paths(mut self, policy: MissingPolicy) -> Self {
+        self.policies.missing = policy;
+        self
+    }
+
+    /// Enables strict mode, off by default.
+    ///
+    /// Extraction only serializes the values on the path, so an error raised by the
+    /// record's `Serialize` impl elsewhere goes unnoticed. In strict mode the whole
+    /// record is serialized first, and any such error is returned as
+    /// [`EvaluateError::SerializationError`] before the path is looked up. A broken
+    /// `Serialize` impl is then never reported as a missing path. This costs one extra
+    /// pass over the record. [`evaluate_from`](Self::evaluate_from) is not affected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::{Serialize, Serializer};
+    /// use serde_evaluate::{EvaluateError, FieldScalarValue, NestedFieldExtractor};
+    ///
+    /// struct Broken;
+    ///
+    /// impl Serialize for Broken {
+    ///     fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+    ///         Err(serde::ser::Error::custom("cannot serialize"))
+    ///     }
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Record {
+    ///     id: u64,
+    ///     payload: Broken,
+    /// }
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let record = Record { id: 7, payload: Broken };
+    ///     let extractor = NestedFieldExtractor::new_from_path(&["id"])?;
+    ///     assert_eq!(extractor.evaluate(&record)?, FieldScalarValue::U64(7));
+    ///
+    ///     let extractor = extractor.with_strict_mode(true);
+    ///     assert!(matches!(
+    ///         extractor.evaluate(&record),
+    ///         Err(EvaluateError::SerializationError { .. })
+    ///     ));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_strict_mode(mut self, strict: bool) -> Self {
+        self.policies.strict = strict;
+        self
+    }
+
+    /// Sets how a segment matching two entries of the same struct or map is handled.
+    /// Defaults to [`DuplicateKeyPolicy::FirstWins`].
+    pub fn with_duplicate_keys(mut self, policy: DuplicateKeyPolicy) -> Self {
+        self.policies.duplicates = policy;
+        self
+    }
+
+    /// Sets how path segments and their aliases are compared with field names and map
+    /// keys. Defaults to [`KeyMatching::Exact`].
+    pub fn with_key_matching(mut self, matching: KeyMatching) -> Self {
+        self.policies.matching = matching;
+        self
+    }
+
+    /// Lets the segment at `index` also match any of the names in `aliases`, replacing
+    /// the aliases it had. Errors still report the segment itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_evaluate::{EvaluateError, FieldScalarValue, NestedFieldExtractor};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Order {
+    ///     buyer: Customer,
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Customer {
+    ///     region: String,
+    /// }
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let order = Order { buyer: Customer { region: "eu".to_string() } };
+    ///
+    ///     // `customer` was renamed to `buyer`; the path keeps matching both.
+    ///     let extractor = NestedFieldExtractor::new_from_path(&["customer", "region"])?
+    ///         .with_aliases(0, &["buyer"])?;
+    ///     assert_eq!(extractor.evaluate(&order)?, FieldScalarValue::String("eu".to_string()));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if `index` is not a segment of the path or
+    /// any alias is empty.
+    pub fn with_aliases<S: AsRef<str>>(
+        mut self,
+        index: usize,
+        aliases: &[S],
+    ) -> Result<Self, EvaluateError> {
+        if index >= self.path_segments.len() {
+            return Err(EvaluateError::InvalidPath(format!(
+                "Segment index {} is out of range for a path of {} segments",
+                index,
+                self.path_segments.len()
+            )));
+        }
+        self.policies.set_aliases(index, validate_aliases(aliases)?);
+        Ok(self)
+    }
+
+    /// Creates a `NestedFieldExtractor` from a path in expression syntax, such as
+    /// `customer.address["zip code"]`.
+    pub(crate) fn new_from_dotted(path: &str) -> Result<Self, EvaluateError> {
+        Self::new_from_path(&parse_path(path)?)
+    }
+
+    /// Returns the path segments this extractor follows.
+    pub fn path(&self) -> &[String] {
+        &self.path_segments
+    }
+
     /// Evaluates the extractor against the given serializable value using the configured path.
     ///
     /// This triggers the serialization process, traversing the nested structure according
     /// to `path_segments` and intercepting the target field's value.
     ///
-    /// # Arguments
+    /// # Arguments
+    ///
+    /// * `value` - A reference to a value that implements `serde::Serialize`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FieldScalarValue)` if the field at the specified path is found and is a supported scalar type.
+    /// * `Err(EvaluateError)` if the path is invalid, an intermediate field is not a struct,
+    ///   the final field is not found or has an unsupported type, or a serialization error occurs.
+    ///
+    /// A [`MissingPolicy`] other than `Error` replaces the not-found errors with a value.
+    pub fn evaluate<T: Serialize>(&self, value: &T) -> Result<FieldScalarValue, EvaluateError> {
+        self.policies.check(value)?;
+        self.extract(value)
+    }
+
+    /// Like [`evaluate`](Self::evaluate), without the strict-mode check.
+    fn extract<T: Serialize>(&self, value: &T) -> Result<FieldScalarValue, EvaluateError> {
+        let mut serializer = self
+            .policies
+            .configure(FieldValueExtractorSerializer::new_nested(
+                &self.path_segments,
+            ));
+
+        // Attempt to serialize the record using our custom serializer, then check if
+        // the serializer captured a result.
+        let result = value.serialize(&mut serializer).and_then(|()| {
+            serializer
+                .into_result()
+                .ok_or_else(|| not_reached(&self.path_segments))
+        });
+
+        self.policies.apply(result)
+    }
+
+    /// Evaluates the configured path and passes the value to `f` as a borrowed [`FieldScalarRef`].
+    ///
+    /// Unlike [`NestedFieldExtractor::evaluate`], string and byte values are not copied: `f`
+    /// sees them borrowed from `value` for the duration of the serialize call.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`NestedFieldExtractor::evaluate`].
+    pub fn evaluate_with<T, F, R>(&self, value: &T, f: F) -> Result<R, EvaluateError>
+    where
+        T: Serialize,
+        F: FnOnce(FieldScalarRef<'_>) -> R,
+    {
+        evaluate_borrowed(&self.path_segments, value, &self.policies, f)
+    }
+
+    /// Extracts the configured path from an encoded record, without deserializing it.
+    ///
+    /// Walks `deserializer` with a [`PathSeed`], skipping every value off the path. See
+    /// the [`deserializer`](crate::deserializer) module for which formats are supported
+    /// and how value types are reported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde_evaluate::{NestedFieldExtractor, FieldScalarValue, EvaluateError};
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let bytes = br#"{"id": 7, "customer": {"region": "eu", "tags": ["a", "b"]}}"#;
+    ///     let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    ///
+    ///     let extractor = NestedFieldExtractor::new_from_path(&["customer", "region"])?;
+    ///     let region = extractor.evaluate_from(&mut deserializer)?;
+    ///     assert_eq!(region, FieldScalarValue::String("eu".to_string()));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`NestedFieldExtractor::evaluate`], plus
+    /// [`EvaluateError::DeserializationError`] if the input is malformed or the format
+    /// is not self-describing. `NestedFieldNotFound` carries the index of the first
+    /// segment that was not found.
+    pub fn evaluate_from<'de, D: Deserializer<'de>>(
+        &self,
+        deserializer: D,
+    ) -> Result<FieldScalarValue, EvaluateError> {
+        self.policies.apply(extract_from(
+            self.policies.seed(&self.path_segments),
+            deserializer,
+        ))
+    }
+
+    /// Checks whether the configured path exists in `value`, without capturing its value.
+    ///
+    /// Any target type is accepted, including structs, maps, sequences and enum variants.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_evaluate::{NestedFieldExtractor, PathProbe, FieldKind, EvaluateError};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Record {
+    ///     metadata: Metadata,
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Metadata {
+    ///     owner: Option<Owner>,
+    ///     reviewer: Option<Owner>,
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Owner {
+    ///     name: String,
+    /// }
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let record = Record {
+    ///         metadata: Metadata {
+    ///             owner: Some(Owner { name: "alice".to_string() }),
+    ///             reviewer: None,
+    ///         },
+    ///     };
+    ///
+    ///     let owner = NestedFieldExtractor::new_from_path(&["metadata", "owner"])?;
+    ///     assert_eq!(
+    ///         owner.exists(&record)?,
+    ///         PathProbe::Present { kind: FieldKind::Struct { name: "Owner", fields: 1 } }
+    ///     );
+    ///
+    ///     let reviewer = NestedFieldExtractor::new_from_path(&["metadata", "reviewer"])?;
+    ///     assert_eq!(reviewer.exists(&record)?, PathProbe::Null);
+    ///
+    ///     let team = NestedFieldExtractor::new_from_path(&["metadata", "team"])?;
+    ///     assert_eq!(team.exists(&record)?, PathProbe::Missing);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// A missing path is reported as [`PathProbe::Missing`], not as an error. Errors are
+    /// only returned when traversal itself fails, e.g. when an intermediate segment is an
+    /// enum variant ([`EvaluateError::UnsupportedVariant`]) or a serialization error occurs.
+    pub fn exists<T: Serialize>(&self, value: &T) -> Result<PathProbe, EvaluateError> {
+        match self.describe(value) {
+            Ok(kind) => Ok(kind.into()),
+            Err(EvaluateError::NestedFieldNotFound { .. }) => Ok(PathProbe::Missing),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Reports the shape of the value at the configured path, without capturing it.
+    ///
+    /// The returned [`FieldKind`] carries the struct, enum and variant names and the
+    /// lengths that Serde reports while serializing. `Option` layers are preserved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_evaluate::{NestedFieldExtractor, FieldKind, VariantShape, EvaluateError};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Record {
+    ///     settings: Settings,
+    ///     mode: Mode,
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Settings {
+    ///     level: u8,
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// enum Mode {
+    ///     Fast,
+    /// }
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let record = Record {
+    ///         settings: Settings { level: 1, tags: vec!["a".to_string()] },
+    ///         mode: Mode::Fast,
+    ///     };
+    ///
+    ///     let settings = NestedFieldExtractor::new_from_path(&["settings"])?;
+    ///     assert_eq!(
+    ///         settings.describe(&record)?,
+    ///         FieldKind::Struct { name: "Settings", fields: 2 }
+    ///     );
     ///
-    /// * `value` - A reference to a value that implements `serde::Serialize`.
+    ///     let mode = NestedFieldExtractor::new_from_path(&["mode"])?;
+    ///     assert_eq!(
+    ///         mode.describe(&record)?,
+    ///         FieldKind::Variant { name: "Mode", variant: "Fast", shape: VariantShape::Unit }
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// * `Ok(FieldScalarValue)` if the field at the specified path is found and is a supported scalar type.
-    /// * `Err(EvaluateError)` if the path is invalid, an intermediate field is not a struct,
-    ///   the final field is not found or has an unsupported type, or a serialization error occurs.
-    pub fn evaluate<T: Serialize>(&self, value: &T) -> Result<FieldScalarValue, EvaluateError> {
-        // Clone the path segments because new_nested takes ownership, but evaluate only has &self.
-        let mut serializer = FieldValueExtractorSerializer::new_nested(self.path_segments.clone());
-
-        // Attempt to serialize the record using our custom serializer.
-        value.serialize(&mut serializer)?;
+    /// Returns `EvaluateError` if:
+    /// * The path is not found ([`EvaluateError::NestedFieldNotFound`]).
+    /// * An intermediate segment is an enum variant ([`EvaluateError::UnsupportedVariant`]).
+    /// * A serialization error occurs.
+    pub fn describe<T: Serialize>(&self, value: &T) -> Result<FieldKind, EvaluateError> {
+        self.policies.check(value)?;
+        let mut serializer = self
+            .policies
+            .configure(FieldValueExtractorSerializer::new_describe(
+                &self.path_segments,
+            ));
+        match value.serialize(&mut serializer) {
+            Err(err) if self.policies.skipped.yields_none(&err) => {
+                return Ok(FieldKind::Option(None))
+            }
+            result => result?,
+        }
 
-        // After serialization, check if the serializer captured a result.
         serializer
-            .into_result()
-            .ok_or_else(|| EvaluateError::NestedFieldNotFound {
-                path: self.path_segments.clone(),
-                failed_at_index: None, // Index unknown at this point
-            })
+            .into_kind_result()
+            .ok_or_else(|| not_reached(&self.path_segments))
     }
 }
 
@@ -270,6 +1116,38 @@ impl CompositeFieldExtractor {
         Ok(CompositeFieldExtractor { extractors })
     }
 
+    /// Creates a `CompositeFieldExtractor` from configured extractors, one per path, so
+    /// that each path keeps its own [`MissingPolicy`] and [`SkippedFieldPolicy`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if the list is empty.
+    pub fn from_extractors(extractors: Vec<NestedFieldExtractor>) -> Result<Self, EvaluateError> {
+        if extractors.is_empty() {
+            return Err(EvaluateError::InvalidPath(
+                "Composite extractor requires at least one field".to_string(),
+            ));
+        }
+        Ok(CompositeFieldExtractor { extractors })
+    }
+
+    /// Creates a `CompositeFieldExtractor` from paths in expression syntax, such as
+    /// `customer.region`.
+    pub(crate) fn new_from_dotted<S: AsRef<str>>(paths: &[S]) -> Result<Self, EvaluateError> {
+        if paths.is_empty() {
+            return Err(EvaluateError::InvalidPath(
+                "Composite extractor requires at least one field".to_string(),
+            ));
+        }
+
+        let extractors = paths
+            .iter()
+            .map(|path| NestedFieldExtractor::new_from_dotted(path.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(CompositeFieldExtractor { extractors })
+    }
+
     /// Extracts scalar values for all configured fields from the given record.
     ///
     /// Returns values in the same order as the fields were specified during construction.
@@ -286,11 +1164,542 @@ impl CompositeFieldExtractor {
         &self,
         record: &T,
     ) -> Result<Vec<FieldScalarValue>, EvaluateError> {
+        self.check(record)?;
         self.extractors
             .iter()
-            .map(|extractor| extractor.evaluate(record))
+            .map(|extractor| extractor.extract(record))
+            .collect()
+    }
+
+    /// Extracts every configured field from the given record, returning one result per
+    /// field in the order the fields were specified.
+    ///
+    /// Unlike [`evaluate`](Self::evaluate), a failing field does not stop extraction,
+    /// so a single call reports every field that could not be extracted. Each error
+    /// names the path it failed on.
+    ///
+    /// # Errors
+    ///
+    /// Returns the outer `Err` only for a failure of the whole record: in strict mode,
+    /// the `EvaluateError::SerializationError` of a record whose `Serialize` impl fails.
+    pub fn evaluate_all<T: Serialize>(
+        &self,
+        record: &T,
+    ) -> Result<Vec<Result<FieldScalarValue, EvaluateError>>, EvaluateError> {
+        self.check(record)?;
+        Ok(self
+            .extractors
+            .iter()
+            .map(|extractor| extractor.extract(record))
+            .collect())
+    }
+
+    /// Extracts every configured field and returns the stable 64-bit hash of the values,
+    /// for routing records to shards or partitions.
+    ///
+    /// The hash only depends on the extracted values, their types and their order, and
+    /// is the same on every platform and in every release. See the [`hash`]
+    /// module for the encoding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_evaluate::hash::jump_consistent_hash;
+    /// use serde_evaluate::{CompositeFieldExtractor, EvaluateError};
+    /// use std::num::NonZeroU32;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Event {
+    ///     tenant_id: u64,
+    ///     region: String,
+    ///     payload: String,
+    /// }
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let shards = NonZeroU32::new(8).unwrap();
+    ///     let routing = CompositeFieldExtractor::new(&["tenant_id", "region"])?;
+    ///
+    ///     let a = Event { tenant_id: 42, region: "eu".to_string(), payload: "a".to_string() };
+    ///     let b = Event { tenant_id: 42, region: "eu".to_string(), payload: "b".to_string() };
+    ///     assert_eq!(
+    ///         jump_consistent_hash(routing.hash64(&a)?, shards),
+    ///         jump_consistent_hash(routing.hash64(&b)?, shards),
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`evaluate`](Self::evaluate).
+    pub fn hash64<T: Serialize>(&self, record: &T) -> Result<u64, EvaluateError> {
+        Ok(hash::hash64(&self.evaluate(record)?))
+    }
+
+    /// Like [`hash64`](Self::hash64), with a 128-bit hash.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`evaluate`](Self::evaluate).
+    pub fn hash128<T: Serialize>(&self, record: &T) -> Result<u128, EvaluateError> {
+        Ok(hash::hash128(&self.evaluate(record)?))
+    }
+
+    /// Validates `record` once if any path is in strict mode.
+    fn check<T: Serialize>(&self, record: &T) -> Result<(), EvaluateError> {
+        if self
+            .extractors
+            .iter()
+            .any(|extractor| extractor.policies.strict)
+        {
+            validate(record)?;
+        }
+        Ok(())
+    }
+
+    /// Evaluates every record in `records`, returning one result per record in input
+    /// order.
+    ///
+    /// The paths are compiled once for the whole batch, as with [`compile`](Self::compile).
+    pub fn evaluate_many<T: Serialize>(
+        &self,
+        records: &[T],
+    ) -> Vec<Result<Vec<FieldScalarValue>, EvaluateError>> {
+        self.compile().evaluate_many(records)
+    }
+
+    /// Like [`evaluate_many`](Self::evaluate_many), but evaluates the records in
+    /// parallel on the rayon thread pool. Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn par_evaluate_many<T: Serialize + Sync>(
+        &self,
+        records: &[T],
+    ) -> Vec<Result<Vec<FieldScalarValue>, EvaluateError>> {
+        self.compile().par_evaluate_many(records)
+    }
+
+    /// Sets how a target field skipped by the record's `Serialize` impl is reported,
+    /// for every path. See [`NestedFieldExtractor::with_skipped_fields`].
+    pub fn with_skipped_fields(mut self, policy: SkippedFieldPolicy) -> Self {
+        for extractor in &mut self.extractors {
+            extractor.policies.skipped = policy;
+        }
+        self
+    }
+
+    /// Sets what is returned for a path that is not found, for every path. To set it
+    /// per path, configure the extractors and use [`from_extractors`](Self::from_extractors).
+    pub fn with_missing_paths(mut self, policy: MissingPolicy) -> Self {
+        for extractor in &mut self.extractors {
+            extractor.policies.missing = policy.clone();
+        }
+        self
+    }
+
+    /// Enables strict mode for every path. The record is then validated once per
+    /// evaluation, not once per path. See [`NestedFieldExtractor::with_strict_mode`].
+    pub fn with_strict_mode(mut self, strict: bool) -> Self {
+        for extractor in &mut self.extractors {
+            extractor.policies.strict = strict;
+        }
+        self
+    }
+
+    /// Sets how duplicate keys are handled, for every path. See
+    /// [`NestedFieldExtractor::with_duplicate_keys`].
+    pub fn with_duplicate_keys(mut self, policy: DuplicateKeyPolicy) -> Self {
+        for extractor in &mut self.extractors {
+            extractor.policies.duplicates = policy;
+        }
+        self
+    }
+
+    /// Sets how segments are compared with keys, for every path. Aliases are set per
+    /// path with [`NestedFieldExtractor::with_aliases`] and
+    /// [`from_extractors`](Self::from_extractors).
+    pub fn with_key_matching(mut self, matching: KeyMatching) -> Self {
+        for extractor in &mut self.extractors {
+            extractor.policies.matching = matching;
+        }
+        self
+    }
+
+    /// Compiles the paths of this extractor into a [`CompiledExtractor`], which
+    /// extracts all of them in a single pass. The policies of each path are kept.
+    pub fn compile(&self) -> CompiledExtractor {
+        CompiledExtractor::from_validated(&self.extractors)
+    }
+}
+
+// =============================================================================
+// Compiled Extractor
+// =============================================================================
+
+/// Extracts multiple scalar fields in a single serialization pass.
+///
+/// The paths are merged into a trie when the extractor is built, so a record is
+/// serialized once no matter how many paths are extracted, and each struct field is
+/// compared against the field names that can continue a path at that depth. Fields
+/// and map entries off every path are skipped, and traversal stops once every path
+/// is filled.
+///
+/// Results are the same as [`CompositeFieldExtractor::evaluate`], including which
+/// error is returned when several paths fail: the first failing path, in the order
+/// the paths were given.
+///
+/// For hot loops, [`evaluate_into`](Self::evaluate_into) reuses an
+/// [`ExtractionState`], so evaluating allocates nothing beyond the extracted values.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{CompiledExtractor, EvaluateError, ExtractionState, FieldScalarValue};
+///
+/// #[derive(Serialize)]
+/// struct Order {
+///     id: u64,
+///     customer: Customer,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Customer {
+///     region: String,
+///     tier: u8,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let extractor = CompiledExtractor::new_from_paths(&[
+///         &["customer", "region"][..],
+///         &["customer", "tier"],
+///         &["id"],
+///     ])?;
+///     let mut state = ExtractionState::new();
+///
+///     for id in 0..3 {
+///         let order = Order {
+///             id,
+///             customer: Customer { region: "eu".to_string(), tier: 2 },
+///         };
+///         let values = extractor.evaluate_into(&order, &mut state)?;
+///         assert_eq!(values[2], FieldScalarValue::U64(id));
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompiledExtractor {
+    paths: Vec<Vec<String>>,
+    nodes: Vec<PathNode>,
+    /// How a skipped or missing target is reported, by path.
+    policies: Vec<Policies>,
+    /// True if any path is in strict mode; the record is then validated once.
+    strict: bool,
+    /// Whether each path fails on duplicate keys; empty if none does.
+    duplicates: Vec<bool>,
+}
+
+impl CompiledExtractor {
+    /// Creates a new `CompiledExtractor` for top-level field names.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if the list is empty or any name is empty.
+    pub fn new<S: AsRef<str>>(field_names: &[S]) -> Result<Self, EvaluateError> {
+        Ok(CompositeFieldExtractor::new(field_names)?.compile())
+    }
+
+    /// Creates a new `CompiledExtractor` from a slice of field paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if the list is empty, any path is empty,
+    /// or any path segment is empty.
+    pub fn new_from_paths<S: AsRef<str>>(paths: &[&[S]]) -> Result<Self, EvaluateError> {
+        Ok(CompositeFieldExtractor::new_from_paths(paths)?.compile())
+    }
+
+    /// Builds the trie from the paths of extractors, which have already been validated.
+    fn from_validated(extractors: &[NestedFieldExtractor]) -> Self {
+        let mut nodes = vec![PathNode::default()];
+        for (index, extractor) in extractors.iter().enumerate() {
+            let mut node = 0;
+            for (depth, segment) in extractor.path().iter().enumerate() {
+                let key = SegmentKey {
+                    name: segment.clone(),
+                    aliases: aliases_of(&extractor.policies.aliases, depth).to_vec(),
+                    matching: extractor.policies.matching,
+                };
+                node = match nodes[node].child_keys.iter().position(|k| *k == key) {
+                    Some(i) => nodes[node].child_nodes[i],
+                    None => {
+                        let child = nodes.len();
+                        nodes.push(PathNode::default());
+                        nodes[node].child_keys.push(key);
+                        nodes[node].child_nodes.push(child);
+                        child
+                    }
+                };
+            }
+            nodes[node].targets.push(index);
+        }
+
+        let policies: Vec<Policies> = extractors
+            .iter()
+            .map(|extractor| extractor.policies.clone())
+            .collect();
+        CompiledExtractor {
+            paths: extractors
+                .iter()
+                .map(|extractor| extractor.path_segments.clone())
+                .collect(),
+            nodes,
+            strict: policies.iter().any(|policies| policies.strict),
+            duplicates: detect_duplicates(&policies),
+            policies,
+        }
+    }
+
+    /// Sets how a target field skipped by the record's `Serialize` impl is reported,
+    /// for every path. See [`NestedFieldExtractor::with_skipped_fields`].
+    pub fn with_skipped_fields(mut self, policy: SkippedFieldPolicy) -> Self {
+        for policies in &mut self.policies {
+            policies.skipped = policy;
+        }
+        self
+    }
+
+    /// Sets what is returned for a path that is not found, for every path. Per-path
+    /// policies are kept by [`CompositeFieldExtractor::compile`].
+    pub fn with_missing_paths(mut self, policy: MissingPolicy) -> Self {
+        for policies in &mut self.policies {
+            policies.missing = policy.clone();
+        }
+        self
+    }
+
+    /// Enables strict mode. See [`NestedFieldExtractor::with_strict_mode`].
+    pub fn with_strict_mode(mut self, strict: bool) -> Self {
+        for policies in &mut self.policies {
+            policies.strict = strict;
+        }
+        self.strict = strict;
+        self
+    }
+
+    /// Sets how duplicate keys are handled, for every path. See
+    /// [`NestedFieldExtractor::with_duplicate_keys`].
+    pub fn with_duplicate_keys(mut self, policy: DuplicateKeyPolicy) -> Self {
+        for policies in &mut self.policies {
+            policies.duplicates = policy;
+        }
+        self.duplicates = detect_duplicates(&self.policies);
+        self
+    }
+
+    /// Sets how segments are compared with keys, for every path. See
+    /// [`CompositeFieldExtractor::with_key_matching`].
+    pub fn with_key_matching(mut self, matching: KeyMatching) -> Self {
+        for policies in &mut self.policies {
+            policies.matching = matching;
+        }
+        for key in self.nodes.iter_mut().flat_map(|node| &mut node.child_keys) {
+            key.matching = matching;
+        }
+        self
+    }
+
+    /// Returns the paths, in the order their values are returned.
+    pub fn paths(&self) -> &[Vec<String>] {
+        &self.paths
+    }
+
+    /// Extracts scalar values for all configured paths from the given record.
+    ///
+    /// Returns values in the same order as the paths were specified during construction.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first path that could not be extracted, such as
+    /// `EvaluateError::NestedFieldNotFound` for a missing path.
+    pub fn evaluate<T: Serialize>(
+        &self,
+        record: &T,
+    ) -> Result<Vec<FieldScalarValue>, EvaluateError> {
+        self.evaluate_owned(record, &mut ExtractionState::new())
+    }
+
+    /// Extracts all configured paths from the given record in a single pass, returning
+    /// one result per path. See [`CompositeFieldExtractor::evaluate_all`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the outer `Err` for a failure of the whole record: the strict-mode check,
+    /// or a `Serialize` impl that fails before every path is reached. Since the paths
+    /// share one pass, the paths not yet reached have no result of their own.
+    pub fn evaluate_all<T: Serialize>(
+        &self,
+        record: &T,
+    ) -> Result<Vec<Result<FieldScalarValue, EvaluateError>>, EvaluateError> {
+        self.check(record)?;
+        let mut slots: Vec<PathSlot> = vec![None; self.paths.len()];
+        let mut serializer = self.serializer(&mut slots);
+        if let Err(err) = record.serialize(&mut serializer) {
+            if slots.iter().any(Option::is_none) {
+                return Err(err);
+            }
+        }
+
+        Ok(slots
+            .into_iter()
+            .zip(&self.paths)
+            .zip(&self.policies)
+            .map(|((slot, path), policies)| {
+                policies.apply(slot.unwrap_or_else(|| Err(not_reached(path))))
+            })
+            .collect())
+    }
+
+    /// Extracts every path and returns the stable 64-bit hash of the values. See
+    /// [`CompositeFieldExtractor::hash64`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`evaluate`](Self::evaluate).
+    pub fn hash64<T: Serialize>(&self, record: &T) -> Result<u64, EvaluateError> {
+        Ok(hash::hash64(&self.evaluate(record)?))
+    }
+
+    /// Like [`hash64`](Self::hash64), with a 128-bit hash.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`evaluate`](Self::evaluate).
+    pub fn hash128<T: Serialize>(&self, record: &T) -> Result<u128, EvaluateError> {
+        Ok(hash::hash128(&self.evaluate(record)?))
+    }
+
+    /// Evaluates every record in `records`, returning one result per record in input
+    /// order. A single [`ExtractionState`] is reused for the whole batch.
+    pub fn evaluate_many<T: Serialize>(
+        &self,
+        records: &[T],
+    ) -> Vec<Result<Vec<FieldScalarValue>, EvaluateError>> {
+        let mut state = ExtractionState::new();
+        records
+            .iter()
+            .map(|record| self.evaluate_owned(record, &mut state))
+            .collect()
+    }
+
+    /// Like [`evaluate_many`](Self::evaluate_many), but evaluates the records in
+    /// parallel on the rayon thread pool, with one [`ExtractionState`] per worker.
+    /// Requires the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn par_evaluate_many<T: Serialize + Sync>(
+        &self,
+        records: &[T],
+    ) -> Vec<Result<Vec<FieldScalarValue>, EvaluateError>> {
+        use rayon::prelude::*;
+
+        records
+            .par_iter()
+            .map_init(ExtractionState::new, |state, record| {
+                self.evaluate_owned(record, state)
+            })
             .collect()
     }
+
+    /// Evaluates into `state` and moves the values out, keeping the slot buffer.
+    fn evaluate_owned<T: Serialize>(
+        &self,
+        record: &T,
+        state: &mut ExtractionState,
+    ) -> Result<Vec<FieldScalarValue>, EvaluateError> {
+        self.evaluate_into(record, state)?;
+        Ok(std::mem::take(&mut state.values))
+    }
+
+    /// Like [`evaluate`](Self::evaluate), but reuses the buffers of `state`.
+    ///
+    /// The returned slice borrows from `state` and is overwritten by the next call.
+    /// Once `state` has been used with this extractor, only owned payloads (strings
+    /// and bytes) are allocated.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`evaluate`](Self::evaluate).
+    pub fn evaluate_into<'s, T: Serialize>(
+        &self,
+        record: &T,
+        state: &'s mut ExtractionState,
+    ) -> Result<&'s [FieldScalarValue], EvaluateError> {
+        self.check(record)?;
+        state.slots.clear();
+        state.slots.resize_with(self.paths.len(), || None);
+        state.values.clear();
+
+        let mut serializer = self.serializer(&mut state.slots);
+        record.serialize(&mut serializer)?;
+
+        for ((slot, path), policies) in state.slots.iter_mut().zip(&self.paths).zip(&self.policies)
+        {
+            let outcome = slot.take().unwrap_or_else(|| Err(not_reached(path)));
+            state.values.push(policies.apply(outcome)?);
+        }
+        Ok(&state.values)
+    }
+
+    /// Creates the serializer filling `slots`, detecting duplicate keys if any path
+    /// asks for it.
+    fn serializer<'c>(&'c self, slots: &'c mut [PathSlot]) -> MultiPathSerializer<'c> {
+        let serializer = MultiPathSerializer::new(&self.nodes, &self.paths, slots);
+        if self.duplicates.is_empty() {
+            serializer
+        } else {
+            serializer.with_duplicate_detection(&self.duplicates)
+        }
+    }
+
+    /// In strict mode, serializes all of `record` to surface errors raised off the paths.
+    fn check<T: Serialize>(&self, record: &T) -> Result<(), EvaluateError> {
+        if self.strict {
+            validate(record)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether each path fails on duplicate keys, or nothing if none does.
+fn detect_duplicates(policies: &[Policies]) -> Vec<bool> {
+    if policies
+        .iter()
+        .all(|policies| policies.duplicates == DuplicateKeyPolicy::FirstWins)
+    {
+        return Vec::new();
+    }
+    policies
+        .iter()
+        .map(|policies| policies.duplicates == DuplicateKeyPolicy::Error)
+        .collect()
+}
+
+/// Reusable buffers for [`CompiledExtractor::evaluate_into`].
+///
+/// A state is not tied to one extractor, but it is sized by the last extractor it was
+/// used with.
+#[derive(Debug, Default)]
+pub struct ExtractionState {
+    slots: Vec<PathSlot>,
+    values: Vec<FieldScalarValue>,
+}
+
+impl ExtractionState {
+    /// Creates an empty state. Buffers grow on first use.
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
 // =============================================================================
@@ -366,8 +1775,11 @@ impl ListFieldExtractor {
         &self,
         record: &T,
     ) -> Result<Vec<FieldScalarValue>, EvaluateError> {
-        let mut serializer = FieldValueExtractorSerializer::new_list(&self.field_name);
-        record.serialize(&mut serializer)?;
+        let mut serializer =
+            FieldValueExtractorSerializer::new_nested_list(std::slice::from_ref(&self.field_name));
+        record
+            .serialize(&mut serializer)
+            .map_err(|err| field_not_found(&self.field_name, err))?;
 
         serializer
             .into_list_result()
@@ -459,15 +1871,53 @@ impl NestedListFieldExtractor {
         &self,
         value: &T,
     ) -> Result<Vec<FieldScalarValue>, EvaluateError> {
-        let mut serializer =
-            FieldValueExtractorSerializer::new_nested_list(self.path_segments.clone());
+        let mut serializer = FieldValueExtractorSerializer::new_nested_list(&self.path_segments);
         value.serialize(&mut serializer)?;
 
         serializer
             .into_list_result()
-            .ok_or_else(|| EvaluateError::NestedFieldNotFound {
-                path: self.path_segments.clone(),
-                failed_at_index: None, // Index unknown at this point
-            })
+            .ok_or_else(|| not_reached(&self.path_segments))
     }
 }
+
+// =============================================================================
+// Batch Evaluation
+// =============================================================================
+
+/// Generates `evaluate_many` and, with the `rayon` feature, `par_evaluate_many` for an
+/// extractor whose `evaluate` returns `Result<$output, EvaluateError>`.
+macro_rules! impl_evaluate_many {
+    ($($extractor:ty => $output:ty),* $(,)?) => {
+        $(
+            impl $extractor {
+                /// Evaluates every record in `records`, returning one result per record
+                /// in input order.
+                pub fn evaluate_many<T: Serialize>(
+                    &self,
+                    records: &[T],
+                ) -> Vec<Result<$output, EvaluateError>> {
+                    records.iter().map(|record| self.evaluate(record)).collect()
+                }
+
+                /// Like [`evaluate_many`](Self::evaluate_many), but evaluates the records
+                /// in parallel on the rayon thread pool. Requires the `rayon` feature.
+                #[cfg(feature = "rayon")]
+                pub fn par_evaluate_many<T: Serialize + Sync>(
+                    &self,
+                    records: &[T],
+                ) -> Vec<Result<$output, EvaluateError>> {
+                    use rayon::prelude::*;
+
+                    records.par_iter().map(|record| self.evaluate(record)).collect()
+                }
+            }
+        )*
+    };
+}
+
+impl_evaluate_many! {
+    FieldExtractor => FieldScalarValue,
+    NestedFieldExtractor => FieldScalarValue,
+    ListFieldExtractor => Vec<FieldScalarValue>,
+    NestedListFieldExtractor => Vec<FieldScalarValue>,
+}
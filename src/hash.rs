@@ -0,0 +1,135 @@
+//! Stable hashing of [`FieldScalarValue`] tuples, for sharding and partitioning.
+//!
+//! Unlike `std::hash::Hash`, whose output may change between platforms and Rust or
+//! crate releases, these hashes are part of the crate's format and never change:
+//!
+//! * The values are encoded with [`encode_key`](crate::encode_key): each value is a
+//!   one-byte type tag followed by its payload, so `1u32` and `1u64`, or `"1"` and
+//!   `1`, hash differently. The tags are `0x01` for `Unit`, then `Bool`, `I8`, `I16`,
+//!   `I32`, `I64`, `I128`, `U8`, `U16`, `U32`, `U64`, `U128`, `F32`, `F64`, `Char`,
+//!   `String`, `Bytes`, up to `0x12` for `Option`, in [`ScalarKind`](crate::ScalarKind)
+//!   order. Payloads are big-endian; strings and bytes are escaped and terminated,
+//!   so `("ab", "c")` and `("a", "bc")` hash differently.
+//! * [`hash64`] is the 64-bit FNV-1a hash of the encoding, and [`hash128`] the
+//!   128-bit FNV-1a hash.
+//!
+//! [`jump_consistent_hash`] and [`modulo_bucket`] map a hash to one of `n` buckets.
+
+use crate::encoding::encode_value;
+use crate::value::FieldScalarValue;
+use std::num::NonZeroU32;
+
+/// Returns the 64-bit FNV-1a hash of the encoding of `values`.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_evaluate::hash::hash64;
+/// use serde_evaluate::FieldScalarValue;
+///
+/// let key = [FieldScalarValue::U64(42), FieldScalarValue::String("eu".to_string())];
+/// assert_eq!(hash64(&key), 0x9e24_427a_b07f_c866);
+/// ```
+pub fn hash64(values: &[FieldScalarValue]) -> u64 {
+    let mut hasher = Fnv1a64::new();
+    stream(values, |bytes| hasher.write(bytes));
+    hasher.finish()
+}
+
+/// Returns the 128-bit FNV-1a hash of the encoding of `values`.
+pub fn hash128(values: &[FieldScalarValue]) -> u128 {
+    let mut hasher = Fnv1a128::new();
+    stream(values, |bytes| hasher.write(bytes));
+    hasher.finish()
+}
+
+/// Assigns `key` to one of `buckets` buckets with Lamping and Veach's jump consistent
+/// hash.
+///
+/// Buckets are balanced, and growing from `n` to `n + 1` buckets only moves about
+/// `1 / (n + 1)` of the keys, all of them into the new bucket `n`. Buckets can only be
+/// added or removed at the end.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_evaluate::hash::jump_consistent_hash;
+/// use std::num::NonZeroU32;
+///
+/// let shards = NonZeroU32::new(16).unwrap();
+/// let shard = jump_consistent_hash(0x9e24_427a_b07f_c866, shards);
+/// assert!(shard < 16);
+/// ```
+pub fn jump_consistent_hash(key: u64, buckets: NonZeroU32) -> u32 {
+    let buckets = i64::from(buckets.get());
+    let mut key = key;
+    let (mut bucket, mut next) = (-1i64, 0i64);
+    while next < buckets {
+        bucket = next;
+        key = key.wrapping_mul(2_862_933_555_777_941_757).wrapping_add(1);
+        next = ((bucket + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    bucket as u32
+}
+
+/// Assigns `hash` to one of `buckets` buckets by remainder.
+///
+/// Changing the number of buckets moves most keys; prefer [`jump_consistent_hash`]
+/// when buckets are added over time.
+pub fn modulo_bucket(hash: u64, buckets: NonZeroU32) -> u32 {
+    (hash % u64::from(buckets.get())) as u32
+}
+
+/// Encodes each value in turn and hands its bytes to `write`.
+fn stream(values: &[FieldScalarValue], mut write: impl FnMut(&[u8])) {
+    let mut buffer = Vec::new();
+    for value in values {
+        buffer.clear();
+        encode_value(&mut buffer, value);
+        write(&buffer);
+    }
+}
+
+/// Incremental 64-bit FNV-1a.
+struct Fnv1a64(u64);
+
+impl Fnv1a64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Fnv1a64(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Incremental 128-bit FNV-1a.
+struct Fnv1a128(u128);
+
+impl Fnv1a128 {
+    const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+    fn new() -> Self {
+        Fnv1a128(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u128::from(byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn finish(&self) -> u128 {
+        self.0
+    }
+}
@@ -0,0 +1,340 @@
+use crate::encoding::{encode_key, encode_value};
+use crate::error::EvaluateError;
+use crate::extractor::{NestedFieldExtractor, NestedListFieldExtractor};
+use crate::value::FieldScalarValue;
+use serde::Serialize;
+
+/// A secondary index over `Serialize` records.
+///
+/// An index has a name, an ordered list of key columns, optional value columns and a
+/// unique flag. Each key column is either a scalar path or a fan-out path pointing at a
+/// `Vec<T>`; a record produces one entry per combination of fan-out elements.
+///
+/// [`entries`](Self::entries) turns a record and its primary key into fully encoded
+/// [`IndexEntry`] items, using the order-preserving [`encoding`](crate::encoding):
+///
+/// | index      | `key_bytes`                       | `value_bytes`                  |
+/// |------------|-----------------------------------|--------------------------------|
+/// | non-unique | `(name, key columns.., pk..)`     | `(value columns..)`            |
+/// | unique     | `(name, key columns..)`           | `(pk.., value columns..)`      |
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{encode_key, EvaluateError, FieldScalarValue, IndexDefinition};
+///
+/// #[derive(Serialize)]
+/// struct Article {
+///     id: u64,
+///     author: String,
+///     tags: Vec<String>,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let index = IndexDefinition::new("by_author_tag")
+///         .with_key_path(&["author"])?
+///         .with_fan_out_path(&["tags"])?;
+///
+///     let article = Article {
+///         id: 7,
+///         author: "alice".to_string(),
+///         tags: vec!["rust".to_string(), "serde".to_string()],
+///     };
+///     let entries = index.entries(&article, &[FieldScalarValue::U64(article.id)])?;
+///
+///     assert_eq!(entries.len(), 2);
+///     assert_eq!(
+///         entries[0].key_bytes,
+///         encode_key(&[
+///             FieldScalarValue::String("by_author_tag".to_string()),
+///             FieldScalarValue::String("alice".to_string()),
+///             FieldScalarValue::String("rust".to_string()),
+///             FieldScalarValue::U64(7),
+///         ])
+///     );
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexDefinition {
+    name: String,
+    key_columns: Vec<KeyColumn>,
+    value_columns: Vec<NestedFieldExtractor>,
+    unique: bool,
+}
+
+#[derive(Debug, Clone)]
+enum KeyColumn {
+    Scalar(NestedFieldExtractor),
+    FanOut(NestedListFieldExtractor),
+}
+
+/// The column values of one record: each key column as a list (one element for scalar
+/// columns), then the value columns.
+#[derive(Debug, PartialEq)]
+struct Extracted {
+    keys: Vec<Vec<FieldScalarValue>>,
+    values: Vec<FieldScalarValue>,
+}
+
+/// One encoded entry of a secondary index, ready to be written to a key-value store.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IndexEntry {
+    /// The order-preserving encoded key.
+    pub key_bytes: Vec<u8>,
+    /// The encoded value.
+    pub value_bytes: Vec<u8>,
+}
+
+/// The index entries to delete and to write after a record update, as computed by
+/// [`IndexDefinition::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexDiff {
+    /// Entries whose key must be deleted, sorted by key.
+    pub removed: Vec<IndexEntry>,
+    /// Entries that must be written, sorted by key.
+    pub added: Vec<IndexEntry>,
+}
+
+impl IndexDiff {
+    /// Returns `true` if the update does not touch the index.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty()
+    }
+}
+
+impl IndexDefinition {
+    /// Creates a non-unique index with no columns.
+    ///
+    /// Add key columns with [`with_key_path`](Self::with_key_path) and
+    /// [`with_fan_out_path`](Self::with_fan_out_path) before calling [`entries`](Self::entries).
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        IndexDefinition {
+            name: name.into(),
+            key_columns: Vec::new(),
+            value_columns: Vec::new(),
+            unique: false,
+        }
+    }
+
+    /// Appends a key column holding the scalar at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if the path or any segment is empty.
+    pub fn with_key_path<S: AsRef<str>>(mut self, path: &[S]) -> Result<Self, EvaluateError> {
+        let extractor = NestedFieldExtractor::new_from_path(path)?;
+        self.key_columns.push(KeyColumn::Scalar(extractor));
+        Ok(self)
+    }
+
+    /// Appends a key column that fans out over the elements of the `Vec<T>` at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if the path or any segment is empty.
+    pub fn with_fan_out_path<S: AsRef<str>>(mut self, path: &[S]) -> Result<Self, EvaluateError> {
+        let extractor = NestedListFieldExtractor::new_from_path(path)?;
+        self.key_columns.push(KeyColumn::FanOut(extractor));
+        Ok(self)
+    }
+
+    /// Appends a value column holding the scalar at `path`, stored in `value_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if the path or any segment is empty.
+    pub fn with_value_path<S: AsRef<str>>(mut self, path: &[S]) -> Result<Self, EvaluateError> {
+        let extractor = NestedFieldExtractor::new_from_path(path)?;
+        self.value_columns.push(extractor);
+        Ok(self)
+    }
+
+    /// Marks the index as unique: the primary key moves from the key to the value.
+    pub fn with_unique(mut self, unique: bool) -> Self {
+        self.unique = unique;
+        self
+    }
+
+    /// Returns the index name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if the index is unique.
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    /// Builds the index entries of `record`, sorted by key.
+    ///
+    /// Fan-out columns produce the cartesian product of their elements. An empty list
+    /// produces no entries, and duplicate elements produce a single entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if the index has no key column, or the first
+    /// error from evaluating a key or value column against `record`.
+    pub fn entries<T: Serialize>(
+        &self,
+        record: &T,
+        primary_key: &[FieldScalarValue],
+    ) -> Result<Vec<IndexEntry>, EvaluateError> {
+        Ok(self.encode_entries(&self.extract(record)?, primary_key))
+    }
+
+    /// Computes the index maintenance needed when a record changes from `old` to `new`.
+    ///
+    /// Both versions are evaluated with the same paths. If every extracted key and value
+    /// column is equal, the diff is empty and nothing is encoded. Otherwise the entries of
+    /// both versions are compared as sets:
+    ///
+    /// * `removed` holds the entries whose key no longer exists in `new`.
+    /// * `added` holds the entries of `new` whose key is new or whose value changed.
+    ///
+    /// Because duplicate fan-out elements collapse into one entry, dropping one of two
+    /// equal list elements removes nothing, and reordering a list changes nothing.
+    ///
+    /// `primary_key` must be the same for both versions; a primary key change is a delete
+    /// of the old record's [`entries`](Self::entries) followed by an insert of the new one's.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use serde_evaluate::{EvaluateError, FieldScalarValue, IndexDefinition};
+    ///
+    /// #[derive(Serialize)]
+    /// struct Article {
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// fn main() -> Result<(), EvaluateError> {
+    ///     let index = IndexDefinition::new("by_tag").with_fan_out_path(&["tags"])?;
+    ///     let pk = [FieldScalarValue::U64(1)];
+    ///
+    ///     let old = Article { tags: vec!["a".to_string(), "b".to_string()] };
+    ///     let new = Article { tags: vec!["b".to_string(), "c".to_string()] };
+    ///
+    ///     let diff = index.diff(&old, &new, &pk)?;
+    ///     assert_eq!(diff.removed, index.entries(&Article { tags: vec!["a".to_string()] }, &pk)?);
+    ///     assert_eq!(diff.added, index.entries(&Article { tags: vec!["c".to_string()] }, &pk)?);
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `EvaluateError` from evaluating the index against either version.
+    pub fn diff<T: Serialize>(
+        &self,
+        old: &T,
+        new: &T,
+        primary_key: &[FieldScalarValue],
+    ) -> Result<IndexDiff, EvaluateError> {
+        let old = self.extract(old)?;
+        let new = self.extract(new)?;
+        if old == new {
+            return Ok(IndexDiff::default());
+        }
+
+        let old = self.encode_entries(&old, primary_key);
+        let new = self.encode_entries(&new, primary_key);
+
+        // Both lists are sorted by key and hold at most one entry per key.
+        let removed = old
+            .iter()
+            .filter(|entry| {
+                new.binary_search_by(|other| other.key_bytes.cmp(&entry.key_bytes))
+                    .is_err()
+            })
+            .cloned()
+            .collect();
+        let added = new
+            .into_iter()
+            .filter(|entry| old.binary_search(entry).is_err())
+            .collect();
+
+        Ok(IndexDiff { removed, added })
+    }
+
+    /// Evaluates every key and value column against `record`.
+    fn extract<T: Serialize>(&self, record: &T) -> Result<Extracted, EvaluateError> {
+        if self.key_columns.is_empty() {
+            return Err(EvaluateError::InvalidPath(format!(
+                "Index '{}' has no key columns",
+                self.name
+            )));
+        }
+
+        let keys = self
+            .key_columns
+            .iter()
+            .map(|column| match column {
+                KeyColumn::Scalar(extractor) => extractor.evaluate(record).map(|v| vec![v]),
+                KeyColumn::FanOut(extractor) => extractor.evaluate(record),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let values = self
+            .value_columns
+            .iter()
+            .map(|extractor| extractor.evaluate(record))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Extracted { keys, values })
+    }
+
+    /// Expands fan-out columns into one key tuple per combination and encodes each entry.
+    fn encode_entries(
+        &self,
+        extracted: &Extracted,
+        primary_key: &[FieldScalarValue],
+    ) -> Vec<IndexEntry> {
+        let mut tuples = vec![Vec::with_capacity(extracted.keys.len())];
+        for elements in &extracted.keys {
+            tuples = tuples
+                .iter()
+                .flat_map(|tuple| {
+                    elements.iter().map(move |element| {
+                        let mut tuple: Vec<FieldScalarValue> = tuple.clone();
+                        tuple.push(element.clone());
+                        tuple
+                    })
+                })
+                .collect();
+        }
+
+        let mut entries: Vec<IndexEntry> = tuples
+            .iter()
+            .map(|key| self.encode_entry(key, primary_key, &extracted.values))
+            .collect();
+        entries.sort();
+        entries.dedup();
+        entries
+    }
+
+    fn encode_entry(
+        &self,
+        key: &[FieldScalarValue],
+        primary_key: &[FieldScalarValue],
+        values: &[FieldScalarValue],
+    ) -> IndexEntry {
+        let mut key_bytes = Vec::new();
+        encode_value(&mut key_bytes, &FieldScalarValue::String(self.name.clone()));
+        key_bytes.extend(encode_key(key));
+
+        let mut value_bytes = Vec::new();
+        if self.unique {
+            value_bytes.extend(encode_key(primary_key));
+        } else {
+            key_bytes.extend(encode_key(primary_key));
+        }
+        value_bytes.extend(encode_key(values));
+
+        IndexEntry {
+            key_bytes,
+            value_bytes,
+        }
+    }
+}
@@ -13,13 +13,34 @@
 //!
 //! *   **Extract Scalar Fields:** Retrieve basic scalar types (integers, floats, bool, char, String) from any level of a struct or map.
 //! *   **Nested Field Access:** Access fields within nested structs or maps using dot (`.`) or index (`[key]`) notation (e.g., `"outer.inner.field"`, `"map[key].field"`).
-//! *   **Composite Extraction:** Extract multiple independent scalar fields at once, returning an ordered `Vec<FieldScalarValue>` for building composite index keys.
+//! *   **Composite Extraction:** Extract multiple independent scalar fields at once, returning an ordered `Vec<FieldScalarValue>` for building composite index keys, or one result per field with `evaluate_all` to report every failing path at once.
 //! *   **List Extraction (FanOut):** Extract `Vec<T>` fields where T is a scalar, returning each element separately for indexing.
 //! *   **Option Handling:**
 //!     *   `Option<Scalar>`: Correctly extracts as `Some(Scalar)` or `None`.
 //!     *   `Option<Option<Scalar>>`: Extracts nested `Option` types (e.g., `Some(Some(Scalar))`, `Some(None)`, `None`).
 //!     *   `Option<Vec<T>>` with `None`: Returns empty list when using list extractors.
+//! *   **Borrowed Evaluation:** `evaluate_with` hands the value to a callback as a `FieldScalarRef`, borrowing strings and bytes instead of copying them.
+//! *   **Existence Checks:** `NestedFieldExtractor::exists` reports whether a path is missing, `None`, or present (with its shape) for any target type, without capturing the value.
+//! *   **Type Introspection:** `NestedFieldExtractor::describe` reports the shape of the value at a path (scalar kind, struct name and field count, map, sequence length, enum variant, option) as a `FieldKind`.
+//! *   **Leaf Enumeration:** `paths` walks any `Serialize` value and returns the path and `FieldScalarValue` of every scalar leaf, for dotted-key flattening and discovery.
+//! *   **Schema Discovery:** `SchemaAggregator` folds many records into per-path statistics (observed variants, null and missing counts, min/max, distinct-count estimate, list lengths) to spot index candidates and schema drift.
+//! *   **Secondary Indexes:** `IndexDefinition` combines key, fan-out and value paths into encoded `IndexEntry` items and computes the entries to delete and insert on update (`diff`), using an order-preserving, type-tagged key encoding (`encode_key`).
+//! *   **Predicates:** `Predicate` compiles expressions such as `status == "active" && score >= 10.0 && tags contains "vip"` and evaluates them against any `Serialize` record.
+//! *   **Computed Fields:** `Expression` applies functions such as `lower(email)`, `truncate(ts, 3600)` or `coalesce(nick, name)` to extracted values; custom functions are added through a `FunctionRegistry`.
+//! *   **Sorting:** `PathComparator` sorts records by several paths, each ascending or descending, with configurable null ordering, extracting each record's key once.
+//! *   **Aggregation:** `group_by` groups records by key paths and computes `count`, `sum`, `min`, `max`, `avg` and `count_distinct` per group, returned in key order.
+//! *   **Encoded Records:** `evaluate_from` extracts a field straight from a self-describing `serde::Deserializer` (JSON, CBOR, ...) through a `PathSeed`, skipping everything off the path instead of deserializing the record.
+//! *   **Compiled Extraction:** `CompiledExtractor` merges many paths into a trie and extracts them all in one serialization pass, reusing an `ExtractionState` so repeated evaluation allocates only the extracted values.
+//! *   **Batch Evaluation:** Every extractor has `evaluate_many`, which evaluates a slice of records and returns one result per record in input order; with the `rayon` feature, `par_evaluate_many` does the same in parallel.
+//! *   **Typed Paths:** With the `derive` feature, `#[derive(Evaluate)]` generates builders such as `UserProfile::paths().settings().level()`, checked by the compiler against the struct definition (honouring `#[serde(rename)]`) and returning the field's own type.
 //! *   **Bytes Support:** Extracts `Vec<u8>` when annotated with `#[serde(with = "serde_bytes")]`.
+//! *   **Skipped Fields:** A field skipped by serde (e.g. `skip_serializing_if`) is reported as `NotFoundReason::Skipped` instead of looking like a typo, and `SkippedFieldPolicy::AsNone` extracts it as `None`.
+//! *   **Missing Paths:** `MissingPolicy` makes an extractor, or one path of a composite extractor, return `None` or a default value instead of failing when its path is not found.
+//! *   **Duplicate Keys:** `DuplicateKeyPolicy::Error` fails with `DuplicateKey`, naming both entries, when a path segment matches the same key twice in one struct or map, as `#[serde(flatten)]` or raw JSON input can produce.
+//! *   **Tagged Enums and Flatten:** The tag of an internally or adjacently tagged enum is addressable (`shape.type`) and extracts as the variant name, and fields of `#[serde(flatten)]` structs are addressed through the outer struct (see [Enums and Flattened Fields](#enums-and-flattened-fields)).
+//! *   **Key Matching:** `with_key_matching` compares path segments with keys exactly, ignoring ASCII case, or ignoring naming convention (`user_id` matches `userId`, `user-id` and `UserId`), and `with_aliases` lets a segment match several names, so paths survive serde renames.
+//! *   **Stable Hashing:** `CompositeFieldExtractor::hash64` and `hash128` hash the extracted values with a portable, type-tagged encoding that never changes between platforms or releases, and `hash::jump_consistent_hash` and `hash::modulo_bucket` map the hash to a shard.
+//! *   **Strict Mode:** `with_strict_mode(true)` serializes the whole record before extracting, so an error raised by a `Serialize` impl off the path is returned as `SerializationError` instead of going unnoticed.
 //! *   **Error Handling:** Returns specific errors for unsupported types (`UnsupportedType`) or missing fields (`FieldNotFound`, `NestedFieldNotFound`).
 //!
 //! ## How it Works
@@ -302,16 +323,59 @@
 //! or `FieldScalarValue::Option(None)`), traversing *through* an `Option` to access fields within the `Some` variant
 //! (e.g., `opt_struct.inner_field`) is currently **not supported**. The extraction path must target the `Option` itself.
 //!
+//! ## Enums and Flattened Fields
+//!
+//! How a path addresses an enum depends on its serde representation:
+//!
+//! | Representation | Tag path | Payload path | Serialized and deserialized records agree |
+//! |---|---|---|---|
+//! | Externally tagged (default) | — | not traversable (`UnsupportedVariant`) | no: `evaluate_from` traverses `shape.Circle.r` |
+//! | Internally tagged (`tag = "type"`) | `shape.type` → `String` | `shape.r` | yes |
+//! | Adjacently tagged (`tag = "t", content = "c"`) | `shape.t` → `String` | `shape.c.r` | yes |
+//! | Untagged | — | `shape.r` | yes |
+//!
+//! A unit variant of an externally tagged enum extracts as `Unit` from a record and as the
+//! variant name from deserialized input.
+//!
+//! Serde serializes an adjacently tagged enum as a struct named after the enum, whose
+//! first field is the tag. A record struct of the same shape (one or two fields, the first
+//! holding a unit variant of an enum with the struct's own name) is indistinguishable
+//! from it, and that field also extracts as the variant name.
+//!
+//! Fields of a `#[serde(flatten)]` struct are addressed as if they belonged to the outer
+//! struct. Serde serializes a struct containing flattened fields as a map, so a missing
+//! field is reported as `MissingKey`, `describe` returns a `Map` without a length, and
+//! fields skipped inside the flattened struct are not reported as skipped.
+//!
 //!
 // Declare modules
+pub mod aggregate;
+pub mod deserializer;
+pub mod encoding;
 pub mod error;
+pub mod expr;
 pub mod extractor;
+pub mod hash;
+pub mod index;
+pub mod path;
+pub mod probe;
+pub mod schema;
 pub mod serializer;
+pub mod sort;
+pub mod typed;
 pub mod value;
 
 // Re-export public API
+/// Group-by and aggregation over records.
+pub use aggregate::{group_by, Aggregate, Aggregates};
+/// Extraction from encoded records through a `DeserializeSeed`.
+pub use deserializer::PathSeed;
+/// Order-preserving encoding of scalar tuples.
+pub use encoding::encode_key;
 /// Errors that can occur during field extraction.
-pub use error::EvaluateError;
+pub use error::{EvaluateError, NotFoundReason};
+/// Boolean and computed expressions evaluated against records.
+pub use expr::{ExprValue, Expression, FunctionRegistry, Predicate};
 /// Public interface for extracting multiple scalar field values as an ordered Vec.
 pub use extractor::CompositeFieldExtractor;
 /// Public interface for extracting top-level scalar field values.
@@ -322,5 +386,30 @@ pub use extractor::ListFieldExtractor;
 pub use extractor::NestedFieldExtractor;
 /// Public interface for extracting list of scalar values from a nested Vec<T> field.
 pub use extractor::NestedListFieldExtractor;
+/// Single-pass extraction of multiple paths, with reusable state.
+pub use extractor::{CompiledExtractor, ExtractionState};
+/// How extractors report skipped fields, missing paths and duplicate keys.
+pub use extractor::{DuplicateKeyPolicy, MissingPolicy, SkippedFieldPolicy};
+/// Secondary index definitions and their encoded entries.
+pub use index::{IndexDefinition, IndexDiff, IndexEntry};
+/// How path segments are compared with field names and map keys.
+pub use path::KeyMatching;
+/// Enumerates every scalar leaf of a record, with its path.
+pub use path::{join_path, paths, PathSegment};
+/// Outcome of probing whether a path exists, and the shape of the value found there.
+pub use probe::{FieldKind, PathProbe, VariantShape};
+/// Per-path statistics aggregated over a sample of records.
+pub use schema::{PathStats, SchemaAggregator};
+/// Derives typed, compile-time-checked paths for a struct.
+#[cfg(feature = "derive")]
+pub use serde_evaluate_derive::Evaluate;
+/// Multi-key sorting of records by path.
+pub use sort::{NullOrder, PathComparator, SortOrder};
+/// Typed paths generated by `#[derive(Evaluate)]`.
+pub use typed::{Evaluate, FromScalar, PathField, TypedListPath, TypedPath};
+/// Borrowed view of an extracted scalar, passed to `evaluate_with` callbacks.
+pub use value::FieldScalarRef;
 /// Enum representing the possible scalar values that can be extracted.
 pub use value::FieldScalarValue;
+/// The variant of a scalar value without its payload.
+pub use value::ScalarKind;
@@ -0,0 +1,188 @@
+use crate::error::EvaluateError;
+use crate::serializer::LeafCollectorSerializer;
+use crate::value::FieldScalarValue;
+use serde::Serialize;
+use std::fmt;
+
+/// One step of a path to a leaf, as produced by [`paths`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PathSegment {
+    /// A struct field name, map key, or enum variant name.
+    Key(String),
+    /// The position of an element within a sequence or tuple. Extractors cannot follow
+    /// this segment.
+    Index(usize),
+}
+
+impl PathSegment {
+    /// Returns the key if this segment is a [`PathSegment::Key`].
+    pub fn as_key(&self) -> Option<&str> {
+        match self {
+            PathSegment::Key(key) => Some(key),
+            PathSegment::Index(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Key(key) => f.write_str(key),
+            PathSegment::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+/// How an extractor compares its path segments (and their aliases) with field names
+/// and map keys.
+///
+/// Paths match the serialized key exactly by default, so renaming fields with
+/// `#[serde(rename_all = "camelCase")]` stops `user_id` from matching `userId`. The
+/// looser modes let a path survive such refactors.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{EvaluateError, FieldScalarValue, KeyMatching, NestedFieldExtractor};
+///
+/// #[derive(Serialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct User {
+///     user_id: u64,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let user = User { user_id: 7 };
+///     let extractor = NestedFieldExtractor::new_from_path(&["user_id"])?;
+///     assert!(extractor.evaluate(&user).is_err());
+///
+///     let extractor = extractor.with_key_matching(KeyMatching::NamingConvention);
+///     assert_eq!(extractor.evaluate(&user)?, FieldScalarValue::U64(7));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum KeyMatching {
+    /// Keys must equal the segment.
+    #[default]
+    Exact,
+    /// Keys may differ from the segment in ASCII case: `userid` matches `userId`.
+    AsciiCaseInsensitive,
+    /// Keys are compared ignoring `_`, `-` and ASCII case, so the snake, camel, kebab,
+    /// Pascal and screaming snake case spellings of a name all match each other.
+    NamingConvention,
+}
+
+impl KeyMatching {
+    /// True if `key` matches `name` in this mode.
+    pub(crate) fn matches(self, name: &str, key: &str) -> bool {
+        match self {
+            KeyMatching::Exact => name == key,
+            KeyMatching::AsciiCaseInsensitive => name.eq_ignore_ascii_case(key),
+            KeyMatching::NamingConvention => {
+                name == key || normalized_name(name).eq(normalized_name(key))
+            }
+        }
+    }
+
+    /// True if `key` matches `segment` or one of its `aliases` in this mode.
+    pub(crate) fn matches_any(self, segment: &str, aliases: &[String], key: &str) -> bool {
+        self.matches(segment, key) || aliases.iter().any(|alias| self.matches(alias, key))
+    }
+}
+
+/// The bytes of `name` without `_` and `-`, in ASCII lowercase.
+fn normalized_name(name: &str) -> impl Iterator<Item = u8> + '_ {
+    name.bytes()
+        .filter(|b| *b != b'_' && *b != b'-')
+        .map(|b| b.to_ascii_lowercase())
+}
+
+/// Returns the aliases of segment `index`, given the aliases of each segment of a path.
+pub(crate) fn aliases_of(aliases: &[Vec<String>], index: usize) -> &[String] {
+    aliases.get(index).map_or(&[], Vec::as_slice)
+}
+
+/// Renders a path as a dotted key, with sequence positions in brackets
+/// (e.g. `"metadata.tags[1]"`).
+///
+/// Keys are written as-is, so a key that itself contains a `.` is ambiguous in the output.
+pub fn join_path(path: &[PathSegment]) -> String {
+    let mut out = String::new();
+    for segment in path {
+        if matches!(segment, PathSegment::Key(_)) && !out.is_empty() {
+            out.push('.');
+        }
+        out.push_str(&segment.to_string());
+    }
+    out
+}
+
+/// Enumerates every scalar leaf of `record`, in serialization order.
+///
+/// This is the dual of the targeted extractors: instead of following one path, it walks
+/// the whole value and records the path and [`FieldScalarValue`] of each scalar.
+///
+/// * Struct fields, map keys and enum variant names become [`PathSegment::Key`]s.
+///   Integer, char and bool map keys are rendered with `to_string`, which is also how
+///   the extractors match them against path segments.
+/// * Sequence and tuple elements become [`PathSegment::Index`]es. The extractors do not
+///   index into sequences, so a path containing an `Index` is for display only; one
+///   made only of keys (see [`PathSegment::as_key`]) can be passed to
+///   [`NestedFieldExtractor::new_from_path`](crate::NestedFieldExtractor::new_from_path),
+///   unless it goes through an externally tagged enum variant.
+/// * `Option<Scalar>` leaves keep their `Option` wrapper, like [`FieldExtractor`](crate::FieldExtractor).
+///   `Some(..)` around a struct, map or sequence is looked through.
+/// * Unit variants are leaves with the value [`FieldScalarValue::Unit`], except the tag
+///   of an adjacently tagged enum, which is the variant name as a `String`.
+/// * Empty sequences and maps produce no leaves.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{paths, join_path, FieldScalarValue, EvaluateError};
+///
+/// #[derive(Serialize)]
+/// struct Record {
+///     id: u32,
+///     meta: Meta,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Meta {
+///     tags: Vec<String>,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let record = Record {
+///         id: 1,
+///         meta: Meta { tags: vec!["a".to_string(), "b".to_string()] },
+///     };
+///
+///     let flattened: Vec<(String, FieldScalarValue)> = paths(&record)?
+///         .into_iter()
+///         .map(|(path, value)| (join_path(&path), value))
+///         .collect();
+///
+///     assert_eq!(flattened, vec![
+///         ("id".to_string(), FieldScalarValue::U32(1)),
+///         ("meta.tags[0]".to_string(), FieldScalarValue::String("a".to_string())),
+///         ("meta.tags[1]".to_string(), FieldScalarValue::String("b".to_string())),
+///     ]);
+///     Ok(())
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Returns `EvaluateError` if a map key is not a string, integer, char or bool
+/// ([`EvaluateError::UnsupportedType`]), or if the record's `Serialize` impl fails.
+pub fn paths<T: Serialize>(
+    record: &T,
+) -> Result<Vec<(Vec<PathSegment>, FieldScalarValue)>, EvaluateError> {
+    let mut serializer = LeafCollectorSerializer::new();
+    record.serialize(&mut serializer)?;
+    Ok(serializer.into_leaves())
+}
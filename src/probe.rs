@@ -0,0 +1,107 @@
+use crate::value::ScalarKind;
+
+/// Outcome of probing a path with [`NestedFieldExtractor::exists`](crate::NestedFieldExtractor::exists).
+///
+/// Unlike [`NestedFieldExtractor::evaluate`](crate::NestedFieldExtractor::evaluate), probing
+/// accepts any target type and never captures the value itself.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathProbe {
+    /// The path does not exist in the record.
+    Missing,
+    /// The path exists and holds `Option::None`, at any level of `Option` nesting.
+    Null,
+    /// The path exists and holds a value. `Some(..)` wrappers are looked through,
+    /// so `kind` describes the innermost value and is never [`FieldKind::Option`].
+    Present {
+        /// The shape of the value found at the path.
+        kind: FieldKind,
+    },
+}
+
+impl PathProbe {
+    /// Returns `true` if the path exists and is not `None`.
+    pub fn is_present(&self) -> bool {
+        matches!(self, PathProbe::Present { .. })
+    }
+}
+
+impl From<FieldKind> for PathProbe {
+    /// Peels `Option` layers off a described kind: any `None` becomes [`PathProbe::Null`].
+    fn from(kind: FieldKind) -> Self {
+        match kind {
+            FieldKind::Option(None) => PathProbe::Null,
+            FieldKind::Option(Some(inner)) => PathProbe::from(*inner),
+            kind => PathProbe::Present { kind },
+        }
+    }
+}
+
+/// The shape of a value as seen by the serializer.
+///
+/// Built from the `name`, `len` and `variant` arguments that Serde passes to each
+/// `serialize_*` call, so it reflects the serialized form: `#[serde(rename)]` names,
+/// and only the fields that were not skipped. Newtype structs are transparent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+    /// A scalar that can be extracted as a [`FieldScalarValue`](crate::FieldScalarValue).
+    Scalar(ScalarKind),
+    /// A struct (`serialize_struct`).
+    Struct {
+        /// The struct name.
+        name: &'static str,
+        /// The number of fields being serialized.
+        fields: usize,
+    },
+    /// A map (`serialize_map`).
+    Map {
+        /// The number of entries, if known up front.
+        len: Option<usize>,
+    },
+    /// A sequence such as `Vec<T>` (`serialize_seq`).
+    Seq {
+        /// The number of elements, if known up front.
+        len: Option<usize>,
+    },
+    /// A tuple (`serialize_tuple`).
+    Tuple {
+        /// The number of elements.
+        len: usize,
+    },
+    /// A tuple struct (`serialize_tuple_struct`).
+    TupleStruct {
+        /// The struct name.
+        name: &'static str,
+        /// The number of fields.
+        len: usize,
+    },
+    /// An enum variant.
+    Variant {
+        /// The enum name.
+        name: &'static str,
+        /// The variant name.
+        variant: &'static str,
+        /// The data the variant carries.
+        shape: VariantShape,
+    },
+    /// An `Option`. Contains `None` or the kind of the `Some` value.
+    Option(Option<Box<FieldKind>>),
+}
+
+/// The data carried by an enum variant described by [`FieldKind::Variant`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantShape {
+    /// A unit variant (`E::A`).
+    Unit,
+    /// A newtype variant (`E::A(T)`).
+    Newtype,
+    /// A tuple variant (`E::A(T, U)`).
+    Tuple {
+        /// The number of fields.
+        len: usize,
+    },
+    /// A struct variant (`E::A { .. }`).
+    Struct {
+        /// The number of fields being serialized.
+        fields: usize,
+    },
+}
@@ -0,0 +1,260 @@
+use crate::error::EvaluateError;
+use crate::path::{paths, PathSegment};
+use crate::value::{FieldScalarValue, ScalarKind};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
+
+/// Number of hashes kept by the distinct-count sketch. Below this many distinct
+/// values the count is exact.
+const SKETCH_SIZE: usize = 256;
+
+/// Aggregates per-path statistics over a sample of records.
+///
+/// Each record is flattened with [`paths`], and sequence positions are
+/// replaced by a `[*]` wildcard so that every element of a list contributes to the
+/// same path (e.g. `items[*].sku`). Call [`observe`](Self::observe) for every record,
+/// then [`report`](Self::report) to get one [`PathStats`] per path.
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{SchemaAggregator, FieldScalarValue, EvaluateError};
+///
+/// #[derive(Serialize)]
+/// struct Event {
+///     user: String,
+///     score: Option<u32>,
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let mut schema = SchemaAggregator::new();
+///     schema.observe(&Event { user: "alice".to_string(), score: Some(3) })?;
+///     schema.observe(&Event { user: "bob".to_string(), score: None })?;
+///
+///     let report = schema.report();
+///     let score = report.iter().find(|stats| stats.path == "score").unwrap();
+///     assert_eq!(score.nulls, 1);
+///     assert_eq!(score.max, Some(FieldScalarValue::U32(3)));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SchemaAggregator {
+    records: u64,
+    paths: BTreeMap<String, PathAccumulator>,
+}
+
+/// Statistics observed for one path by a [`SchemaAggregator`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathStats {
+    /// The path, with sequence positions written as `[*]`.
+    pub path: String,
+    /// The variants seen at this path, with `Some(..)` wrappers looked through.
+    /// Empty if the path only ever held `None`.
+    pub kinds: BTreeSet<ScalarKind>,
+    /// Number of leaves seen at this path, including `None`s. A path under a list
+    /// can contribute several leaves per record.
+    pub values: u64,
+    /// Number of leaves that were `Option::None`.
+    pub nulls: u64,
+    /// Number of observed records in which the path did not appear at all.
+    pub missing: u64,
+    /// The smallest non-null value, by [`FieldScalarValue::total_cmp`].
+    pub min: Option<FieldScalarValue>,
+    /// The largest non-null value, by [`FieldScalarValue::total_cmp`].
+    pub max: Option<FieldScalarValue>,
+    /// Estimated number of distinct non-null values. Exact below 256 distinct values.
+    pub distinct: u64,
+    /// For paths under a sequence, the largest element count seen in the innermost
+    /// sequence. Inferred from the leaves, so empty sequences are not counted.
+    pub max_list_len: Option<usize>,
+}
+
+impl SchemaAggregator {
+    /// Creates an empty aggregator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of records observed so far.
+    pub fn records(&self) -> u64 {
+        self.records
+    }
+
+    /// Flattens `record` and folds its leaves into the statistics.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`paths`] if the record cannot be
+    /// flattened. The record is then not counted.
+    pub fn observe<T: Serialize>(&mut self, record: &T) -> Result<(), EvaluateError> {
+        let leaves = paths(record)?;
+        self.records += 1;
+
+        for (path, value) in leaves {
+            let (normalized, list_len) = normalize(&path);
+            let accumulator = self.paths.entry(normalized).or_default();
+            accumulator.observe(self.records, value, list_len);
+        }
+        Ok(())
+    }
+
+    /// Returns the statistics of every path seen so far, sorted by path.
+    pub fn report(&self) -> Vec<PathStats> {
+        self.paths
+            .iter()
+            .map(|(path, accumulator)| accumulator.to_stats(path, self.records))
+            .collect()
+    }
+}
+
+/// Replaces indices with `[*]` and returns the element count implied by the last index.
+fn normalize(path: &[PathSegment]) -> (String, Option<usize>) {
+    let mut out = String::new();
+    let mut list_len = None;
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                if !out.is_empty() {
+                    out.push('.');
+                }
+                out.push_str(key);
+            }
+            PathSegment::Index(index) => {
+                out.push_str("[*]");
+                list_len = Some(index + 1);
+            }
+        }
+    }
+    (out, list_len)
+}
+
+#[derive(Debug, Clone, Default)]
+struct PathAccumulator {
+    kinds: BTreeSet<ScalarKind>,
+    /// Number of records in which the path appeared.
+    present_in: u64,
+    /// Ordinal of the last record that touched this path, to count each record once.
+    last_record: u64,
+    values: u64,
+    nulls: u64,
+    min: Option<FieldScalarValue>,
+    max: Option<FieldScalarValue>,
+    sketch: DistinctSketch,
+    max_list_len: Option<usize>,
+}
+
+impl PathAccumulator {
+    fn observe(&mut self, record: u64, value: FieldScalarValue, list_len: Option<usize>) {
+        if self.last_record != record {
+            self.last_record = record;
+            self.present_in += 1;
+        }
+        self.values += 1;
+        self.max_list_len = self.max_list_len.max(list_len);
+
+        let Some(value) = unwrap_some(value) else {
+            self.nulls += 1;
+            return;
+        };
+
+        self.kinds.insert(value.kind());
+        self.sketch.insert(&value);
+        if self
+            .min
+            .as_ref()
+            .is_none_or(|min| value.total_cmp(min) == Ordering::Less)
+        {
+            self.min = Some(value.clone());
+        }
+        if self
+            .max
+            .as_ref()
+            .is_none_or(|max| value.total_cmp(max) == Ordering::Greater)
+        {
+            self.max = Some(value);
+        }
+    }
+
+    fn to_stats(&self, path: &str, records: u64) -> PathStats {
+        PathStats {
+            path: path.to_string(),
+            kinds: self.kinds.clone(),
+            values: self.values,
+            nulls: self.nulls,
+            missing: records - self.present_in,
+            min: self.min.clone(),
+            max: self.max.clone(),
+            distinct: self.sketch.estimate(),
+            max_list_len: self.max_list_len,
+        }
+    }
+}
+
+/// Peels `Some(..)` layers; returns `None` for a null at any depth.
+fn unwrap_some(value: FieldScalarValue) -> Option<FieldScalarValue> {
+    match value {
+        FieldScalarValue::Option(None) => None,
+        FieldScalarValue::Option(Some(inner)) => unwrap_some(*inner),
+        value => Some(value),
+    }
+}
+
+/// K-minimum-values sketch: keeps the `SKETCH_SIZE` smallest value hashes.
+#[derive(Debug, Clone, Default)]
+struct DistinctSketch {
+    hashes: BTreeSet<u64>,
+}
+
+impl DistinctSketch {
+    fn insert(&mut self, value: &FieldScalarValue) {
+        let hash = hash_value(value);
+        if self.hashes.len() < SKETCH_SIZE {
+            self.hashes.insert(hash);
+        } else if let Some(&largest) = self.hashes.last() {
+            if hash < largest && self.hashes.insert(hash) {
+                self.hashes.pop_last();
+            }
+        }
+    }
+
+    fn estimate(&self) -> u64 {
+        if self.hashes.len() < SKETCH_SIZE {
+            return self.hashes.len() as u64;
+        }
+        // The k-th smallest of n uniform hashes sits near k / n of the hash space.
+        let kth = *self.hashes.last().expect("sketch is full") as f64;
+        ((SKETCH_SIZE - 1) as f64 * u64::MAX as f64 / kth) as u64
+    }
+}
+
+fn hash_value(value: &FieldScalarValue) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.kind().hash(&mut hasher);
+    match value {
+        FieldScalarValue::Unit => {}
+        FieldScalarValue::Bool(v) => v.hash(&mut hasher),
+        FieldScalarValue::I8(v) => v.hash(&mut hasher),
+        FieldScalarValue::I16(v) => v.hash(&mut hasher),
+        FieldScalarValue::I32(v) => v.hash(&mut hasher),
+        FieldScalarValue::I64(v) => v.hash(&mut hasher),
+        FieldScalarValue::I128(v) => v.hash(&mut hasher),
+        FieldScalarValue::U8(v) => v.hash(&mut hasher),
+        FieldScalarValue::U16(v) => v.hash(&mut hasher),
+        FieldScalarValue::U32(v) => v.hash(&mut hasher),
+        FieldScalarValue::U64(v) => v.hash(&mut hasher),
+        FieldScalarValue::U128(v) => v.hash(&mut hasher),
+        FieldScalarValue::F32(v) => v.to_bits().hash(&mut hasher),
+        FieldScalarValue::F64(v) => v.to_bits().hash(&mut hasher),
+        FieldScalarValue::Char(v) => v.hash(&mut hasher),
+        FieldScalarValue::String(v) => v.hash(&mut hasher),
+        FieldScalarValue::Bytes(v) => v.hash(&mut hasher),
+        // Options are unwrapped before reaching the sketch.
+        FieldScalarValue::Option(v) => v.as_deref().map(hash_value).hash(&mut hasher),
+    }
+    hasher.finish()
+}
@@ -3,26 +3,37 @@
 //! This is the core serializer that intercepts Serde serialization to extract
 //! targeted field values without full deserialization.
 
-use crate::error::EvaluateError;
-use crate::value::FieldScalarValue;
+use crate::error::{EvaluateError, NotFoundReason};
+use crate::path::{aliases_of, KeyMatching};
+use crate::probe::{FieldKind, VariantShape};
+use crate::value::{FieldScalarRef, FieldScalarValue};
 use serde::ser;
 use serde::{Serialize, Serializer};
 
 use super::key::StringKeySerializer;
 use super::list::{ListCapture, SeqSerializer};
 use super::skip::Skip;
-use super::{wrap_in_options, ExtractionMode};
+use super::{
+    adjacent_tag_enum, with_wrapped_in_options, wrap_in_options, wrap_kind_in_options,
+    ExtractionMode, ScalarCallback,
+};
 
 // =============================================================================
 // State Separation: Config, State, and Result
 // =============================================================================
 
 /// Immutable extraction configuration.
-struct ExtractorConfig {
-    /// Target path segments to traverse.
-    path: Vec<String>,
+struct ExtractorConfig<'p> {
+    /// Target path segments to traverse, borrowed from the extractor.
+    path: &'p [String],
     /// Whether extracting a scalar or list.
     extraction_mode: ExtractionMode,
+    /// Whether a segment matching two entries of the same struct or map is an error.
+    detect_duplicates: bool,
+    /// How segments are compared with field names and map keys.
+    matching: KeyMatching,
+    /// Extra names accepted for each segment, by segment index.
+    aliases: &'p [Vec<String>],
 }
 
 /// Mutable traversal state during serialization.
@@ -36,6 +47,18 @@ struct TraversalState {
     ready_to_capture: bool,
     /// Tracks nesting level when capturing Option<Option<...>>.
     option_nesting_level: u8,
+    /// Name of the newtype struct wrapping the target, reported for targets that
+    /// have no name of their own.
+    newtype_name: Option<&'static str>,
+    /// Position of the next entry of the struct or map being serialized.
+    entry_index: usize,
+    /// Name and length of the struct whose fields are being serialized, `None` in a map.
+    struct_shape: Option<(&'static str, usize)>,
+    /// Enum whose unit variants are adjacent tags, while serializing the field that
+    /// may hold one.
+    adjacent_tag: Option<&'static str>,
+    /// Position of the entry that matched each path segment, when detecting duplicates.
+    matched_entries: Vec<Option<usize>>,
 }
 
 /// Extraction results.
@@ -45,6 +68,8 @@ struct ExtractionResult {
     value: Option<FieldScalarValue>,
     /// Collected list elements (when in List mode).
     list_values: Vec<FieldScalarValue>,
+    /// The shape of the value at the target path (when in Describe mode).
+    kind: Option<FieldKind>,
 }
 
 // =============================================================================
@@ -52,55 +77,105 @@ struct ExtractionResult {
 // =============================================================================
 
 /// Custom Serializer Implementation for extracting field values.
-pub(crate) struct FieldValueExtractorSerializer {
+pub(crate) struct FieldValueExtractorSerializer<'p, 'f> {
     /// Immutable configuration.
-    config: ExtractorConfig,
+    config: ExtractorConfig<'p>,
     /// Mutable traversal state.
     state: TraversalState,
     /// Extraction results.
     result: ExtractionResult,
+    /// Optional callback receiving the captured scalar by reference.
+    callback: Option<&'f mut ScalarCallback<'f>>,
 }
 
-impl FieldValueExtractorSerializer {
+impl<'p, 'f> FieldValueExtractorSerializer<'p, 'f> {
     /// Core constructor with explicit path and extraction mode.
-    fn with_mode(path: Vec<String>, mode: ExtractionMode) -> Self {
+    fn with_mode(path: &'p [String], mode: ExtractionMode) -> Self {
         FieldValueExtractorSerializer {
             config: ExtractorConfig {
                 path,
                 extraction_mode: mode,
+                detect_duplicates: false,
+                matching: KeyMatching::Exact,
+                aliases: &[],
             },
             state: TraversalState::default(),
             result: ExtractionResult::default(),
+            callback: None,
         }
     }
 
-    /// Creates a new serializer for extracting a single top-level scalar field.
-    pub(crate) fn new(field_name: &str) -> Self {
-        Self::with_mode(vec![field_name.to_string()], ExtractionMode::Scalar)
+    /// Creates a new serializer for extracting a scalar field by path.
+    /// A top-level field is a one-segment path.
+    pub(crate) fn new_nested(path_segments: &'p [String]) -> Self {
+        Self::with_mode(path_segments, ExtractionMode::Scalar)
     }
 
-    /// Creates a new serializer for extracting a nested scalar field by path.
-    pub(crate) fn new_nested(path_segments: Vec<String>) -> Self {
-        Self::with_mode(path_segments, ExtractionMode::Scalar)
+    /// Creates a serializer configured to extract a list from a path.
+    pub(crate) fn new_nested_list(path_segments: &'p [String]) -> Self {
+        Self::with_mode(path_segments, ExtractionMode::List)
     }
 
-    /// Creates a serializer configured to extract a list from a top-level field.
-    pub(crate) fn new_list(field_name: &str) -> Self {
-        Self::with_mode(vec![field_name.to_string()], ExtractionMode::List)
+    /// Creates a serializer that only reports the shape of the value at a nested path.
+    pub(crate) fn new_describe(path_segments: &'p [String]) -> Self {
+        Self::with_mode(path_segments, ExtractionMode::Describe)
     }
 
-    /// Creates a serializer configured to extract a list from a nested path.
-    pub(crate) fn new_nested_list(path_segments: Vec<String>) -> Self {
-        Self::with_mode(path_segments, ExtractionMode::List)
+    /// Fails with `DuplicateKey` when a path segment matches a second entry of the
+    /// same struct or map, instead of following the first one only.
+    pub(crate) fn with_duplicate_detection(mut self) -> Self {
+        self.config.detect_duplicates = true;
+        self.state.matched_entries = vec![None; self.config.path.len()];
+        self
+    }
+
+    /// Compares segments with keys using `matching`, also accepting the names in
+    /// `aliases`, indexed by segment.
+    pub(crate) fn with_key_matching(
+        mut self,
+        matching: KeyMatching,
+        aliases: &'p [Vec<String>],
+    ) -> Self {
+        self.config.matching = matching;
+        self.config.aliases = aliases;
+        self
+    }
+
+    /// Hands captured scalars to `callback` by reference instead of storing an owned copy.
+    pub(crate) fn with_callback(mut self, callback: &'f mut ScalarCallback<'f>) -> Self {
+        self.callback = Some(callback);
+        self
     }
 
     /// Called by individual scalar serialize_* methods.
     /// Captures the value if ready_to_capture flag is set,
     /// potentially wrapping based on option_nesting_level.
-    fn capture_value(&mut self, value: FieldScalarValue) -> Result<(), EvaluateError> {
+    fn capture_value(&mut self, value: FieldScalarRef<'_>) -> Result<(), EvaluateError> {
+        if self.is_intermediate() {
+            return Err(match value {
+                FieldScalarRef::Unit | FieldScalarRef::Option(None) => {
+                    self.not_found(NotFoundReason::Null)
+                }
+                _ => self.not_traversable("scalar"),
+            });
+        }
         if self.state.ready_to_capture {
-            // Wrap the value according to the current nesting level.
-            self.result.value = Some(wrap_in_options(value, self.state.option_nesting_level));
+            if self.extraction_mode() == ExtractionMode::Describe {
+                self.record_kind(FieldKind::Scalar(value.kind()));
+                return Ok(());
+            }
+            let level = self.state.option_nesting_level;
+            match self.callback.as_deref_mut() {
+                Some(callback) => {
+                    with_wrapped_in_options(value, level, callback);
+                    // Sentinel to indicate the target was found; the value went to the callback.
+                    self.result.value = Some(FieldScalarValue::Unit);
+                }
+                None => {
+                    // Wrap the value according to the current nesting level.
+                    self.result.value = Some(wrap_in_options(value.into(), level));
+                }
+            }
         }
         Ok(())
     }
@@ -121,6 +196,31 @@ impl FieldValueExtractorSerializer {
         }
     }
 
+    /// Returns the shape of the value at the target path after describing.
+    /// Returns None if the target field was not reached.
+    pub(crate) fn into_kind_result(self) -> Option<FieldKind> {
+        self.result.kind
+    }
+
+    /// Records the kind of the target, wrapped according to option_nesting_level,
+    /// and stops capturing.
+    fn record_kind(&mut self, kind: FieldKind) {
+        self.result.kind = Some(wrap_kind_in_options(kind, self.state.option_nesting_level));
+        self.result.value = Some(FieldScalarValue::Unit); // Sentinel to indicate success
+        self.state.ready_to_capture = false;
+    }
+
+    /// In Describe mode, records a value found at the target and returns `true`
+    /// so the caller can skip its contents instead of capturing or failing.
+    fn describe_target(&mut self, kind: FieldKind) -> bool {
+        if self.state.ready_to_capture && self.extraction_mode() == ExtractionMode::Describe {
+            self.record_kind(kind);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Helper method for ListCapture to push a captured value.
     pub(crate) fn push_list_value(&mut self, value: FieldScalarValue) {
         self.result.list_values.push(value);
@@ -132,9 +232,133 @@ impl FieldValueExtractorSerializer {
         self.state.ready_to_capture = false;
     }
 
+    /// True while the value being serialized is before the end of the path, so it
+    /// must be traversed rather than captured.
+    fn is_intermediate(&self) -> bool {
+        self.state.current_path_index < self.config.path.len()
+    }
+
+    /// True if `key` matches the segment at `current_path_index`, which must be
+    /// before the end of the path.
+    fn matches_segment(&self, key: &str) -> bool {
+        let index = self.state.current_path_index;
+        self.config.matching.matches_any(
+            &self.config.path[index],
+            aliases_of(self.config.aliases, index),
+            key,
+        )
+    }
+
+    /// Builds the error for the segment at `current_path_index`, which could not be
+    /// followed.
+    fn not_found(&self, reason: NotFoundReason) -> EvaluateError {
+        EvaluateError::NestedFieldNotFound {
+            path: self.config.path.to_vec(),
+            failed_at_index: self.state.current_path_index,
+            reason,
+        }
+    }
+
+    /// Records that entry `entry` of the current struct or map matches the segment at
+    /// `current_path_index`. Fails if another entry already matched it.
+    fn match_entry(&mut self, entry: usize) -> Result<(), EvaluateError> {
+        if !self.config.detect_duplicates {
+            return Ok(());
+        }
+        let index = self.state.current_path_index;
+        match self.state.matched_entries[index] {
+            Some(first_entry) => Err(EvaluateError::DuplicateKey {
+                path: self.config.path.to_vec(),
+                failed_at_index: index,
+                first_entry,
+                second_entry: entry,
+            }),
+            None => {
+                self.state.matched_entries[index] = Some(entry);
+                Ok(())
+            }
+        }
+    }
+
+    /// Serializes `value`, the entry that matched the segment at `current_path_index`.
+    /// `adjacent_tag` is the enum whose unit variant `value` would be an adjacent tag of.
+    fn descend<T: ?Sized + Serialize>(
+        &mut self,
+        value: &T,
+        adjacent_tag: Option<&'static str>,
+    ) -> Result<(), EvaluateError> {
+        let original_entry = std::mem::take(&mut self.state.entry_index);
+        let original_struct = self.state.struct_shape;
+        let original_tag = std::mem::replace(&mut self.state.adjacent_tag, adjacent_tag);
+        self.state.current_path_index += 1;
+        let is_last_segment = self.state.current_path_index == self.config.path.len();
+
+        let result = if is_last_segment {
+            self.state.ready_to_capture = true;
+            let res = value.serialize(&mut *self);
+            self.state.ready_to_capture = false;
+            res
+        } else {
+            value.serialize(&mut *self)
+        };
+
+        self.state.current_path_index -= 1;
+        self.state.entry_index = original_entry;
+        self.state.struct_shape = original_struct;
+        self.state.adjacent_tag = original_tag;
+        result
+    }
+
+    /// Builds the error for a value on the path that cannot be traversed.
+    fn not_traversable(&self, type_name: &'static str) -> EvaluateError {
+        self.not_found(NotFoundReason::NotTraversable { type_name })
+    }
+
+    /// The path segments matched so far, ending with the value being serialized.
+    fn traversed(&self) -> &[String] {
+        &self.config.path[..self.state.current_path_index]
+    }
+
+    /// The kind of value expected where the current value was found.
+    fn expected(&self) -> &'static str {
+        if self.is_intermediate() {
+            "struct or map"
+        } else if self.extraction_mode() == ExtractionMode::List {
+            "list"
+        } else {
+            "scalar"
+        }
+    }
+
+    /// Builds the error for a target whose type cannot be extracted. `rust_type`
+    /// falls back to the name of an enclosing newtype struct.
+    fn unsupported_type(
+        &self,
+        type_name: &'static str,
+        rust_type: Option<&'static str>,
+    ) -> EvaluateError {
+        EvaluateError::unsupported_type(
+            type_name,
+            rust_type.or(self.state.newtype_name),
+            self.expected(),
+        )
+        .at_path(self.traversed())
+    }
+
+    /// Builds the error for an enum variant with data found on the path.
+    fn unsupported_variant(
+        &self,
+        variant_type: &'static str,
+        name: &'static str,
+        variant: &'static str,
+    ) -> EvaluateError {
+        EvaluateError::unsupported_variant(variant_type, Some(name), Some(variant), self.expected())
+            .at_path(self.traversed())
+    }
+
     // Helper accessors for cleaner code in trait implementations
-    fn path(&self) -> &[String] {
-        &self.config.path
+    pub(super) fn path(&self) -> &[String] {
+        self.config.path
     }
 
     fn extraction_mode(&self) -> ExtractionMode {
@@ -146,17 +370,17 @@ impl FieldValueExtractorSerializer {
 // Serializer Trait Implementation
 // =============================================================================
 
-impl<'a> Serializer for &'a mut FieldValueExtractorSerializer {
+impl<'a, 'p, 'f> Serializer for &'a mut FieldValueExtractorSerializer<'p, 'f> {
     type Ok = ();
     type Error = EvaluateError;
 
-    type SerializeSeq = SeqSerializer<'a>;
+    type SerializeSeq = SeqSerializer<'a, 'p, 'f>;
     type SerializeTuple = Skip;
     type SerializeTupleStruct = Skip;
-    type SerializeTupleVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Skip;
     type SerializeMap = Self;
     type SerializeStruct = Self;
-    type SerializeStructVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
+    type SerializeStructVariant = Skip;
 
     // Use macro for simple scalar captures
     impl_extractor_capture_methods! {
@@ -176,35 +400,37 @@ impl<'a> Serializer for &'a mut FieldValueExtractorSerializer {
         serialize_char(char) => Char,
     }
 
-    // These need special handling for conversion
+    // Borrowed payloads are only copied if the result is stored as an owned value.
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        self.capture_value(FieldScalarValue::String(v.to_string()))
+        self.capture_value(FieldScalarRef::String(v))
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        self.capture_value(FieldScalarValue::Bytes(v.to_vec()))
+        self.capture_value(FieldScalarRef::Bytes(v))
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        if self.is_intermediate() {
+            return Err(self.not_found(NotFoundReason::Null));
+        }
         if self.state.ready_to_capture {
             match self.extraction_mode() {
                 ExtractionMode::Scalar => {
-                    // Construct the nested None value based on the *current* nesting level.
-                    let none_value = wrap_in_options(
-                        FieldScalarValue::Option(None),
-                        self.state.option_nesting_level,
-                    );
-                    // Directly set the value, bypassing capture_value which would wrap again.
-                    self.result.value = Some(none_value);
+                    // capture_value wraps the None based on the *current* nesting level.
+                    self.capture_value(FieldScalarRef::Option(None))
                 }
                 ExtractionMode::List => {
                     // Option<Vec<T>> = None results in empty list
                     // Set sentinel value to indicate we found the field (list_values remains empty)
                     self.result.value = Some(FieldScalarValue::Unit);
                     self.state.ready_to_capture = false;
+                    Ok(())
+                }
+                ExtractionMode::Describe => {
+                    self.record_kind(FieldKind::Option(None));
+                    Ok(())
                 }
             }
-            Ok(())
         } else {
             // Not capturing, None is just part of structure traversal.
             Ok(())
@@ -215,12 +441,9 @@ impl<'a> Serializer for &'a mut FieldValueExtractorSerializer {
         if self.state.ready_to_capture {
             // Increment nesting level *before* serializing inner value.
             let original_level = self.state.option_nesting_level;
-            self.state.option_nesting_level =
-                original_level
-                    .checked_add(1)
-                    .ok_or(EvaluateError::UnsupportedType {
-                        type_name: "Deeply Nested Option (>255 levels)",
-                    })?;
+            self.state.option_nesting_level = original_level
+                .checked_add(1)
+                .ok_or_else(|| self.unsupported_type("Deeply Nested Option (>255 levels)", None))?;
             let result = value.serialize(&mut *self);
             // Restore nesting level *after* serializing inner value.
             self.state.option_nesting_level = original_level;
@@ -232,74 +455,108 @@ impl<'a> Serializer for &'a mut FieldValueExtractorSerializer {
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        self.capture_value(FieldScalarValue::Unit)
+        self.capture_value(FieldScalarRef::Unit)
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
-        self.capture_value(FieldScalarValue::Unit)
+        self.capture_value(FieldScalarRef::Unit)
     }
 
     fn serialize_unit_variant(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
+        if self.state.adjacent_tag == Some(name) {
+            return self.capture_value(FieldScalarRef::String(variant));
+        }
+        if self.describe_target(FieldKind::Variant {
+            name,
+            variant,
+            shape: VariantShape::Unit,
+        }) {
+            return Ok(());
+        }
         self.serialize_unit()
     }
 
     fn serialize_newtype_struct<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(&mut *self)
+        if !self.state.ready_to_capture {
+            return value.serialize(&mut *self);
+        }
+        // The outermost newtype around the target names it in errors.
+        let original_name = self.state.newtype_name;
+        self.state.newtype_name = original_name.or(Some(name));
+        let result = value.serialize(&mut *self);
+        self.state.newtype_name = original_name;
+        result
     }
 
     fn serialize_newtype_variant<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
         _value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
+        if self.describe_target(FieldKind::Variant {
+            name,
+            variant,
+            shape: VariantShape::Newtype,
+        }) {
+            return Ok(());
+        }
         if self.state.ready_to_capture {
             self.state.ready_to_capture = false;
         }
-        Err(EvaluateError::UnsupportedVariant {
-            variant_type: "newtype",
-        })
+        Err(self.unsupported_variant("newtype", name, variant))
     }
 
-    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        if self.is_intermediate() {
+            return Err(self.not_traversable("sequence"));
+        }
         if self.state.ready_to_capture {
             match self.extraction_mode() {
                 ExtractionMode::Scalar => {
                     self.state.ready_to_capture = false;
-                    Err(EvaluateError::UnsupportedType {
-                        type_name: "sequence",
-                    })
+                    Err(self.unsupported_type("sequence", None))
                 }
                 ExtractionMode::List => {
                     // Return ListCapture to collect elements
                     Ok(SeqSerializer::ListCapture(ListCapture { serializer: self }))
                 }
+                ExtractionMode::Describe => {
+                    self.record_kind(FieldKind::Seq { len });
+                    Ok(SeqSerializer::Skip(Skip))
+                }
             }
         } else {
             Ok(SeqSerializer::Skip(Skip))
         }
     }
 
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        if self.is_intermediate() {
+            return Err(self.not_traversable("tuple"));
+        }
+        if self.describe_target(FieldKind::Tuple { len }) {
+            return Ok(Skip);
+        }
         if self.state.ready_to_capture {
             self.state.ready_to_capture = false;
-            Err(EvaluateError::UnsupportedType { type_name: "tuple" })
+            Err(self.unsupported_type("tuple", None))
         } else {
             Ok(Skip)
         }
@@ -307,14 +564,18 @@ impl<'a> Serializer for &'a mut FieldValueExtractorSerializer {
 
     fn serialize_tuple_struct(
         self,
-        _name: &'static str,
-        _len: usize,
+        name: &'static str,
+        len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        if self.is_intermediate() {
+            return Err(self.not_traversable("tuple struct"));
+        }
+        if self.describe_target(FieldKind::TupleStruct { name, len }) {
+            return Ok(Skip);
+        }
         if self.state.ready_to_capture {
             self.state.ready_to_capture = false;
-            Err(EvaluateError::UnsupportedType {
-                type_name: "tuple struct",
-            })
+            Err(self.unsupported_type("tuple struct", Some(name)))
         } else {
             Ok(Skip)
         }
@@ -322,49 +583,55 @@ impl<'a> Serializer for &'a mut FieldValueExtractorSerializer {
 
     fn serialize_tuple_variant(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
-        _len: usize,
+        variant: &'static str,
+        len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        if self.describe_target(FieldKind::Variant {
+            name,
+            variant,
+            shape: VariantShape::Tuple { len },
+        }) {
+            return Ok(Skip);
+        }
         if self.state.ready_to_capture {
             self.state.ready_to_capture = false;
         }
-        Err(EvaluateError::UnsupportedVariant {
-            variant_type: "tuple",
-        })
+        Err(self.unsupported_variant("tuple", name, variant))
     }
 
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         if self.path().is_empty() {
             return Err(EvaluateError::FieldNotFound {
                 field_name: "<internal error: empty path>".to_string(),
             });
         }
+        self.state.struct_shape = None;
         if self.state.current_path_index >= self.path().len() {
+            if self.describe_target(FieldKind::Map { len }) {
+                return Ok(self);
+            }
             if self.state.ready_to_capture {
                 self.state.ready_to_capture = false;
-                return Err(EvaluateError::UnsupportedType { type_name: "map" });
-            } else {
-                return Ok(self);
+                return Err(self.unsupported_type("map", None));
             }
         }
-
-        self.state.ready_to_capture = true;
         Ok(self)
     }
 
     fn serialize_struct(
         self,
-        _name: &'static str,
-        _len: usize,
+        name: &'static str,
+        len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.state.struct_shape = Some((name, len));
         if self.state.current_path_index >= self.path().len() {
-            if self.state.ready_to_capture {
+            if self.describe_target(FieldKind::Struct { name, fields: len }) {
+                Ok(self)
+            } else if self.state.ready_to_capture {
                 self.state.ready_to_capture = false;
-                Err(EvaluateError::UnsupportedType {
-                    type_name: "struct",
-                })
+                Err(self.unsupported_type("struct", Some(name)))
             } else {
                 Ok(self)
             }
@@ -375,17 +642,22 @@ impl<'a> Serializer for &'a mut FieldValueExtractorSerializer {
 
     fn serialize_struct_variant(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
-        _len: usize,
+        variant: &'static str,
+        len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        if self.describe_target(FieldKind::Variant {
+            name,
+            variant,
+            shape: VariantShape::Struct { fields: len },
+        }) {
+            return Ok(Skip);
+        }
         if self.state.ready_to_capture {
             self.state.ready_to_capture = false;
         }
-        Err(EvaluateError::UnsupportedVariant {
-            variant_type: "struct",
-        })
+        Err(self.unsupported_variant("struct", name, variant))
     }
 }
 
@@ -393,26 +665,29 @@ impl<'a> Serializer for &'a mut FieldValueExtractorSerializer {
 // SerializeMap Implementation
 // =============================================================================
 
-impl ser::SerializeMap for &mut FieldValueExtractorSerializer {
+impl ser::SerializeMap for &mut FieldValueExtractorSerializer<'_, '_> {
     type Ok = ();
     type Error = EvaluateError;
 
     fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
         self.state.current_map_key_match = None;
+        let entry = self.state.entry_index;
+        self.state.entry_index += 1;
 
-        if self.result.value.is_none() && self.state.current_path_index < self.config.path.len() {
-            let mut key_serializer = StringKeySerializer { key: None };
-            key.serialize(&mut key_serializer)?;
-
-            if let Some(key_str) = key_serializer.key {
-                if key_str == self.config.path[self.state.current_path_index] {
-                    self.state.current_map_key_match = Some(true);
-                } else {
-                    self.state.current_map_key_match = Some(false);
-                }
-            } else {
-                self.state.current_map_key_match = Some(false);
+        // Once the target is found, keys are only compared to catch duplicates.
+        if (self.result.value.is_none() || self.config.detect_duplicates)
+            && self.state.current_path_index < self.config.path.len()
+        {
+            let mut matched = false;
+            let mut key_serializer = StringKeySerializer {
+                on_key: |key: &str| matched = self.matches_segment(key),
+            };
+            key.serialize(&mut key_serializer)
+                .map_err(|err| err.at_path(self.traversed()))?;
+            if matched {
+                self.match_entry(entry)?;
             }
+            self.state.current_map_key_match = Some(matched && self.result.value.is_none());
         } else {
             self.state.current_map_key_match = Some(false);
         }
@@ -421,32 +696,18 @@ impl ser::SerializeMap for &mut FieldValueExtractorSerializer {
 
     fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
         match self.state.current_map_key_match.take() {
-            Some(true) => {
-                self.state.current_path_index += 1;
-                let is_last_segment = self.state.current_path_index == self.config.path.len();
-
-                let result = if is_last_segment {
-                    self.state.ready_to_capture = true;
-                    let res = value.serialize(&mut **self);
-                    self.state.ready_to_capture = false;
-                    res
-                } else {
-                    value.serialize(&mut **self)
-                };
-
-                self.state.current_path_index -= 1;
-                result
-            }
-            Some(false) | None => {
-                let mut dummy_serializer = FieldValueExtractorSerializer::new_nested(vec![]);
-                let _ = value.serialize(&mut dummy_serializer);
-                Ok(())
-            }
+            Some(true) => self.descend(value, None),
+            // Values off the path are never serialized.
+            Some(false) | None => Ok(()),
         }
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Ok(())
+        if self.result.value.is_none() && self.is_intermediate() {
+            Err(self.not_found(NotFoundReason::MissingKey))
+        } else {
+            Ok(())
+        }
     }
 }
 
@@ -454,7 +715,7 @@ impl ser::SerializeMap for &mut FieldValueExtractorSerializer {
 // SerializeStruct Implementation
 // =============================================================================
 
-impl ser::SerializeStruct for &mut FieldValueExtractorSerializer {
+impl ser::SerializeStruct for &mut FieldValueExtractorSerializer<'_, '_> {
     type Ok = ();
     type Error = EvaluateError;
 
@@ -463,41 +724,38 @@ impl ser::SerializeStruct for &mut FieldValueExtractorSerializer {
         key: &'static str,
         value: &T,
     ) -> Result<(), Self::Error> {
-        if self.result.value.is_some() {
+        let entry = self.state.entry_index;
+        self.state.entry_index += 1;
+        if self.result.value.is_some() && !self.config.detect_duplicates {
             return Ok(());
         }
 
-        if self.state.current_path_index < self.config.path.len()
-            && key == self.config.path[self.state.current_path_index]
-        {
-            self.state.current_path_index += 1;
-            let is_last_segment = self.state.current_path_index == self.config.path.len();
+        if self.state.current_path_index < self.config.path.len() && self.matches_segment(key) {
+            self.match_entry(entry)?;
+            if self.result.value.is_none() {
+                let tag = self
+                    .state
+                    .struct_shape
+                    .and_then(|(name, len)| adjacent_tag_enum(name, len, entry));
+                return self.descend(value, tag);
+            }
+        }
 
-            let result = if is_last_segment {
-                self.state.ready_to_capture = true;
-                let res = value.serialize(&mut **self);
-                self.state.ready_to_capture = false;
-                res
-            } else {
-                value.serialize(&mut **self)
-            };
+        Ok(())
+    }
 
-            self.state.current_path_index -= 1;
-            return result;
+    // Serde calls this instead of `serialize_field` for a field it skips, e.g. with
+    // `skip_serializing_if`.
+    fn skip_field(&mut self, key: &'static str) -> Result<(), Self::Error> {
+        if self.result.value.is_none() && self.is_intermediate() && self.matches_segment(key) {
+            return Err(self.not_found(NotFoundReason::Skipped));
         }
-
         Ok(())
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        if self.result.value.is_none()
-            && self.state.current_path_index > 0
-            && self.state.current_path_index < self.config.path.len()
-        {
-            Err(EvaluateError::NestedFieldNotFound {
-                path: self.config.path.clone(),
-                failed_at_index: Some(self.state.current_path_index),
-            })
+        if self.result.value.is_none() && self.is_intermediate() {
+            Err(self.not_found(NotFoundReason::MissingField))
         } else {
             Ok(())
         }
@@ -1,16 +1,19 @@
-//! StringKeySerializer for extracting string keys from maps.
+//! StringKeySerializer for matching string keys of maps.
 //!
-//! Used to compare map keys against path segments during traversal.
+//! Used to compare map keys against path segments during traversal, without
+//! copying string keys. Integer, char and bool keys are rendered with `to_string`,
+//! the way [`paths`](crate::paths) renders them.
 
 use crate::error::EvaluateError;
 use serde::{Serialize, Serializer};
 
-/// Helper serializer to extract a string key from map serialization.
-pub(super) struct StringKeySerializer {
-    pub(super) key: Option<String>,
+/// Helper serializer that hands a key from map serialization to `on_key` as a string,
+/// which compares it against path segments.
+pub(super) struct StringKeySerializer<F> {
+    pub(super) on_key: F,
 }
 
-impl Serializer for &mut StringKeySerializer {
+impl<F: FnMut(&str)> Serializer for &mut StringKeySerializer<F> {
     type Ok = ();
     type Error = EvaluateError;
 
@@ -23,12 +26,12 @@ impl Serializer for &mut StringKeySerializer {
     type SerializeStructVariant = serde::ser::Impossible<Self::Ok, Self::Error>;
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        self.key = Some(v.to_string());
+        (self.on_key)(v);
         Ok(())
     }
 
-    // All non-string types return an error as map keys must be strings
-    impl_key_reject_methods! {
+    // Keys with an unambiguous text form are matched by it.
+    impl_key_to_string_methods! {
         serialize_bool(bool),
         serialize_i8(i8),
         serialize_i16(i16),
@@ -40,61 +43,52 @@ impl Serializer for &mut StringKeySerializer {
         serialize_u32(u32),
         serialize_u64(u64),
         serialize_u128(u128),
-        serialize_f32(f32),
-        serialize_f64(f64),
         serialize_char(char),
-        serialize_bytes(&[u8]),
-        serialize_unit(),
     }
 
-    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+    // Other types return an error, as their keys cannot be written as a path segment
+    impl_key_reject_methods! {
+        serialize_f32(f32) => "float",
+        serialize_f64(f64) => "float",
+        serialize_bytes(&[u8]) => "bytes",
+        serialize_unit() => "unit",
+        serialize_none() => "none",
     }
 
     fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Option",
-        })
+        Err(reject_key("option", None))
     }
 
-    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(reject_key("unit struct", Some(name)))
     }
 
     fn serialize_unit_variant(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
         _variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("unit variant", Some(name)))
     }
 
     fn serialize_newtype_struct<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("newtype struct", Some(name)))
     }
 
     fn serialize_newtype_variant<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
         _variant: &'static str,
         _value: &T,
@@ -102,70 +96,60 @@ impl Serializer for &mut StringKeySerializer {
     where
         T: ?Sized + Serialize,
     {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("newtype variant", Some(name)))
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("sequence", None))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("tuple", None))
     }
 
     fn serialize_tuple_struct(
         self,
-        _name: &'static str,
+        name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("tuple struct", Some(name)))
     }
 
     fn serialize_tuple_variant(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("tuple variant", Some(name)))
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("map", None))
     }
 
     fn serialize_struct(
         self,
-        _name: &'static str,
+        name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("struct", Some(name)))
     }
 
     fn serialize_struct_variant(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
         _variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "Map key must be a string",
-        })
+        Err(reject_key("struct variant", Some(name)))
     }
 }
+
+/// Builds the error for a map key that has no text form. The caller fills in the
+/// path of the map.
+fn reject_key(type_name: &'static str, rust_type: Option<&'static str>) -> EvaluateError {
+    EvaluateError::unsupported_type(type_name, rust_type, "string map key")
+}
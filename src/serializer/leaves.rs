@@ -0,0 +1,449 @@
+//! LeafCollectorSerializer for enumerating every scalar leaf of a record.
+//!
+//! The dual of `FieldValueExtractorSerializer`: instead of matching a target path,
+//! it records the path and value of every scalar it encounters.
+
+use crate::error::EvaluateError;
+use crate::path::PathSegment;
+use crate::value::FieldScalarValue;
+use serde::ser;
+use serde::{Serialize, Serializer};
+
+use super::scalar_capture::ScalarCaptureSerializer;
+use super::{adjacent_tag_enum, wrap_in_options};
+
+/// Serializer that records `(path, value)` for every scalar leaf.
+pub(crate) struct LeafCollectorSerializer {
+    /// Path from the root to the value currently being serialized.
+    path: Vec<PathSegment>,
+    /// Tracks nesting level of `Some(..)` around the current value.
+    option_nesting_level: u8,
+    /// Enum whose unit variants are adjacent tags, while serializing the struct field
+    /// that may hold one.
+    adjacent_tag: Option<&'static str>,
+    /// Collected leaves, in serialization order.
+    leaves: Vec<(Vec<PathSegment>, FieldScalarValue)>,
+}
+
+impl LeafCollectorSerializer {
+    pub(crate) fn new() -> Self {
+        LeafCollectorSerializer {
+            path: Vec::new(),
+            option_nesting_level: 0,
+            adjacent_tag: None,
+            leaves: Vec::new(),
+        }
+    }
+
+    pub(crate) fn into_leaves(self) -> Vec<(Vec<PathSegment>, FieldScalarValue)> {
+        self.leaves
+    }
+
+    /// Records a leaf at the current path, wrapped according to option_nesting_level.
+    fn record(&mut self, value: FieldScalarValue) -> Result<(), EvaluateError> {
+        let value = wrap_in_options(value, self.option_nesting_level);
+        self.leaves.push((self.path.clone(), value));
+        Ok(())
+    }
+
+    /// The current path with each segment rendered as a string, for error reporting.
+    fn rendered_path(&self) -> Vec<String> {
+        self.path.iter().map(ToString::to_string).collect()
+    }
+
+    /// Called when entering a compound value. `Some(..)` wrappers around it are dropped,
+    /// since each child starts with a fresh option nesting level.
+    fn enter_compound(&mut self) -> Compound<'_> {
+        Compound {
+            serializer: self,
+            struct_shape: None,
+            next_index: 0,
+            pending_key: None,
+            pushed_variant: false,
+        }
+    }
+
+    /// Serializes `value` with `segment` appended to the current path.
+    fn serialize_child<T>(&mut self, segment: PathSegment, value: &T) -> Result<(), EvaluateError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_child_of(None, segment, value)
+    }
+
+    /// Like `serialize_child`, for a value that is an adjacent tag if it is a unit
+    /// variant of the enum `adjacent_tag`.
+    fn serialize_child_of<T>(
+        &mut self,
+        adjacent_tag: Option<&'static str>,
+        segment: PathSegment,
+        value: &T,
+    ) -> Result<(), EvaluateError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.path.push(segment);
+        let original_level = self.option_nesting_level;
+        let original_tag = std::mem::replace(&mut self.adjacent_tag, adjacent_tag);
+        self.option_nesting_level = 0;
+        let result = value.serialize(&mut *self);
+        self.option_nesting_level = original_level;
+        self.adjacent_tag = original_tag;
+        self.path.pop();
+        result
+    }
+}
+
+/// Renders a map key as a path segment.
+///
+/// Strings are used as-is; integer, char and bool keys are rendered with `to_string`.
+fn key_segment<T: ?Sized + Serialize>(key: &T) -> Result<PathSegment, EvaluateError> {
+    let mut key_serializer = ScalarCaptureSerializer::new();
+    key.serialize(&mut key_serializer)?;
+
+    let key = match key_serializer.into_result() {
+        Some(FieldScalarValue::String(s)) => s,
+        Some(FieldScalarValue::Char(c)) => c.to_string(),
+        Some(FieldScalarValue::Bool(b)) => b.to_string(),
+        Some(FieldScalarValue::I8(v)) => v.to_string(),
+        Some(FieldScalarValue::I16(v)) => v.to_string(),
+        Some(FieldScalarValue::I32(v)) => v.to_string(),
+        Some(FieldScalarValue::I64(v)) => v.to_string(),
+        Some(FieldScalarValue::I128(v)) => v.to_string(),
+        Some(FieldScalarValue::U8(v)) => v.to_string(),
+        Some(FieldScalarValue::U16(v)) => v.to_string(),
+        Some(FieldScalarValue::U32(v)) => v.to_string(),
+        Some(FieldScalarValue::U64(v)) => v.to_string(),
+        Some(FieldScalarValue::U128(v)) => v.to_string(),
+        Some(other) => {
+            let type_name = match other {
+                FieldScalarValue::F32(_) | FieldScalarValue::F64(_) => "float",
+                FieldScalarValue::Bytes(_) => "bytes",
+                FieldScalarValue::Option(_) => "option",
+                _ => "unit",
+            };
+            return Err(EvaluateError::unsupported_type(
+                type_name,
+                None,
+                "string or integer map key",
+            ));
+        }
+        None => {
+            return Err(EvaluateError::unsupported_type(
+                "unit",
+                None,
+                "string or integer map key",
+            ))
+        }
+    };
+    Ok(PathSegment::Key(key))
+}
+
+// =============================================================================
+// Serializer Trait Implementation
+// =============================================================================
+
+impl<'a> Serializer for &'a mut LeafCollectorSerializer {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    // Use macro for simple scalar captures
+    impl_leaf_record_methods! {
+        serialize_bool(bool) => Bool,
+        serialize_i8(i8) => I8,
+        serialize_i16(i16) => I16,
+        serialize_i32(i32) => I32,
+        serialize_i64(i64) => I64,
+        serialize_i128(i128) => I128,
+        serialize_u8(u8) => U8,
+        serialize_u16(u16) => U16,
+        serialize_u32(u32) => U32,
+        serialize_u64(u64) => U64,
+        serialize_u128(u128) => U128,
+        serialize_f32(f32) => F32,
+        serialize_f64(f64) => F64,
+        serialize_char(char) => Char,
+    }
+
+    // These need special handling for conversion
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.record(FieldScalarValue::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.record(FieldScalarValue::Bytes(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.record(FieldScalarValue::Option(None))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        let original_level = self.option_nesting_level;
+        self.option_nesting_level = original_level.checked_add(1).ok_or_else(|| {
+            EvaluateError::unsupported_type("Deeply Nested Option (>255 levels)", None, "scalar")
+                .at_path(&self.rendered_path())
+        })?;
+        let result = value.serialize(&mut *self);
+        self.option_nesting_level = original_level;
+        result
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.record(FieldScalarValue::Unit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.record(FieldScalarValue::Unit)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        if self.adjacent_tag == Some(name) {
+            return self.record(FieldScalarValue::String(variant.to_string()));
+        }
+        self.record(FieldScalarValue::Unit)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        // Externally tagged: the variant name is a path segment.
+        self.serialize_child(PathSegment::Key(variant.to_string()), value)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self.enter_compound())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self.enter_compound())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self.enter_compound())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.path.push(PathSegment::Key(variant.to_string()));
+        let mut compound = self.enter_compound();
+        compound.pushed_variant = true;
+        Ok(compound)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self.enter_compound())
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        let mut compound = self.enter_compound();
+        compound.struct_shape = Some((name, len));
+        Ok(compound)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.path.push(PathSegment::Key(variant.to_string()));
+        let mut compound = self.enter_compound();
+        compound.pushed_variant = true;
+        Ok(compound)
+    }
+}
+
+// =============================================================================
+// Compound Implementations
+// =============================================================================
+
+/// Walks the children of a compound value, appending a segment for each one.
+pub(crate) struct Compound<'a> {
+    serializer: &'a mut LeafCollectorSerializer,
+    /// Name and length of the struct being walked, `None` for other compounds.
+    struct_shape: Option<(&'static str, usize)>,
+    /// Index of the next element, for sequences, tuples and struct fields.
+    next_index: usize,
+    /// Pending map key, rendered as a segment.
+    pending_key: Option<PathSegment>,
+    /// True if a variant name was pushed onto the path and must be popped on `end`.
+    pushed_variant: bool,
+}
+
+impl Compound<'_> {
+    fn serialize_indexed<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), EvaluateError> {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.serializer
+            .serialize_child(PathSegment::Index(index), value)
+    }
+
+    fn finish(self) -> Result<(), EvaluateError> {
+        if self.pushed_variant {
+            self.serializer.path.pop();
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.serialize_indexed(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.serialize_indexed(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.serialize_indexed(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.serialize_indexed(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        let segment =
+            key_segment(key).map_err(|err| err.at_path(&self.serializer.rendered_path()))?;
+        self.pending_key = Some(segment);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let segment = self.pending_key.take().ok_or_else(|| {
+            <EvaluateError as ser::Error>::custom("serialize_value called before serialize_key")
+        })?;
+        self.serializer.serialize_child(segment, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        let index = self.next_index;
+        self.next_index += 1;
+        let tag = self
+            .struct_shape
+            .and_then(|(name, len)| adjacent_tag_enum(name, len, index));
+        self.serializer
+            .serialize_child_of(tag, PathSegment::Key(key.to_string()), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.serializer
+            .serialize_child(PathSegment::Key(key.to_string()), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
@@ -11,12 +11,12 @@ use super::scalar_capture::ScalarCaptureSerializer;
 use super::skip::Skip;
 
 /// Enum to represent either Skip or ListCapture for SerializeSeq.
-pub(crate) enum SeqSerializer<'a> {
+pub(crate) enum SeqSerializer<'a, 'p, 'f> {
     Skip(Skip),
-    ListCapture(ListCapture<'a>),
+    ListCapture(ListCapture<'a, 'p, 'f>),
 }
 
-impl ser::SerializeSeq for SeqSerializer<'_> {
+impl ser::SerializeSeq for SeqSerializer<'_, '_, '_> {
     type Ok = ();
     type Error = EvaluateError;
 
@@ -39,11 +39,11 @@ impl ser::SerializeSeq for SeqSerializer<'_> {
 }
 
 /// Captures each element of a sequence as a scalar value.
-pub(crate) struct ListCapture<'a> {
-    pub(crate) serializer: &'a mut FieldValueExtractorSerializer,
+pub(crate) struct ListCapture<'a, 'p, 'f> {
+    pub(crate) serializer: &'a mut FieldValueExtractorSerializer<'p, 'f>,
 }
 
-impl ser::SerializeSeq for ListCapture<'_> {
+impl ser::SerializeSeq for ListCapture<'_, '_, '_> {
     type Ok = ();
     type Error = EvaluateError;
 
@@ -53,7 +53,9 @@ impl ser::SerializeSeq for ListCapture<'_> {
     {
         // Create a sub-serializer to capture this single element as a scalar
         let mut element_serializer = ScalarCaptureSerializer::new();
-        value.serialize(&mut element_serializer)?;
+        value
+            .serialize(&mut element_serializer)
+            .map_err(|err| err.at_path(self.serializer.path()))?;
 
         if let Some(scalar) = element_serializer.into_result() {
             self.serializer.push_list_value(scalar);
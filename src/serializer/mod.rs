@@ -5,6 +5,9 @@
 //! - `scalar_capture`: Serializer for capturing individual scalar values from list elements
 //! - `key`: Serializer for extracting string keys from maps
 //! - `list`: List capture logic for extracting Vec<T> fields
+//! - `leaves`: LeafCollectorSerializer for enumerating every scalar leaf
+//! - `multi`: MultiPathSerializer for extracting several paths in one pass
+//! - `validate`: Validator for surfacing errors raised anywhere in a record
 //! - `extractor`: Main FieldValueExtractorSerializer
 
 // =============================================================================
@@ -35,7 +38,7 @@ macro_rules! impl_scalar_capture_methods {
 
 /// Generates scalar serialize methods for `FieldValueExtractorSerializer`.
 ///
-/// Each method calls `capture_value()` with the corresponding `FieldScalarValue` variant.
+/// Each method calls `capture_value()` with the corresponding `FieldScalarRef` variant.
 ///
 /// # Usage
 /// ```ignore
@@ -48,7 +51,29 @@ macro_rules! impl_extractor_capture_methods {
     ($($method:ident($ty:ty) => $variant:ident),* $(,)?) => {
         $(
             fn $method(self, v: $ty) -> Result<Self::Ok, Self::Error> {
-                self.capture_value(FieldScalarValue::$variant(v))
+                self.capture_value(FieldScalarRef::$variant(v))
+            }
+        )*
+    };
+}
+
+/// Generates scalar serialize methods for `LeafCollectorSerializer` and
+/// `MultiPathSerializer`.
+///
+/// Each method calls `record()` with the corresponding `FieldScalarValue` variant.
+///
+/// # Usage
+/// ```ignore
+/// impl_leaf_record_methods! {
+///     serialize_bool(bool) => Bool,
+///     serialize_i32(i32) => I32,
+/// }
+/// ```
+macro_rules! impl_leaf_record_methods {
+    ($($method:ident($ty:ty) => $variant:ident),* $(,)?) => {
+        $(
+            fn $method(self, v: $ty) -> Result<Self::Ok, Self::Error> {
+                self.record(FieldScalarValue::$variant(v))
             }
         )*
     };
@@ -56,23 +81,33 @@ macro_rules! impl_extractor_capture_methods {
 
 /// Generates rejection methods for `StringKeySerializer`.
 ///
-/// Each method returns an `UnsupportedType` error since map keys must be strings.
+/// Each method returns an `UnsupportedType` error naming the kind of key found,
+/// since only string, integer, char and bool keys can match a path segment.
 ///
 /// # Usage
 /// ```ignore
 /// impl_key_reject_methods! {
-///     serialize_bool(bool),
-///     serialize_i32(i32),
-///     serialize_unit(),
+///     serialize_f64(f64) => "float",
+///     serialize_unit() => "unit",
 /// }
 /// ```
 macro_rules! impl_key_reject_methods {
-    ($($method:ident($($ty:ty),*)),* $(,)?) => {
+    ($($method:ident($($ty:ty),*) => $kind:literal),* $(,)?) => {
         $(
             fn $method(self, $(_: $ty),*) -> Result<Self::Ok, Self::Error> {
-                Err(EvaluateError::UnsupportedType {
-                    type_name: "Map key must be a string",
-                })
+                Err(reject_key($kind, None))
+            }
+        )*
+    };
+}
+
+/// Generates methods for `StringKeySerializer` that match a key by its `to_string`.
+macro_rules! impl_key_to_string_methods {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, v: $ty) -> Result<Self::Ok, Self::Error> {
+                (self.on_key)(&v.to_string());
+                Ok(())
             }
         )*
     };
@@ -82,13 +117,23 @@ macro_rules! impl_key_reject_methods {
 
 mod extractor;
 mod key;
+mod leaves;
 mod list;
+mod multi;
 mod scalar_capture;
 mod skip;
+mod validate;
 
 pub(crate) use extractor::FieldValueExtractorSerializer;
+pub(crate) use leaves::LeafCollectorSerializer;
+pub(crate) use multi::{MultiPathSerializer, PathNode, PathSlot, SegmentKey};
+pub(crate) use validate::validate;
 
-use crate::value::FieldScalarValue;
+use crate::probe::FieldKind;
+use crate::value::{FieldScalarRef, FieldScalarValue};
+
+/// Callback receiving a borrowed scalar instead of an owned copy.
+pub(crate) type ScalarCallback<'f> = dyn FnMut(FieldScalarRef<'_>) + 'f;
 
 /// Extraction mode for the serializer.
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
@@ -98,6 +143,34 @@ pub(crate) enum ExtractionMode {
     Scalar,
     /// Extract a list of scalar values from a sequence.
     List,
+    /// Report the shape of the value at the path, without capturing it.
+    Describe,
+}
+
+/// Returns the enum whose unit variants are adjacent tags when serialized as field
+/// `index` of the struct `struct_name` with `len` fields, if that field can be one.
+///
+/// Serde writes `#[serde(tag = "t", content = "c")]` enums as a struct named after the
+/// enum, with one field for a unit variant and two otherwise. The first field is the
+/// tag, which holds the variant as a unit variant of that same enum; such a tag is
+/// extracted as the variant name, like the tag of an internally tagged enum. A struct
+/// of the same shape whose first field is a unit variant of a same-named enum cannot be
+/// told apart from it.
+pub(crate) fn adjacent_tag_enum(
+    struct_name: &'static str,
+    len: usize,
+    index: usize,
+) -> Option<&'static str> {
+    (index == 0 && matches!(len, 1 | 2)).then_some(struct_name)
+}
+
+/// Helper function to wrap a described kind in N levels of Option(Some(...))
+pub(crate) fn wrap_kind_in_options(kind: FieldKind, level: u8) -> FieldKind {
+    let mut current = kind;
+    for _ in 0..level {
+        current = FieldKind::Option(Some(Box::new(current)));
+    }
+    current
 }
 
 /// Helper function to wrap a value in N levels of Option(Some(...))
@@ -108,3 +181,18 @@ pub(crate) fn wrap_in_options(value: FieldScalarValue, level: u8) -> FieldScalar
     }
     current
 }
+
+/// Calls `f` with `value` wrapped in N levels of `Option(Some(...))`.
+///
+/// Each level borrows the previous one from the stack, so no boxing is needed.
+pub(crate) fn with_wrapped_in_options(
+    value: FieldScalarRef<'_>,
+    level: u8,
+    f: &mut ScalarCallback<'_>,
+) {
+    if level == 0 {
+        f(value)
+    } else {
+        with_wrapped_in_options(FieldScalarRef::Option(Some(&value)), level - 1, f)
+    }
+}
@@ -0,0 +1,614 @@
+//! MultiPathSerializer for extracting several paths in one serialization pass.
+//!
+//! The paths are merged into a trie of [`PathNode`]s. The serializer walks the record
+//! once, descends only into fields and map entries that lead to a path, and fills one
+//! slot per path. It stops descending once every slot is filled.
+
+use crate::error::{EvaluateError, NotFoundReason};
+use crate::path::KeyMatching;
+use crate::value::FieldScalarValue;
+use serde::ser;
+use serde::{Serialize, Serializer};
+
+use super::key::StringKeySerializer;
+use super::skip::Skip;
+use super::{adjacent_tag_enum, wrap_in_options};
+
+/// The segment leading to a child node, and how it is compared with keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SegmentKey {
+    pub(crate) name: String,
+    /// Extra names the segment accepts.
+    pub(crate) aliases: Vec<String>,
+    pub(crate) matching: KeyMatching,
+}
+
+impl SegmentKey {
+    /// True if `key` reaches the child node.
+    fn matches(&self, key: &str) -> bool {
+        self.matching.matches_any(&self.name, &self.aliases, key)
+    }
+}
+
+/// A node of the path trie. Node 0 is the record itself.
+///
+/// Paths share a node only if their segments up to it are compared in the same way,
+/// so one key may match several children.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PathNode {
+    /// Keys of the child nodes, compared against field names and map keys.
+    pub(crate) child_keys: Vec<SegmentKey>,
+    /// Indices of the child nodes, parallel to `child_keys`.
+    pub(crate) child_nodes: Vec<usize>,
+    /// Indices of the paths that end at this node.
+    pub(crate) targets: Vec<usize>,
+}
+
+/// The outcome for one path: `None` until the path is reached.
+pub(crate) type PathSlot = Option<Result<FieldScalarValue, EvaluateError>>;
+
+/// Serializer that fills one slot per path of a trie.
+pub(crate) struct MultiPathSerializer<'c> {
+    nodes: &'c [PathNode],
+    /// The full paths, by index, for error reporting.
+    paths: &'c [Vec<String>],
+    slots: &'c mut [PathSlot],
+    /// Node of the value currently being serialized.
+    node: usize,
+    /// Number of path segments matched to reach `node`.
+    depth: usize,
+    /// Tracks nesting level of `Some(..)` around the current value.
+    option_nesting_level: u8,
+    /// Name of the newtype struct wrapping the current value, if a path ends here.
+    newtype_name: Option<&'static str>,
+    /// Child node of the map key just serialized, if it matched.
+    pending_child: Option<usize>,
+    /// Further child nodes the map key matched, when it matches several segments.
+    also_pending: Vec<usize>,
+    /// Number of slots still `None`.
+    unfilled: usize,
+    /// Whether each path fails on duplicate keys; empty if none does.
+    detect_duplicates: &'c [bool],
+    /// Position of the next entry of the struct or map being serialized.
+    entry_index: usize,
+    /// Name and length of the struct whose fields are being serialized, `None` in a map.
+    struct_shape: Option<(&'static str, usize)>,
+    /// Enum whose unit variants are adjacent tags, while serializing the field that
+    /// may hold one.
+    adjacent_tag: Option<&'static str>,
+    /// Position of the entry each node was reached through, when detecting duplicates.
+    matched_entries: Vec<Option<usize>>,
+}
+
+impl<'c> MultiPathSerializer<'c> {
+    /// Creates a serializer for the trie `nodes` built from `paths`, with one slot per
+    /// path. Every slot must be `None`.
+    pub(crate) fn new(
+        nodes: &'c [PathNode],
+        paths: &'c [Vec<String>],
+        slots: &'c mut [PathSlot],
+    ) -> Self {
+        let unfilled = slots.len();
+        MultiPathSerializer {
+            nodes,
+            paths,
+            slots,
+            node: 0,
+            depth: 0,
+            option_nesting_level: 0,
+            newtype_name: None,
+            pending_child: None,
+            also_pending: Vec::new(),
+            unfilled,
+            detect_duplicates: &[],
+            entry_index: 0,
+            struct_shape: None,
+            adjacent_tag: None,
+            matched_entries: Vec::new(),
+        }
+    }
+
+    /// Fails the paths flagged in `detect`, by index, with `DuplicateKey` when one of
+    /// their segments matches a second entry of the same struct or map. Other paths
+    /// keep the value of the first entry.
+    pub(crate) fn with_duplicate_detection(mut self, detect: &'c [bool]) -> Self {
+        self.detect_duplicates = detect;
+        self.matched_entries = vec![None; self.nodes.len()];
+        self
+    }
+
+    /// True if keys are still compared after every slot is filled, to catch duplicates.
+    fn detecting(&self) -> bool {
+        !self.matched_entries.is_empty()
+    }
+
+    /// Records that entry `entry` of the current struct or map leads to `child`.
+    /// Returns `child` if it should be traversed: an entry that matches a child already
+    /// reached fails the detecting paths below it and is not traversed.
+    fn match_entry(&mut self, child: usize, entry: usize) -> Option<usize> {
+        if !self.detecting() {
+            return Some(child);
+        }
+        let Some(first_entry) = self.matched_entries[child] else {
+            self.matched_entries[child] = Some(entry);
+            return Some(child);
+        };
+        let (nodes, paths, depth) = (self.nodes, self.paths, self.depth);
+        let mut stack = vec![child];
+        while let Some(node) = stack.pop() {
+            for &target in &nodes[node].targets {
+                if !self.detect_duplicates[target] {
+                    continue;
+                }
+                if self.slots[target].is_none() {
+                    self.unfilled -= 1;
+                }
+                self.slots[target] = Some(Err(EvaluateError::DuplicateKey {
+                    path: paths[target].clone(),
+                    failed_at_index: depth,
+                    first_entry,
+                    second_entry: entry,
+                }));
+            }
+            stack.extend(&nodes[node].child_nodes);
+        }
+        None
+    }
+
+    /// True if a path ends at the current node.
+    fn is_target(&self) -> bool {
+        !self.nodes[self.node].targets.is_empty()
+    }
+
+    /// Fills the unfilled slots of the paths ending at the current node.
+    fn fill(&mut self, outcome: Result<FieldScalarValue, EvaluateError>) {
+        let targets = &self.nodes[self.node].targets;
+        let Some(last) = targets.iter().rposition(|&t| self.slots[t].is_none()) else {
+            return;
+        };
+        for &target in &targets[..last] {
+            if self.slots[target].is_none() {
+                self.slots[target] = Some(outcome.clone());
+                self.unfilled -= 1;
+            }
+        }
+        // The last slot takes the value itself, so a single path never clones it.
+        self.slots[targets[last]] = Some(outcome);
+        self.unfilled -= 1;
+    }
+
+    /// Records a scalar found at the current node, wrapped according to
+    /// option_nesting_level.
+    fn record(&mut self, value: FieldScalarValue) -> Result<(), EvaluateError> {
+        let reason = match value {
+            FieldScalarValue::Unit | FieldScalarValue::Option(None) => NotFoundReason::Null,
+            _ => NotFoundReason::NotTraversable {
+                type_name: "scalar",
+            },
+        };
+        if self.is_target() {
+            self.fill(Ok(wrap_in_options(value, self.option_nesting_level)));
+        }
+        self.not_found(reason);
+        Ok(())
+    }
+
+    /// Fails the paths that continue below the current node, whose value is a leaf.
+    fn not_traversable(&mut self, type_name: &'static str) {
+        self.not_found(NotFoundReason::NotTraversable { type_name });
+    }
+
+    /// Fails the unfilled paths that continue below the current node: the segment at
+    /// the current depth could not be followed.
+    fn not_found(&mut self, reason: NotFoundReason) {
+        if self.unfilled == 0 {
+            return;
+        }
+        let (nodes, paths, depth) = (self.nodes, self.paths, self.depth);
+        for &child in &nodes[self.node].child_nodes {
+            self.fail_subtree(child, &|target| EvaluateError::NestedFieldNotFound {
+                path: paths[target].clone(),
+                failed_at_index: depth,
+                reason,
+            });
+        }
+    }
+
+    /// Records a compound value found at the current node, which cannot be extracted
+    /// as a scalar. `rust_type` falls back to the name of an enclosing newtype struct.
+    fn reject(&mut self, type_name: &'static str, rust_type: Option<&'static str>) {
+        if self.is_target() {
+            let error = EvaluateError::unsupported_type(
+                type_name,
+                rust_type.or(self.newtype_name),
+                "scalar",
+            )
+            .at_path(&self.traversed());
+            self.fill(Err(error));
+        }
+    }
+
+    /// Fails every unfilled path at or below the current node. Used for enum variants
+    /// with data, which are never traversed.
+    fn reject_variant(
+        &mut self,
+        variant_type: &'static str,
+        name: &'static str,
+        variant: &'static str,
+    ) {
+        let (paths, depth) = (self.paths, self.depth);
+        self.fail_subtree(self.node, &|target| {
+            let expected = if paths[target].len() == depth {
+                "scalar"
+            } else {
+                "struct or map"
+            };
+            EvaluateError::unsupported_variant(variant_type, Some(name), Some(variant), expected)
+                .at_path(&paths[target][..depth])
+        });
+    }
+
+    /// The path segments matched to reach the current node.
+    fn traversed(&self) -> Vec<String> {
+        // Every leaf of the trie is a target, so some path passes through the node.
+        let mut node = self.node;
+        loop {
+            if let Some(&target) = self.nodes[node].targets.first() {
+                return self.paths[target][..self.depth].to_vec();
+            }
+            node = self.nodes[node].child_nodes[0];
+        }
+    }
+
+    /// Fills every unfilled slot of the paths ending at or below `node` with the error
+    /// built for that path.
+    fn fail_subtree(&mut self, node: usize, error: &dyn Fn(usize) -> EvaluateError) {
+        let nodes = self.nodes;
+        for &target in &nodes[node].targets {
+            if self.slots[target].is_none() {
+                self.slots[target] = Some(Err(error(target)));
+                self.unfilled -= 1;
+            }
+        }
+        for &child in &nodes[node].child_nodes {
+            self.fail_subtree(child, error);
+        }
+    }
+
+    /// Serializes `value` as the child node `child`. `adjacent_tag` is the enum whose
+    /// unit variant `value` would be an adjacent tag of.
+    fn descend<T>(
+        &mut self,
+        child: usize,
+        value: &T,
+        adjacent_tag: Option<&'static str>,
+    ) -> Result<(), EvaluateError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let original_node = self.node;
+        let original_level = self.option_nesting_level;
+        let original_name = self.newtype_name.take();
+        let original_entry = std::mem::take(&mut self.entry_index);
+        let original_struct = self.struct_shape;
+        let original_tag = std::mem::replace(&mut self.adjacent_tag, adjacent_tag);
+        self.node = child;
+        self.depth += 1;
+        self.option_nesting_level = 0;
+        let result = value.serialize(&mut *self);
+        self.node = original_node;
+        self.depth -= 1;
+        self.option_nesting_level = original_level;
+        self.newtype_name = original_name;
+        self.entry_index = original_entry;
+        self.struct_shape = original_struct;
+        self.adjacent_tag = original_tag;
+        result
+    }
+}
+
+/// Returns the children of `node` that `key` matches, where paths continue.
+fn children<'a>(node: &'a PathNode, key: &'a str) -> impl Iterator<Item = usize> + 'a {
+    node.child_keys
+        .iter()
+        .zip(&node.child_nodes)
+        .filter(move |(segment, _)| segment.matches(key))
+        .map(|(_, &child)| child)
+}
+
+// =============================================================================
+// Serializer Trait Implementation
+// =============================================================================
+
+impl<'a, 'c> Serializer for &'a mut MultiPathSerializer<'c> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    type SerializeSeq = Skip;
+    type SerializeTuple = Skip;
+    type SerializeTupleStruct = Skip;
+    type SerializeTupleVariant = Skip;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Skip;
+
+    impl_leaf_record_methods! {
+        serialize_bool(bool) => Bool,
+        serialize_i8(i8) => I8,
+        serialize_i16(i16) => I16,
+        serialize_i32(i32) => I32,
+        serialize_i64(i64) => I64,
+        serialize_i128(i128) => I128,
+        serialize_u8(u8) => U8,
+        serialize_u16(u16) => U16,
+        serialize_u32(u32) => U32,
+        serialize_u64(u64) => U64,
+        serialize_u128(u128) => U128,
+        serialize_f32(f32) => F32,
+        serialize_f64(f64) => F64,
+        serialize_char(char) => Char,
+    }
+
+    // Borrowed payloads are only copied when a path ends here.
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        if self.is_target() {
+            self.record(FieldScalarValue::String(v.to_string()))
+        } else {
+            self.not_traversable("scalar");
+            Ok(())
+        }
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        if self.is_target() {
+            self.record(FieldScalarValue::Bytes(v.to_vec()))
+        } else {
+            self.not_traversable("scalar");
+            Ok(())
+        }
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.record(FieldScalarValue::Option(None))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        if self.is_target() {
+            let original_level = self.option_nesting_level;
+            self.option_nesting_level = original_level.checked_add(1).ok_or_else(|| {
+                EvaluateError::unsupported_type(
+                    "Deeply Nested Option (>255 levels)",
+                    None,
+                    "scalar",
+                )
+                .at_path(&self.traversed())
+            })?;
+            let result = value.serialize(&mut *self);
+            self.option_nesting_level = original_level;
+            result
+        } else {
+            value.serialize(&mut *self)
+        }
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.record(FieldScalarValue::Unit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.record(FieldScalarValue::Unit)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        if self.adjacent_tag == Some(name) {
+            return self.record(FieldScalarValue::String(variant.to_string()));
+        }
+        self.record(FieldScalarValue::Unit)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        if !self.is_target() {
+            return value.serialize(&mut *self);
+        }
+        // The outermost newtype around a target names it in errors.
+        let original_name = self.newtype_name;
+        self.newtype_name = original_name.or(Some(name));
+        let result = value.serialize(&mut *self);
+        self.newtype_name = original_name;
+        result
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.reject_variant("newtype", name, variant);
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        self.reject("sequence", None);
+        self.not_traversable("sequence");
+        Ok(Skip)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.reject("tuple", None);
+        self.not_traversable("tuple");
+        Ok(Skip)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.reject("tuple struct", Some(name));
+        self.not_traversable("tuple struct");
+        Ok(Skip)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.reject_variant("tuple", name, variant);
+        Ok(Skip)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        self.reject("map", None);
+        self.struct_shape = None;
+        Ok(self)
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.reject("struct", Some(name));
+        self.struct_shape = Some((name, len));
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.reject_variant("struct", name, variant);
+        Ok(Skip)
+    }
+}
+
+// =============================================================================
+// SerializeMap Implementation
+// =============================================================================
+
+impl ser::SerializeMap for &mut MultiPathSerializer<'_> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.pending_child = None;
+        self.also_pending.clear();
+        let entry = self.entry_index;
+        self.entry_index += 1;
+        let node = &self.nodes[self.node];
+        if (self.unfilled > 0 || self.detecting()) && !node.child_keys.is_empty() {
+            let mut first = None;
+            let mut others = std::mem::take(&mut self.also_pending);
+            let mut key_serializer = StringKeySerializer {
+                on_key: |key: &str| {
+                    for child in children(node, key) {
+                        match first {
+                            None => first = Some(child),
+                            Some(_) => others.push(child),
+                        }
+                    }
+                },
+            };
+            key.serialize(&mut key_serializer)
+                .map_err(|err| err.at_path(&self.traversed()))?;
+            self.pending_child = first.and_then(|child| self.match_entry(child, entry));
+            others.retain(|&child| self.match_entry(child, entry).is_some());
+            self.also_pending = others;
+        }
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        if let Some(child) = self.pending_child.take() {
+            self.descend(child, value, None)?;
+        }
+        // Values off every path are never serialized.
+        let mut others = std::mem::take(&mut self.also_pending);
+        for &child in &others {
+            self.descend(child, value, None)?;
+        }
+        others.clear();
+        self.also_pending = others;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.not_found(NotFoundReason::MissingKey);
+        Ok(())
+    }
+}
+
+// =============================================================================
+// SerializeStruct Implementation
+// =============================================================================
+
+impl ser::SerializeStruct for &mut MultiPathSerializer<'_> {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        let entry = self.entry_index;
+        self.entry_index += 1;
+        if self.unfilled == 0 && !self.detecting() {
+            return Ok(());
+        }
+        let tag = self
+            .struct_shape
+            .and_then(|(name, len)| adjacent_tag_enum(name, len, entry));
+        for child in children(&self.nodes[self.node], key) {
+            if let Some(child) = self.match_entry(child, entry) {
+                self.descend(child, value, tag)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Serde calls this instead of `serialize_field` for a field it skips, e.g. with
+    // `skip_serializing_if`.
+    fn skip_field(&mut self, key: &'static str) -> Result<(), Self::Error> {
+        if self.unfilled == 0 {
+            return Ok(());
+        }
+        let (nodes, paths, depth) = (self.nodes, self.paths, self.depth);
+        for child in children(&nodes[self.node], key) {
+            self.fail_subtree(child, &|target| EvaluateError::NestedFieldNotFound {
+                path: paths[target].clone(),
+                failed_at_index: depth,
+                reason: NotFoundReason::Skipped,
+            });
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.not_found(NotFoundReason::MissingField);
+        Ok(())
+    }
+}
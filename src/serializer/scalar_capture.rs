@@ -111,74 +111,77 @@ impl Serializer for &mut ScalarCaptureSerializer {
 
     fn serialize_newtype_variant<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
         _value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        Err(EvaluateError::UnsupportedVariant {
-            variant_type: "newtype",
-        })
+        Err(reject_variant("newtype", name, variant))
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "nested sequence",
-        })
+        Err(reject("nested sequence", None))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        Err(EvaluateError::UnsupportedType { type_name: "tuple" })
+        Err(reject("tuple", None))
     }
 
     fn serialize_tuple_struct(
         self,
-        _name: &'static str,
+        name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "tuple struct",
-        })
+        Err(reject("tuple struct", Some(name)))
     }
 
     fn serialize_tuple_variant(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        Err(EvaluateError::UnsupportedVariant {
-            variant_type: "tuple",
-        })
+        Err(reject_variant("tuple", name, variant))
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Err(EvaluateError::UnsupportedType { type_name: "map" })
+        Err(reject("map", None))
     }
 
     fn serialize_struct(
         self,
-        _name: &'static str,
+        name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Err(EvaluateError::UnsupportedType {
-            type_name: "struct",
-        })
+        Err(reject("struct", Some(name)))
     }
 
     fn serialize_struct_variant(
         self,
-        _name: &'static str,
+        name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Err(EvaluateError::UnsupportedVariant {
-            variant_type: "struct",
-        })
+        Err(reject_variant("struct", name, variant))
     }
 }
+
+/// Builds the error for a compound element. The caller fills in the path of the list.
+fn reject(type_name: &'static str, rust_type: Option<&'static str>) -> EvaluateError {
+    EvaluateError::unsupported_type(type_name, rust_type, "scalar")
+}
+
+/// Builds the error for an enum variant with data. The caller fills in the path of
+/// the list.
+fn reject_variant(
+    variant_type: &'static str,
+    name: &'static str,
+    variant: &'static str,
+) -> EvaluateError {
+    EvaluateError::unsupported_variant(variant_type, Some(name), Some(variant), "scalar")
+}
@@ -77,3 +77,35 @@ impl ser::SerializeSeq for Skip {
         Ok(())
     }
 }
+
+impl ser::SerializeTupleVariant for Skip {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_field<T>(&mut self, _value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for Skip {
+    type Ok = ();
+    type Error = EvaluateError;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, _value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
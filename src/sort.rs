@@ -0,0 +1,217 @@
+use crate::error::EvaluateError;
+use crate::expr::{compare_numbers, unwrap_null};
+use crate::extractor::CompositeFieldExtractor;
+use crate::value::FieldScalarValue;
+use serde::Serialize;
+use std::cmp::Ordering;
+
+/// The direction of one sort key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SortOrder {
+    /// Smallest first.
+    #[default]
+    Asc,
+    /// Largest first.
+    Desc,
+}
+
+/// Where `null` keys (`None`, JSON `null`) sort, regardless of [`SortOrder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NullOrder {
+    /// Nulls before every other value.
+    First,
+    /// Nulls after every other value.
+    #[default]
+    Last,
+}
+
+/// Orders `Serialize` records by a list of paths, each ascending or descending.
+///
+/// Paths use the same syntax as [expressions](crate::expr): `customer.region`,
+/// `labels["team name"]`. Keys are extracted with a [`CompositeFieldExtractor`] and
+/// compared after looking through `Some(..)`. Numbers compare by value across integer
+/// and float types, as in expressions, so `U64(3)`, `I64(-1)` and `F64(2.5)` from JSON
+/// sort as `-1, 2.5, 3`; numbers equal by value are then ordered by their
+/// [`ScalarKind`](crate::ScalarKind). Other values are compared with
+/// [`FieldScalarValue::total_cmp`], which orders values of different kinds by kind.
+///
+/// [`sort`](Self::sort) extracts the keys of each record once and then sorts on them
+/// (a Schwartzian transform). [`compare`](Self::compare) compares two records directly,
+/// for `sort_by`, `max_by` or a sorted insert. To sort by hand, extract with
+/// [`sort_key`](Self::sort_key) and compare with [`compare_keys`](Self::compare_keys).
+///
+/// # Example
+///
+/// ```rust
+/// use serde::Serialize;
+/// use serde_evaluate::{EvaluateError, PathComparator, SortOrder::{Asc, Desc}};
+///
+/// #[derive(Serialize)]
+/// struct Order {
+///     id: u32,
+///     customer: Customer,
+///     total: f64,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Customer {
+///     region: String,
+/// }
+///
+/// fn order(id: u32, region: &str, total: f64) -> Order {
+///     Order { id, customer: Customer { region: region.to_string() }, total }
+/// }
+///
+/// fn main() -> Result<(), EvaluateError> {
+///     let mut orders = vec![
+///         order(1, "us", 10.0),
+///         order(2, "eu", 5.0),
+///         order(3, "eu", 20.0),
+///     ];
+///
+///     let comparator = PathComparator::new(&[("customer.region", Asc), ("total", Desc)])?;
+///     comparator.sort(&mut orders)?;
+///
+///     let ids: Vec<u32> = orders.iter().map(|o| o.id).collect();
+///     assert_eq!(ids, vec![3, 2, 1]);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathComparator {
+    extractor: CompositeFieldExtractor,
+    orders: Vec<SortOrder>,
+    nulls: NullOrder,
+}
+
+impl PathComparator {
+    /// Creates a comparator from `(path, order)` pairs, most significant first.
+    /// Nulls sort last unless changed with [`with_nulls`](Self::with_nulls).
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::InvalidPath` if no keys are given or a path has an empty
+    /// segment, or `EvaluateError::ExpressionSyntax` if a path cannot be parsed.
+    pub fn new<S: AsRef<str>>(keys: &[(S, SortOrder)]) -> Result<Self, EvaluateError> {
+        let paths: Vec<&str> = keys.iter().map(|(path, _)| path.as_ref()).collect();
+
+        Ok(PathComparator {
+            extractor: CompositeFieldExtractor::new_from_dotted(&paths)?,
+            orders: keys.iter().map(|(_, order)| *order).collect(),
+            nulls: NullOrder::default(),
+        })
+    }
+
+    /// Sets where null keys sort.
+    pub fn with_nulls(mut self, nulls: NullOrder) -> Self {
+        self.nulls = nulls;
+        self
+    }
+
+    /// Extracts the sort key of `record`, one value per path.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from extracting any of the paths, such as
+    /// [`EvaluateError::NestedFieldNotFound`].
+    pub fn sort_key<T: Serialize>(
+        &self,
+        record: &T,
+    ) -> Result<Vec<FieldScalarValue>, EvaluateError> {
+        self.extractor.evaluate(record)
+    }
+
+    /// Compares two keys returned by [`sort_key`](Self::sort_key).
+    pub fn compare_keys(&self, a: &[FieldScalarValue], b: &[FieldScalarValue]) -> Ordering {
+        a.iter()
+            .zip(b)
+            .zip(&self.orders)
+            .map(|((a, b), order)| self.compare_values(a, b, *order))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// Compares two records by their sort keys.
+    ///
+    /// Both keys are extracted on every call, so sorting a slice with
+    /// [`sort`](Self::sort) is cheaper than with `sort_by`. Since a `sort_by` closure
+    /// cannot return an error, the caller decides how a failed extraction orders:
+    ///
+    /// ```rust
+    /// # use serde::Serialize;
+    /// # use serde_evaluate::{EvaluateError, PathComparator, SortOrder};
+    /// # #[derive(Serialize)]
+    /// # struct Order { total: f64 }
+    /// # fn main() -> Result<(), EvaluateError> {
+    /// let comparator = PathComparator::new(&[("total", SortOrder::Desc)])?;
+    /// let mut orders = vec![Order { total: 5.0 }, Order { total: 20.0 }];
+    ///
+    /// orders.sort_by(|a, b| comparator.compare(a, b).expect("orders have a total"));
+    /// assert_eq!(orders[0].total, 20.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`sort_key`](Self::sort_key) for either record.
+    pub fn compare<T: Serialize>(&self, a: &T, b: &T) -> Result<Ordering, EvaluateError> {
+        Ok(self.compare_keys(&self.sort_key(a)?, &self.sort_key(b)?))
+    }
+
+    /// Sorts `records` in place. The sort is stable.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`sort_key`](Self::sort_key). Keys are extracted
+    /// before anything is moved, so `records` is left untouched on error.
+    pub fn sort<T: Serialize>(&self, records: &mut [T]) -> Result<(), EvaluateError> {
+        let keys = records
+            .iter()
+            .map(|record| self.sort_key(record))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut order: Vec<usize> = (0..records.len()).collect();
+        order.sort_by(|&a, &b| self.compare_keys(&keys[a], &keys[b]));
+
+        // `destination[i]` is where the record currently at `i` belongs. Each swap puts
+        // one record in its final place.
+        let mut destination = vec![0; order.len()];
+        for (position, &source) in order.iter().enumerate() {
+            destination[source] = position;
+        }
+        for i in 0..records.len() {
+            while destination[i] != i {
+                let target = destination[i];
+                records.swap(i, target);
+                destination.swap(i, target);
+            }
+        }
+        Ok(())
+    }
+
+    fn compare_values(
+        &self,
+        a: &FieldScalarValue,
+        b: &FieldScalarValue,
+        order: SortOrder,
+    ) -> Ordering {
+        let nulls_first = self.nulls == NullOrder::First;
+        match (unwrap_null(a), unwrap_null(b)) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) if nulls_first => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) if nulls_first => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => {
+                let ordering = compare_numbers(a, b)
+                    .unwrap_or(Ordering::Equal)
+                    .then_with(|| a.total_cmp(b));
+                match order {
+                    SortOrder::Asc => ordering,
+                    SortOrder::Desc => ordering.reverse(),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Typed paths generated by `#[derive(Evaluate)]`.
+//!
+//! With the `derive` feature, `#[derive(Evaluate)]` on a struct generates a
+//! `paths()` function returning a path builder with one method per serialized field.
+//! Field names are checked by the compiler, `#[serde(rename)]` and
+//! `#[serde(rename_all)]` are honoured, and fields skipped by serde have no method.
+//!
+//! * A scalar field (integers, floats, `bool`, `char`, `String`, or an `Option` of one)
+//!   ends the path with a [`TypedPath`], which returns the field's own type.
+//! * A `Vec` of scalars ends the path with a [`TypedListPath`].
+//! * A field whose type also derives `Evaluate` (or an `Option` of one) continues the
+//!   path with that type's builder.
+//!
+//! Fields of any other type (maps, enums, tuples, ...) must be marked
+//! `#[evaluate(skip)]`. Generic structs, enums and tuple structs are not supported.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(feature = "derive")]
+//! # fn main() -> Result<(), serde_evaluate::EvaluateError> {
+//! use serde::Serialize;
+//! use serde_evaluate::Evaluate;
+//!
+//! #[derive(Serialize, Evaluate)]
+//! struct UserProfile {
+//!     name: String,
+//!     settings: Settings,
+//! }
+//!
+//! #[derive(Serialize, Evaluate)]
+//! #[serde(rename_all = "camelCase")]
+//! struct Settings {
+//!     theme_level: u8,
+//!     beta: Option<bool>,
+//! }
+//!
+//! let profile = UserProfile {
+//!     name: "ada".to_string(),
+//!     settings: Settings { theme_level: 3, beta: None },
+//! };
+//!
+//! let level = UserProfile::paths().settings().theme_level();
+//! assert_eq!(level.evaluate(&profile)?, 3u8);
+//! assert_eq!(level.extractor().path(), ["settings", "themeLevel"]);
+//!
+//! assert_eq!(UserProfile::paths().settings().beta().evaluate(&profile)?, None);
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "derive"))]
+//! # fn main() {}
+//! ```
+
+use crate::error::EvaluateError;
+use crate::extractor::{NestedFieldExtractor, NestedListFieldExtractor, SkippedFieldPolicy};
+use crate::value::FieldScalarValue;
+use serde::Serialize;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A type that can be the value of a field in a typed path.
+///
+/// Implemented for the scalar types, `Option` and `Vec` of them, and by
+/// `#[derive(Evaluate)]`. `R` is the record type the path starts from.
+pub trait PathField {
+    /// The builder or leaf returned for a field of this type.
+    type Path<R>;
+
+    /// Creates the path for a field at `segments`.
+    fn path<R>(segments: Vec<&'static str>) -> Self::Path<R>;
+}
+
+/// A struct whose fields can be traversed by typed paths. Implemented by
+/// `#[derive(Evaluate)]`.
+pub trait Evaluate {
+    /// The path builder of this struct, for records of type `R`.
+    type Paths<R>;
+
+    /// Creates the builder for this struct found at `segments` in `R`.
+    fn paths_at<R>(segments: Vec<&'static str>) -> Self::Paths<R>;
+}
+
+impl<T: Evaluate> PathField for Option<T> {
+    type Path<R> = T::Paths<R>;
+
+    fn path<R>(segments: Vec<&'static str>) -> Self::Path<R> {
+        T::paths_at(segments)
+    }
+}
+
+/// Returns `prefix` followed by `segment`. Used by generated code.
+#[doc(hidden)]
+pub fn child_segments(prefix: &[&'static str], segment: &'static str) -> Vec<&'static str> {
+    let mut segments = Vec::with_capacity(prefix.len() + 1);
+    segments.extend_from_slice(prefix);
+    segments.push(segment);
+    segments
+}
+
+/// A scalar type a [`FieldScalarValue`] can be converted back into.
+pub trait FromScalar: Sized {
+    /// Converts `value`, which must be the variant this type serializes as.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EvaluateError::TypeMismatch` if `value` is another variant.
+    fn from_scalar(value: FieldScalarValue) -> Result<Self, EvaluateError>;
+}
+
+macro_rules! impl_from_scalar {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl FromScalar for $ty {
+                fn from_scalar(value: FieldScalarValue) -> Result<Self, EvaluateError> {
+                    match value {
+                        FieldScalarValue::$variant(v) => Ok(v),
+                        other => Err(mismatch(stringify!($ty), &other)),
+                    }
+                }
+            }
+
+            impl PathField for $ty {
+                type Path<R> = TypedPath<R, $ty>;
+
+                fn path<R>(segments: Vec<&'static str>) -> Self::Path<R> {
+                    TypedPath::new(segments)
+                }
+            }
+
+            impl PathField for Option<$ty> {
+                type Path<R> = TypedPath<R, Option<$ty>>;
+
+                fn path<R>(segments: Vec<&'static str>) -> Self::Path<R> {
+                    TypedPath::new_optional(segments)
+                }
+            }
+
+            impl PathField for Vec<$ty> {
+                type Path<R> = TypedListPath<R, $ty>;
+
+                fn path<R>(segments: Vec<&'static str>) -> Self::Path<R> {
+                    TypedListPath::new(segments)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_scalar! {
+    bool => Bool,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    f32 => F32,
+    f64 => F64,
+    char => Char,
+    String => String,
+}
+
+impl<T: FromScalar> FromScalar for Option<T> {
+    fn from_scalar(value: FieldScalarValue) -> Result<Self, EvaluateError> {
+        match value {
+            FieldScalarValue::Option(None) => Ok(None),
+            FieldScalarValue::Option(Some(inner)) => T::from_scalar(*inner).map(Some),
+            other => Err(mismatch("Option", &other)),
+        }
+    }
+}
+
+fn mismatch(expected: &str, found: &FieldScalarValue) -> EvaluateError {
+    EvaluateError::TypeMismatch {
+        message: format!("expected {}, found {:?}", expected, found.kind()),
+    }
+}
+
+/// A path to a scalar field of type `T` in records of type `R`.
+pub struct TypedPath<R, T> {
+    extractor: NestedFieldExtractor,
+    marker: PhantomData<fn(&R) -> T>,
+}
+
+impl<R, T> TypedPath<R, T> {
+    fn new(segments: Vec<&'static str>) -> Self {
+        TypedPath {
+            extractor: NestedFieldExtractor::new_from_path(&segments)
+                .expect("derived paths have non-empty segments"),
+            marker: PhantomData,
+        }
+    }
+
+    /// Like `new`, for an `Option` field: skipping it with
+    /// `#[serde(skip_serializing_if = "Option::is_none")]` means `None`.
+    fn new_optional(segments: Vec<&'static str>) -> Self {
+        let mut path = Self::new(segments);
+        path.extractor = path
+            .extractor
+            .with_skipped_fields(SkippedFieldPolicy::AsNone);
+        path
+    }
+
+    /// Returns the untyped extractor for this path.
+    pub fn extractor(&self) -> &NestedFieldExtractor {
+        &self.extractor
+    }
+
+    /// Converts this path into its untyped extractor.
+    pub fn into_extractor(self) -> NestedFieldExtractor {
+        self.extractor
+    }
+}
+
+impl<R: Serialize, T: FromScalar> TypedPath<R, T> {
+    /// Extracts the field from `record`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`NestedFieldExtractor::evaluate`]. A path through an `Option` that is
+    /// `None` is [`EvaluateError::NestedFieldNotFound`], but an `Option` field skipped
+    /// while serializing evaluates to `None` ([`SkippedFieldPolicy::AsNone`]).
+    pub fn evaluate(&self, record: &R) -> Result<T, EvaluateError> {
+        T::from_scalar(self.extractor.evaluate(record)?)
+    }
+}
+
+impl<R, T> Clone for TypedPath<R, T> {
+    fn clone(&self) -> Self {
+        TypedPath {
+            extractor: self.extractor.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R, T> fmt::Debug for TypedPath<R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedPath").field(&self.extractor).finish()
+    }
+}
+
+/// A path to a `Vec<T>` field of scalars in records of type `R`.
+pub struct TypedListPath<R, T> {
+    extractor: NestedListFieldExtractor,
+    marker: PhantomData<fn(&R) -> T>,
+}
+
+impl<R, T> TypedListPath<R, T> {
+    fn new(segments: Vec<&'static str>) -> Self {
+        TypedListPath {
+            extractor: NestedListFieldExtractor::new_from_path(&segments)
+                .expect("derived paths have non-empty segments"),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the untyped extractor for this path.
+    pub fn extractor(&self) -> &NestedListFieldExtractor {
+        &self.extractor
+    }
+
+    /// Converts this path into its untyped extractor.
+    pub fn into_extractor(self) -> NestedListFieldExtractor {
+        self.extractor
+    }
+}
+
+impl<R: Serialize, T: FromScalar> TypedListPath<R, T> {
+    /// Extracts the elements of the list from `record`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`NestedListFieldExtractor::evaluate`].
+    pub fn evaluate(&self, record: &R) -> Result<Vec<T>, EvaluateError> {
+        self.extractor
+            .evaluate(record)?
+            .into_iter()
+            .map(T::from_scalar)
+            .collect()
+    }
+}
+
+impl<R, T> Clone for TypedListPath<R, T> {
+    fn clone(&self) -> Self {
+        TypedListPath {
+            extractor: self.extractor.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R, T> fmt::Debug for TypedListPath<R, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedListPath")
+            .field(&self.extractor)
+            .finish()
+    }
+}
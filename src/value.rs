@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 
 /// Represents the scalar value extracted from a field.
 ///
@@ -43,3 +44,250 @@ pub enum FieldScalarValue {
     /// Optional scalar value (`Option<T>`). Contains `None` or `Some(Box<FieldScalarValue>)`.
     Option(Option<Box<FieldScalarValue>>),
 }
+
+impl FieldScalarValue {
+    /// Returns the variant of this value without its payload.
+    pub fn kind(&self) -> ScalarKind {
+        match self {
+            FieldScalarValue::Unit => ScalarKind::Unit,
+            FieldScalarValue::Bool(_) => ScalarKind::Bool,
+            FieldScalarValue::I8(_) => ScalarKind::I8,
+            FieldScalarValue::I16(_) => ScalarKind::I16,
+            FieldScalarValue::I32(_) => ScalarKind::I32,
+            FieldScalarValue::I64(_) => ScalarKind::I64,
+            FieldScalarValue::I128(_) => ScalarKind::I128,
+            FieldScalarValue::U8(_) => ScalarKind::U8,
+            FieldScalarValue::U16(_) => ScalarKind::U16,
+            FieldScalarValue::U32(_) => ScalarKind::U32,
+            FieldScalarValue::U64(_) => ScalarKind::U64,
+            FieldScalarValue::U128(_) => ScalarKind::U128,
+            FieldScalarValue::F32(_) => ScalarKind::F32,
+            FieldScalarValue::F64(_) => ScalarKind::F64,
+            FieldScalarValue::Char(_) => ScalarKind::Char,
+            FieldScalarValue::String(_) => ScalarKind::String,
+            FieldScalarValue::Bytes(_) => ScalarKind::Bytes,
+            FieldScalarValue::Option(_) => ScalarKind::Option,
+        }
+    }
+
+    /// Calls `f` with this value borrowed as a [`FieldScalarRef`].
+    ///
+    /// Each `Option` layer borrows the inner one from the stack, so no boxing is needed.
+    pub(crate) fn with_ref<R>(&self, f: impl FnOnce(FieldScalarRef<'_>) -> R) -> R {
+        let mut f = Some(f);
+        let mut output = None;
+        self.visit_ref(&mut |value| {
+            if let Some(f) = f.take() {
+                output = Some(f(value));
+            }
+        });
+        output.expect("visit_ref calls the callback once")
+    }
+
+    fn visit_ref(&self, f: &mut dyn FnMut(FieldScalarRef<'_>)) {
+        let value = match self {
+            FieldScalarValue::Unit => FieldScalarRef::Unit,
+            FieldScalarValue::Bool(v) => FieldScalarRef::Bool(*v),
+            FieldScalarValue::I8(v) => FieldScalarRef::I8(*v),
+            FieldScalarValue::I16(v) => FieldScalarRef::I16(*v),
+            FieldScalarValue::I32(v) => FieldScalarRef::I32(*v),
+            FieldScalarValue::I64(v) => FieldScalarRef::I64(*v),
+            FieldScalarValue::I128(v) => FieldScalarRef::I128(*v),
+            FieldScalarValue::U8(v) => FieldScalarRef::U8(*v),
+            FieldScalarValue::U16(v) => FieldScalarRef::U16(*v),
+            FieldScalarValue::U32(v) => FieldScalarRef::U32(*v),
+            FieldScalarValue::U64(v) => FieldScalarRef::U64(*v),
+            FieldScalarValue::U128(v) => FieldScalarRef::U128(*v),
+            FieldScalarValue::F32(v) => FieldScalarRef::F32(*v),
+            FieldScalarValue::F64(v) => FieldScalarRef::F64(*v),
+            FieldScalarValue::Char(v) => FieldScalarRef::Char(*v),
+            FieldScalarValue::String(v) => FieldScalarRef::String(v),
+            FieldScalarValue::Bytes(v) => FieldScalarRef::Bytes(v),
+            FieldScalarValue::Option(None) => FieldScalarRef::Option(None),
+            FieldScalarValue::Option(Some(inner)) => {
+                return inner.visit_ref(&mut |inner| f(FieldScalarRef::Option(Some(&inner))));
+            }
+        };
+        f(value)
+    }
+
+    /// Compares two values with a total order.
+    ///
+    /// Values of different variants are ordered by their [`ScalarKind`]; values of the
+    /// same variant by their payload. Floats use [`f64::total_cmp`], so `NaN` sorts after
+    /// every other number and `-0.0` before `0.0`. `None` sorts before any `Some`.
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        use FieldScalarValue::*;
+        match (self, other) {
+            (Unit, Unit) => Ordering::Equal,
+            (Bool(a), Bool(b)) => a.cmp(b),
+            (I8(a), I8(b)) => a.cmp(b),
+            (I16(a), I16(b)) => a.cmp(b),
+            (I32(a), I32(b)) => a.cmp(b),
+            (I64(a), I64(b)) => a.cmp(b),
+            (I128(a), I128(b)) => a.cmp(b),
+            (U8(a), U8(b)) => a.cmp(b),
+            (U16(a), U16(b)) => a.cmp(b),
+            (U32(a), U32(b)) => a.cmp(b),
+            (U64(a), U64(b)) => a.cmp(b),
+            (U128(a), U128(b)) => a.cmp(b),
+            (F32(a), F32(b)) => a.total_cmp(b),
+            (F64(a), F64(b)) => a.total_cmp(b),
+            (Char(a), Char(b)) => a.cmp(b),
+            (String(a), String(b)) => a.cmp(b),
+            (Bytes(a), Bytes(b)) => a.cmp(b),
+            (Option(a), Option(b)) => match (a, b) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (Some(a), Some(b)) => a.total_cmp(b),
+            },
+            _ => self.kind().cmp(&other.kind()),
+        }
+    }
+}
+
+/// The variant of a [`FieldScalarValue`] (or [`FieldScalarRef`]) without its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ScalarKind {
+    /// Unit value (`()`).
+    Unit,
+    /// Boolean value (`bool`).
+    Bool,
+    /// Signed 8-bit integer (`i8`).
+    I8,
+    /// Signed 16-bit integer (`i16`).
+    I16,
+    /// Signed 32-bit integer (`i32`).
+    I32,
+    /// Signed 64-bit integer (`i64`).
+    I64,
+    /// Signed 128-bit integer (`i128`).
+    I128,
+    /// Unsigned 8-bit integer (`u8`).
+    U8,
+    /// Unsigned 16-bit integer (`u16`).
+    U16,
+    /// Unsigned 32-bit integer (`u32`).
+    U32,
+    /// Unsigned 64-bit integer (`u64`).
+    U64,
+    /// Unsigned 128-bit integer (`u128`).
+    U128,
+    /// 32-bit floating point number (`f32`).
+    F32,
+    /// 64-bit floating point number (`f64`).
+    F64,
+    /// Character (`char`).
+    Char,
+    /// String (`String` or `&str`).
+    String,
+    /// Byte array (`Vec<u8>` with `serde_bytes`, or `&[u8]`).
+    Bytes,
+    /// Optional scalar value (`Option<T>`).
+    Option,
+}
+
+/// Borrowed counterpart of [`FieldScalarValue`].
+///
+/// String and byte payloads borrow from the record while it is being serialized,
+/// so inspecting a value through this type does not copy it. Produced by the
+/// `evaluate_with` methods of [`FieldExtractor`](crate::FieldExtractor) and
+/// [`NestedFieldExtractor`](crate::NestedFieldExtractor).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldScalarRef<'a> {
+    /// Unit value (`()`).
+    Unit,
+    /// Boolean value (`bool`).
+    Bool(bool),
+    /// Signed 8-bit integer (`i8`).
+    I8(i8),
+    /// Signed 16-bit integer (`i16`).
+    I16(i16),
+    /// Signed 32-bit integer (`i32`).
+    I32(i32),
+    /// Signed 64-bit integer (`i64`).
+    I64(i64),
+    /// Signed 128-bit integer (`i128`).
+    I128(i128),
+    /// Unsigned 8-bit integer (`u8`).
+    U8(u8),
+    /// Unsigned 16-bit integer (`u16`).
+    U16(u16),
+    /// Unsigned 32-bit integer (`u32`).
+    U32(u32),
+    /// Unsigned 64-bit integer (`u64`).
+    U64(u64),
+    /// Unsigned 128-bit integer (`u128`).
+    U128(u128),
+    /// 32-bit floating point number (`f32`).
+    F32(f32),
+    /// 64-bit floating point number (`f64`).
+    F64(f64),
+    /// Character (`char`).
+    Char(char),
+    /// Borrowed string slice.
+    String(&'a str),
+    /// Borrowed byte slice, typically produced by `#[serde(with = "serde_bytes")]`.
+    Bytes(&'a [u8]),
+    /// Optional scalar value (`Option<T>`). Contains `None` or a reference to the inner value.
+    Option(Option<&'a FieldScalarRef<'a>>),
+}
+
+impl FieldScalarRef<'_> {
+    /// Returns the variant of this value without its payload.
+    pub fn kind(&self) -> ScalarKind {
+        match self {
+            FieldScalarRef::Unit => ScalarKind::Unit,
+            FieldScalarRef::Bool(_) => ScalarKind::Bool,
+            FieldScalarRef::I8(_) => ScalarKind::I8,
+            FieldScalarRef::I16(_) => ScalarKind::I16,
+            FieldScalarRef::I32(_) => ScalarKind::I32,
+            FieldScalarRef::I64(_) => ScalarKind::I64,
+            FieldScalarRef::I128(_) => ScalarKind::I128,
+            FieldScalarRef::U8(_) => ScalarKind::U8,
+            FieldScalarRef::U16(_) => ScalarKind::U16,
+            FieldScalarRef::U32(_) => ScalarKind::U32,
+            FieldScalarRef::U64(_) => ScalarKind::U64,
+            FieldScalarRef::U128(_) => ScalarKind::U128,
+            FieldScalarRef::F32(_) => ScalarKind::F32,
+            FieldScalarRef::F64(_) => ScalarKind::F64,
+            FieldScalarRef::Char(_) => ScalarKind::Char,
+            FieldScalarRef::String(_) => ScalarKind::String,
+            FieldScalarRef::Bytes(_) => ScalarKind::Bytes,
+            FieldScalarRef::Option(_) => ScalarKind::Option,
+        }
+    }
+
+    /// Copies the borrowed value into an owned [`FieldScalarValue`].
+    pub fn to_owned_value(&self) -> FieldScalarValue {
+        match *self {
+            FieldScalarRef::Unit => FieldScalarValue::Unit,
+            FieldScalarRef::Bool(v) => FieldScalarValue::Bool(v),
+            FieldScalarRef::I8(v) => FieldScalarValue::I8(v),
+            FieldScalarRef::I16(v) => FieldScalarValue::I16(v),
+            FieldScalarRef::I32(v) => FieldScalarValue::I32(v),
+            FieldScalarRef::I64(v) => FieldScalarValue::I64(v),
+            FieldScalarRef::I128(v) => FieldScalarValue::I128(v),
+            FieldScalarRef::U8(v) => FieldScalarValue::U8(v),
+            FieldScalarRef::U16(v) => FieldScalarValue::U16(v),
+            FieldScalarRef::U32(v) => FieldScalarValue::U32(v),
+            FieldScalarRef::U64(v) => FieldScalarValue::U64(v),
+            FieldScalarRef::U128(v) => FieldScalarValue::U128(v),
+            FieldScalarRef::F32(v) => FieldScalarValue::F32(v),
+            FieldScalarRef::F64(v) => FieldScalarValue::F64(v),
+            FieldScalarRef::Char(v) => FieldScalarValue::Char(v),
+            FieldScalarRef::String(v) => FieldScalarValue::String(v.to_string()),
+            FieldScalarRef::Bytes(v) => FieldScalarValue::Bytes(v.to_vec()),
+            FieldScalarRef::Option(v) => {
+                FieldScalarValue::Option(v.map(|inner| Box::new(inner.to_owned_value())))
+            }
+        }
+    }
+}
+
+impl From<FieldScalarRef<'_>> for FieldScalarValue {
+    fn from(value: FieldScalarRef<'_>) -> Self {
+        value.to_owned_value()
+    }
+}
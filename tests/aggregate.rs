@@ -0,0 +1,304 @@
+// Tests for group_by and aggregates
+
+use serde::Serialize;
+use serde_evaluate::aggregate::{group_by, Aggregate, Aggregates};
+use serde_evaluate::error::EvaluateError;
+use serde_evaluate::value::FieldScalarValue as V;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Order {
+    customer: Customer,
+    status: String,
+    total: u64,
+    discount: Option<f64>,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Customer {
+    id: u32,
+    region: String,
+}
+
+fn order(id: u32, region: &str, status: &str, total: u64, discount: Option<f64>) -> Order {
+    Order {
+        customer: Customer {
+            id,
+            region: region.to_string(),
+        },
+        status: status.to_string(),
+        total,
+        discount,
+        tags: Vec::new(),
+    }
+}
+
+fn sample_orders() -> Vec<Order> {
+    vec![
+        order(1, "eu", "paid", 10, Some(1.5)),
+        order(1, "eu", "open", 30, None),
+        order(2, "eu", "paid", 20, Some(0.5)),
+        order(3, "us", "paid", 5, None),
+    ]
+}
+
+fn s(value: &str) -> V {
+    V::String(value.to_string())
+}
+
+fn group<'a>(groups: &'a [(Vec<V>, Aggregates)], key: &[V]) -> &'a Aggregates {
+    let (_, aggregates) = groups
+        .iter()
+        .find(|(k, _)| k == key)
+        .unwrap_or_else(|| panic!("no group for {:?}", key));
+    aggregates
+}
+
+// =============================================================================
+// Grouping
+// =============================================================================
+
+#[test]
+fn test_group_by_single_key() {
+    let orders = sample_orders();
+    let groups = group_by(
+        &orders,
+        &["customer.region"],
+        &[
+            Aggregate::count("discount").unwrap(),
+            Aggregate::sum("total").unwrap(),
+            Aggregate::min("total").unwrap(),
+            Aggregate::max("total").unwrap(),
+            Aggregate::avg("total").unwrap(),
+            Aggregate::count_distinct("customer.id").unwrap(),
+        ],
+    )
+    .unwrap();
+
+    let expected = vec![
+        (
+            vec![s("eu")],
+            Aggregates {
+                count: 3,
+                values: vec![
+                    V::U64(2),
+                    V::I128(60),
+                    V::U64(10),
+                    V::U64(30),
+                    V::F64(20.0),
+                    V::U64(2),
+                ],
+            },
+        ),
+        (
+            vec![s("us")],
+            Aggregates {
+                count: 1,
+                values: vec![
+                    V::U64(0),
+                    V::I128(5),
+                    V::U64(5),
+                    V::U64(5),
+                    V::F64(5.0),
+                    V::U64(1),
+                ],
+            },
+        ),
+    ];
+    assert_eq!(groups, expected);
+}
+
+#[test]
+fn test_group_by_composite_key_is_ordered() {
+    let orders = sample_orders();
+    let groups = group_by(&orders, &["customer.region", "status"], &[]).unwrap();
+    let keys: Vec<_> = groups.iter().map(|(key, _)| key.clone()).collect();
+    assert_eq!(
+        keys,
+        vec![
+            vec![s("eu"), s("open")],
+            vec![s("eu"), s("paid")],
+            vec![s("us"), s("paid")],
+        ]
+    );
+    assert_eq!(group(&groups, &[s("eu"), s("paid")]).count, 2);
+}
+
+#[test]
+fn test_group_by_no_keys_is_one_group() {
+    let orders = sample_orders();
+    let groups = group_by(&orders, &[] as &[&str], &[Aggregate::sum("total").unwrap()]).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(
+        groups[0],
+        (
+            Vec::new(),
+            Aggregates {
+                count: 4,
+                values: vec![V::I128(65)]
+            }
+        )
+    );
+}
+
+#[test]
+fn test_group_by_empty_input() {
+    let orders: Vec<Order> = Vec::new();
+    let groups = group_by(&orders, &["status"], &[]).unwrap();
+    assert!(groups.is_empty());
+}
+
+#[test]
+fn test_group_by_works_over_iterators() {
+    let orders = sample_orders();
+    let groups = group_by(
+        orders.iter().filter(|o| o.status == "paid"),
+        &["customer.region"],
+        &[],
+    )
+    .unwrap();
+    assert_eq!(group(&groups, &[s("eu")]).count, 2);
+    assert_eq!(group(&groups, &[s("us")]).count, 1);
+}
+
+// =============================================================================
+// Aggregates
+// =============================================================================
+
+#[test]
+fn test_aggregates_skip_nulls() {
+    let orders = sample_orders();
+    let groups = group_by(
+        &orders,
+        &["customer.region"],
+        &[
+            Aggregate::sum("discount").unwrap(),
+            Aggregate::avg("discount").unwrap(),
+            Aggregate::min("discount").unwrap(),
+            Aggregate::max("discount").unwrap(),
+            Aggregate::count_distinct("discount").unwrap(),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        group(&groups, &[s("eu")]).values,
+        vec![
+            V::F64(2.0),
+            V::F64(1.0),
+            V::F64(0.5),
+            V::F64(1.5),
+            V::U64(2)
+        ]
+    );
+    // Only nulls: sum, avg, min and max are null
+    let null = V::Option(None);
+    assert_eq!(
+        group(&groups, &[s("us")]).values,
+        vec![null.clone(), null.clone(), null.clone(), null, V::U64(0)]
+    );
+}
+
+#[test]
+fn test_aggregates_sum_mixed_numbers_is_float() {
+    let records = vec![
+        serde_json::json!({"n": 1}),
+        serde_json::json!({"n": -2}),
+        serde_json::json!({"n": 0.5}),
+    ];
+    let groups = group_by(&records, &[] as &[&str], &[Aggregate::sum("n").unwrap()]).unwrap();
+    assert_eq!(group(&groups, &[]).values, vec![V::F64(-0.5)]);
+}
+
+#[test]
+fn test_aggregates_min_max_strings() {
+    let orders = sample_orders();
+    let groups = group_by(
+        &orders,
+        &[] as &[&str],
+        &[
+            Aggregate::min("status").unwrap(),
+            Aggregate::max("status").unwrap(),
+        ],
+    )
+    .unwrap();
+    assert_eq!(group(&groups, &[]).values, vec![s("open"), s("paid")]);
+}
+
+#[test]
+fn test_group_keys_use_total_cmp() {
+    let records = vec![
+        BTreeMap::from([("k", f64::NAN)]),
+        BTreeMap::from([("k", 0.0)]),
+        BTreeMap::from([("k", f64::NAN)]),
+        BTreeMap::from([("k", -0.0)]),
+    ];
+    let groups = group_by(&records, &["k"], &[]).unwrap();
+    let counts: Vec<_> = groups
+        .iter()
+        .map(|(key, aggregates)| (format!("{:?}", key), aggregates.count))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![
+            ("[F64(-0.0)]".to_string(), 1),
+            ("[F64(0.0)]".to_string(), 1),
+            ("[F64(NaN)]".to_string(), 2),
+        ]
+    );
+
+    // Equality itself stays IEEE
+    assert_ne!(V::F64(f64::NAN), V::F64(f64::NAN));
+    assert_eq!(V::F64(-0.0), V::F64(0.0));
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+#[test]
+fn test_aggregates_non_numeric_sum_is_type_mismatch() {
+    let orders = sample_orders();
+    let result = group_by(&orders, &["status"], &[Aggregate::avg("status").unwrap()]);
+    assert!(
+        matches!(result, Err(EvaluateError::TypeMismatch { .. })),
+        "Expected TypeMismatch, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_aggregates_sum_overflow_is_type_mismatch() {
+    let records = vec![
+        BTreeMap::from([("n", i128::MAX)]),
+        BTreeMap::from([("n", 1)]),
+    ];
+    let result = group_by(&records, &[] as &[&str], &[Aggregate::sum("n").unwrap()]);
+    assert!(matches!(result, Err(EvaluateError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_group_by_missing_and_unsupported_paths() {
+    let orders = sample_orders();
+    assert!(matches!(
+        group_by(&orders, &["customer.country"], &[]),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+    assert!(matches!(
+        group_by(
+            &orders,
+            &["status"],
+            &[Aggregate::count("missing").unwrap()]
+        ),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+    assert!(matches!(
+        group_by(&orders, &["tags"], &[]),
+        Err(EvaluateError::UnsupportedType { .. })
+    ));
+    assert!(matches!(
+        Aggregate::sum("total."),
+        Err(EvaluateError::ExpressionSyntax { position: 6, .. })
+    ));
+}
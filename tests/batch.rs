@@ -0,0 +1,147 @@
+use serde::Serialize;
+use serde_evaluate::{
+    CompiledExtractor, CompositeFieldExtractor, EvaluateError, FieldExtractor, FieldScalarValue,
+    ListFieldExtractor, NestedFieldExtractor, NestedListFieldExtractor,
+};
+
+#[derive(Serialize)]
+struct Event {
+    id: u32,
+    user: User,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct User {
+    name: String,
+    roles: Vec<String>,
+}
+
+fn events(n: u32) -> Vec<Event> {
+    (0..n)
+        .map(|id| Event {
+            id,
+            user: User {
+                name: format!("user-{id}"),
+                roles: vec!["reader".to_string()],
+            },
+            tags: vec![format!("tag-{}", id % 3)],
+        })
+        .collect()
+}
+
+fn name(id: u32) -> FieldScalarValue {
+    FieldScalarValue::String(format!("user-{id}"))
+}
+
+// =============================================================================
+// evaluate_many
+// =============================================================================
+
+#[test]
+fn field_extractor_preserves_order() {
+    let results = FieldExtractor::new("id").evaluate_many(&events(5));
+    let expected: Vec<_> = (0..5).map(|id| Ok(FieldScalarValue::U32(id))).collect();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn nested_extractor_matches_evaluate() {
+    let records = events(4);
+    let extractor = NestedFieldExtractor::new_from_path(&["user", "name"]).unwrap();
+    let expected: Vec<_> = records.iter().map(|r| extractor.evaluate(r)).collect();
+    assert_eq!(extractor.evaluate_many(&records), expected);
+}
+
+#[test]
+fn list_extractors() {
+    let records = events(2);
+    assert_eq!(
+        ListFieldExtractor::new("tags").evaluate_many(&records),
+        vec![
+            Ok(vec![FieldScalarValue::String("tag-0".to_string())]),
+            Ok(vec![FieldScalarValue::String("tag-1".to_string())]),
+        ]
+    );
+
+    let roles = NestedListFieldExtractor::new_from_path(&["user", "roles"]).unwrap();
+    assert!(roles
+        .evaluate_many(&records)
+        .iter()
+        .all(|result| result == &Ok(vec![FieldScalarValue::String("reader".to_string())])));
+}
+
+#[test]
+fn composite_and_compiled_extractors() {
+    let records = events(3);
+    let expected: Vec<_> = (0..3)
+        .map(|id| Ok(vec![FieldScalarValue::U32(id), name(id)]))
+        .collect();
+
+    let composite =
+        CompositeFieldExtractor::new_from_paths(&[&["id"][..], &["user", "name"]]).unwrap();
+    assert_eq!(composite.evaluate_many(&records), expected);
+    assert_eq!(composite.compile().evaluate_many(&records), expected);
+}
+
+#[test]
+fn errors_are_reported_per_record() {
+    let records = vec![
+        serde_json::json!({"id": 1}),
+        serde_json::json!({"other": 2}),
+        serde_json::json!({"id": 3}),
+    ];
+    let results = CompiledExtractor::new(&["id"])
+        .unwrap()
+        .evaluate_many(&records);
+
+    assert_eq!(results[0], Ok(vec![FieldScalarValue::U64(1)]));
+    assert!(matches!(
+        results[1],
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+    assert_eq!(results[2], Ok(vec![FieldScalarValue::U64(3)]));
+}
+
+#[test]
+fn empty_batch() {
+    let records: Vec<Event> = Vec::new();
+    assert!(FieldExtractor::new("id").evaluate_many(&records).is_empty());
+}
+
+// =============================================================================
+// par_evaluate_many
+// =============================================================================
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_matches_sequential() {
+    let records = events(1000);
+
+    let field = FieldExtractor::new("id");
+    assert_eq!(
+        field.par_evaluate_many(&records),
+        field.evaluate_many(&records)
+    );
+
+    let nested = NestedFieldExtractor::new_from_path(&["user", "name"]).unwrap();
+    assert_eq!(
+        nested.par_evaluate_many(&records),
+        nested.evaluate_many(&records)
+    );
+
+    let list = ListFieldExtractor::new("tags");
+    assert_eq!(
+        list.par_evaluate_many(&records),
+        list.evaluate_many(&records)
+    );
+
+    let composite =
+        CompositeFieldExtractor::new_from_paths(&[&["user", "name"][..], &["id"]]).unwrap();
+    let results = composite.par_evaluate_many(&records);
+    assert_eq!(results, composite.evaluate_many(&records));
+    assert_eq!(
+        results[999],
+        Ok(vec![name(999), FieldScalarValue::U32(999)])
+    );
+}
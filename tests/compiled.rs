@@ -0,0 +1,355 @@
+use serde::Serialize;
+use serde_evaluate::{
+    CompiledExtractor, CompositeFieldExtractor, EvaluateError, ExtractionState, FieldScalarValue,
+    NotFoundReason,
+};
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Order {
+    id: u64,
+    customer: Customer,
+    note: Option<String>,
+    tags: Vec<String>,
+    labels: BTreeMap<String, String>,
+    status: Status,
+}
+
+#[derive(Serialize)]
+struct Customer {
+    name: String,
+    region: String,
+    tier: Option<Option<u8>>,
+}
+
+#[derive(Serialize)]
+#[allow(dead_code)]
+enum Status {
+    Open,
+    Shipped { carrier: String },
+}
+
+fn sample_order() -> Order {
+    Order {
+        id: 7,
+        customer: Customer {
+            name: "Ada".to_string(),
+            region: "eu".to_string(),
+            tier: Some(Some(2)),
+        },
+        note: None,
+        tags: vec!["vip".to_string()],
+        labels: BTreeMap::from([
+            ("team".to_string(), "core".to_string()),
+            ("zone".to_string(), "a".to_string()),
+        ]),
+        status: Status::Shipped {
+            carrier: "ups".to_string(),
+        },
+    }
+}
+
+fn compiled(paths: &[&[&str]]) -> CompiledExtractor {
+    CompiledExtractor::new_from_paths(paths).unwrap()
+}
+
+// =============================================================================
+// Happy path
+// =============================================================================
+
+#[test]
+fn top_level_fields() {
+    let extractor = CompiledExtractor::new(&["id", "note"]).unwrap();
+    assert_eq!(
+        extractor.evaluate(&sample_order()).unwrap(),
+        vec![FieldScalarValue::U64(7), FieldScalarValue::Option(None)]
+    );
+}
+
+#[test]
+fn shared_prefixes_are_returned_in_path_order() {
+    let extractor = compiled(&[
+        &["customer", "region"],
+        &["id"],
+        &["customer", "name"],
+        &["labels", "zone"],
+        &["labels", "team"],
+    ]);
+    assert_eq!(
+        extractor.evaluate(&sample_order()).unwrap(),
+        vec![
+            FieldScalarValue::String("eu".to_string()),
+            FieldScalarValue::U64(7),
+            FieldScalarValue::String("Ada".to_string()),
+            FieldScalarValue::String("a".to_string()),
+            FieldScalarValue::String("core".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_paths_each_get_the_value() {
+    let extractor = compiled(&[&["customer", "name"], &["customer", "name"]]);
+    let name = FieldScalarValue::String("Ada".to_string());
+    assert_eq!(
+        extractor.evaluate(&sample_order()).unwrap(),
+        vec![name.clone(), name]
+    );
+}
+
+#[test]
+fn nested_options_are_preserved() {
+    let extractor = compiled(&[&["customer", "tier"]]);
+    assert_eq!(
+        extractor.evaluate(&sample_order()).unwrap(),
+        vec![FieldScalarValue::Option(Some(Box::new(
+            FieldScalarValue::Option(Some(Box::new(FieldScalarValue::U8(2))))
+        )))]
+    );
+}
+
+#[test]
+fn paths_are_reported_in_order() {
+    let extractor = compiled(&[&["id"], &["customer", "name"]]);
+    assert_eq!(
+        extractor.paths(),
+        &[
+            vec!["id".to_string()],
+            vec!["customer".to_string(), "name".to_string()]
+        ]
+    );
+}
+
+// =============================================================================
+// Reusable state
+// =============================================================================
+
+#[test]
+fn state_is_reset_between_records() {
+    let extractor = compiled(&[&["id"], &["customer", "region"]]);
+    let mut state = ExtractionState::new();
+
+    let mut order = sample_order();
+    assert_eq!(
+        extractor.evaluate_into(&order, &mut state).unwrap(),
+        &[
+            FieldScalarValue::U64(7),
+            FieldScalarValue::String("eu".to_string())
+        ]
+    );
+
+    order.id = 8;
+    order.customer.region = "us".to_string();
+    assert_eq!(
+        extractor.evaluate_into(&order, &mut state).unwrap(),
+        &[
+            FieldScalarValue::U64(8),
+            FieldScalarValue::String("us".to_string())
+        ]
+    );
+}
+
+#[test]
+fn state_recovers_after_an_error() {
+    let extractor = compiled(&[&["id"], &["customer", "missing"]]);
+    let mut state = ExtractionState::new();
+    assert!(extractor
+        .evaluate_into(&sample_order(), &mut state)
+        .is_err());
+
+    let extractor = compiled(&[&["id"]]);
+    assert_eq!(
+        extractor
+            .evaluate_into(&sample_order(), &mut state)
+            .unwrap(),
+        &[FieldScalarValue::U64(7)]
+    );
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+#[test]
+fn empty_path_list_is_rejected() {
+    let paths: &[&[&str]] = &[];
+    assert!(matches!(
+        CompiledExtractor::new_from_paths(paths),
+        Err(EvaluateError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn empty_segment_is_rejected() {
+    assert!(matches!(
+        CompiledExtractor::new_from_paths(&[&["customer", ""]]),
+        Err(EvaluateError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn missing_path_is_not_found() {
+    let extractor = compiled(&[&["id"], &["customer", "email"]]);
+    assert!(matches!(
+        extractor.evaluate(&sample_order()),
+        Err(EvaluateError::NestedFieldNotFound {
+            path,
+            failed_at_index: 1,
+            reason: NotFoundReason::MissingField,
+            ..
+        }) if path == ["customer", "email"]
+    ));
+}
+
+#[test]
+fn compound_target_is_unsupported() {
+    let extractor = compiled(&[&["customer"], &["customer", "name"]]);
+    assert!(matches!(
+        extractor.evaluate(&sample_order()),
+        Err(EvaluateError::UnsupportedType {
+            type_name: "struct",
+            rust_type: Some("Customer"),
+            expected: "scalar",
+            path,
+            ..
+        }) if path == ["customer"]
+    ));
+
+    let extractor = compiled(&[&["tags"]]);
+    assert!(matches!(
+        extractor.evaluate(&sample_order()),
+        Err(EvaluateError::UnsupportedType {
+            type_name: "sequence",
+            rust_type: None,
+            expected: "scalar",
+            path,
+            ..
+        }) if path == ["tags"]
+    ));
+}
+
+#[test]
+fn first_failing_path_wins() {
+    let extractor = compiled(&[&["customer", "email"], &["labels"]]);
+    assert!(matches!(
+        extractor.evaluate(&sample_order()),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+
+    let extractor = compiled(&[&["labels"], &["customer", "email"]]);
+    assert!(matches!(
+        extractor.evaluate(&sample_order()),
+        Err(EvaluateError::UnsupportedType {
+            type_name: "map",
+            rust_type: None,
+            expected: "scalar",
+            path,
+            ..
+        }) if path == ["labels"]
+    ));
+}
+
+#[test]
+fn struct_variant_is_unsupported() {
+    let extractor = compiled(&[&["id"], &["status", "carrier"]]);
+    assert!(matches!(
+        extractor.evaluate(&sample_order()),
+        Err(EvaluateError::UnsupportedVariant {
+            variant_type: "struct",
+            rust_type: Some("Status"),
+            variant: Some("Shipped"),
+            expected: "struct or map",
+            path,
+            ..
+        }) if path == ["status"]
+    ));
+}
+
+// =============================================================================
+// Parity with CompositeFieldExtractor
+// =============================================================================
+
+#[test]
+fn matches_composite_extractor() {
+    let order = sample_order();
+    let cases: &[&[&[&str]]] = &[
+        &[&["id"], &["customer", "name"], &["note"]],
+        &[&["labels", "team"], &["customer", "tier"]],
+        &[&["customer", "tier"], &["customer", "email"]],
+        &[&["tags"], &["id"]],
+        &[&["status"]],
+    ];
+
+    for paths in cases {
+        let composite = CompositeFieldExtractor::new_from_paths(paths).unwrap();
+        assert_eq!(
+            composite.compile().evaluate(&order),
+            composite.evaluate(&order),
+            "{paths:?}"
+        );
+    }
+}
+
+#[test]
+fn evaluate_all_matches_composite_extractor() {
+    let order = sample_order();
+    let paths: &[&[&str]] = &[
+        &["customer", "email"],
+        &["id"],
+        &["tags"],
+        &["status", "carrier"],
+        &["labels", "team"],
+    ];
+
+    let composite = CompositeFieldExtractor::new_from_paths(paths).unwrap();
+    let results = composite.compile().evaluate_all(&order).unwrap();
+    assert_eq!(Ok(results.clone()), composite.evaluate_all(&order));
+    assert_eq!(results.iter().filter(|r| r.is_err()).count(), 3);
+}
+
+#[test]
+fn evaluate_all_fails_the_record_on_serialization_failure() {
+    struct Failing;
+
+    impl Serialize for Failing {
+        fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("boom"))
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Record {
+        id: u64,
+        broken: Failing,
+        name: String,
+    }
+
+    let record = Record {
+        id: 1,
+        broken: Failing,
+        name: "x".to_string(),
+    };
+    assert!(matches!(
+        compiled(&[&["id"], &["broken"], &["name"]]).evaluate_all(&record),
+        Err(EvaluateError::SerializationError { .. })
+    ));
+
+    // Every path was filled before the failure, so each keeps its own result.
+    assert_eq!(
+        compiled(&[&["id"]]).evaluate_all(&record),
+        Ok(vec![Ok(FieldScalarValue::U64(1))])
+    );
+}
+
+#[test]
+fn json_values_are_extracted() {
+    let value = serde_json::json!({"a": {"b": 1, "c": "x"}, "d": [1, 2]});
+    let extractor = compiled(&[&["a", "c"], &["a", "b"]]);
+    assert_eq!(
+        extractor.evaluate(&value).unwrap(),
+        vec![
+            FieldScalarValue::String("x".to_string()),
+            FieldScalarValue::U64(1),
+        ]
+    );
+}
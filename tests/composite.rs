@@ -361,3 +361,39 @@ fn error_first_field_fails_fast() {
         EvaluateError::NestedFieldNotFound { ref path, .. } if path == &vec!["missing".to_string()]
     ));
 }
+
+// =============================================================================
+// Collecting every error
+// =============================================================================
+
+#[test]
+fn evaluate_all_reports_every_failing_field() {
+    let extractor = CompositeFieldExtractor::new(&["missing", "name", "other"]).unwrap();
+    let results = extractor.evaluate_all(&sample_record()).unwrap();
+
+    assert_eq!(results.len(), 3);
+    assert!(matches!(
+        &results[0],
+        Err(EvaluateError::NestedFieldNotFound { path, .. }) if path == &vec!["missing".to_string()]
+    ));
+    assert_eq!(
+        results[1],
+        Ok(FieldScalarValue::String("Alice".to_string()))
+    );
+    assert!(matches!(
+        &results[2],
+        Err(EvaluateError::NestedFieldNotFound { path, .. }) if path == &vec!["other".to_string()]
+    ));
+}
+
+#[test]
+fn evaluate_all_matches_evaluate_on_success() {
+    let extractor = CompositeFieldExtractor::new(&["age", "active"]).unwrap();
+    let record = sample_record();
+    let results: Result<Vec<_>, _> = extractor
+        .evaluate_all(&record)
+        .unwrap()
+        .into_iter()
+        .collect();
+    assert_eq!(results, extractor.evaluate(&record));
+}
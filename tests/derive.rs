@@ -0,0 +1,197 @@
+use serde::Serialize;
+use serde_evaluate::{EvaluateError, FieldScalarValue};
+use serde_evaluate_derive::Evaluate;
+use std::collections::HashMap;
+
+#[derive(Serialize, Evaluate)]
+struct UserProfile {
+    name: String,
+    age: u32,
+    nickname: Option<String>,
+    tags: Vec<String>,
+    settings: Settings,
+    backup: Option<Settings>,
+    #[serde(rename = "e-mail")]
+    email: String,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    cache: Vec<u8>,
+    #[evaluate(skip)]
+    labels: HashMap<String, String>,
+    r#type: char,
+}
+
+#[derive(Serialize, Evaluate)]
+#[serde(rename_all = "camelCase")]
+struct Settings {
+    theme_level: u8,
+    dark_mode: bool,
+    #[serde(rename(serialize = "ratio_x", deserialize = "ratio"))]
+    ratio: f64,
+}
+
+#[derive(Serialize, Evaluate)]
+#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+struct Shouting {
+    max_value: i64,
+}
+
+fn sample_profile() -> UserProfile {
+    UserProfile {
+        name: "ada".to_string(),
+        age: 36,
+        nickname: None,
+        tags: vec!["admin".to_string(), "ops".to_string()],
+        settings: Settings {
+            theme_level: 3,
+            dark_mode: true,
+            ratio: 0.5,
+        },
+        backup: None,
+        email: "ada@example.com".to_string(),
+        cache: Vec::new(),
+        labels: HashMap::new(),
+        r#type: 'u',
+    }
+}
+
+// =============================================================================
+// Typed evaluation
+// =============================================================================
+
+#[test]
+fn top_level_fields_are_typed() {
+    let profile = sample_profile();
+    assert_eq!(
+        UserProfile::paths().name().evaluate(&profile).unwrap(),
+        "ada"
+    );
+    assert_eq!(
+        UserProfile::paths().age().evaluate(&profile).unwrap(),
+        36u32
+    );
+    assert_eq!(
+        UserProfile::paths().r#type().evaluate(&profile).unwrap(),
+        'u'
+    );
+}
+
+#[test]
+fn nested_fields_are_typed() {
+    let profile = sample_profile();
+    let settings = UserProfile::paths().settings();
+    assert_eq!(settings.theme_level().evaluate(&profile).unwrap(), 3u8);
+    assert!(settings.dark_mode().evaluate(&profile).unwrap());
+    assert_eq!(settings.ratio().evaluate(&profile).unwrap(), 0.5);
+}
+
+#[test]
+fn option_fields_are_typed() {
+    let mut profile = sample_profile();
+    let nickname = UserProfile::paths().nickname();
+    assert_eq!(nickname.evaluate(&profile).unwrap(), None);
+
+    profile.nickname = Some("countess".to_string());
+    assert_eq!(
+        nickname.evaluate(&profile).unwrap(),
+        Some("countess".to_string())
+    );
+}
+
+#[test]
+fn skipped_option_fields_are_none() {
+    #[derive(Serialize, Evaluate)]
+    struct Sparse {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        settings: Option<Settings>,
+    }
+
+    let sparse = Sparse {
+        note: None,
+        settings: None,
+    };
+    assert_eq!(Sparse::paths().note().evaluate(&sparse).unwrap(), None);
+    assert!(matches!(
+        Sparse::paths().settings().theme_level().evaluate(&sparse),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+
+    let sparse = Sparse {
+        note: Some("hi".to_string()),
+        ..sparse
+    };
+    assert_eq!(
+        Sparse::paths().note().evaluate(&sparse).unwrap(),
+        Some("hi".to_string())
+    );
+}
+
+#[test]
+fn list_fields_are_typed() {
+    assert_eq!(
+        UserProfile::paths()
+            .tags()
+            .evaluate(&sample_profile())
+            .unwrap(),
+        vec!["admin".to_string(), "ops".to_string()]
+    );
+}
+
+#[test]
+fn paths_through_none_are_not_found() {
+    let mut profile = sample_profile();
+    let level = UserProfile::paths().backup().theme_level();
+    assert!(matches!(
+        level.evaluate(&profile),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+
+    profile.backup = Some(Settings {
+        theme_level: 9,
+        dark_mode: false,
+        ratio: 1.0,
+    });
+    assert_eq!(level.evaluate(&profile).unwrap(), 9);
+}
+
+// =============================================================================
+// Serde renames
+// =============================================================================
+
+#[test]
+fn renames_are_honoured() {
+    let paths = UserProfile::paths();
+    assert_eq!(paths.email().extractor().path(), ["e-mail"]);
+    assert_eq!(
+        paths.settings().theme_level().extractor().path(),
+        ["settings", "themeLevel"]
+    );
+    assert_eq!(
+        paths.settings().ratio().extractor().path(),
+        ["settings", "ratio_x"]
+    );
+    assert_eq!(paths.r#type().extractor().path(), ["type"]);
+    assert_eq!(
+        Shouting::paths().max_value().extractor().path(),
+        ["MAX-VALUE"]
+    );
+
+    assert_eq!(
+        paths.email().evaluate(&sample_profile()).unwrap(),
+        "ada@example.com"
+    );
+}
+
+#[test]
+fn untyped_extractor_is_available() {
+    let extractor = UserProfile::paths()
+        .settings()
+        .theme_level()
+        .into_extractor();
+    assert_eq!(
+        extractor.evaluate(&sample_profile()).unwrap(),
+        FieldScalarValue::U8(3)
+    );
+}
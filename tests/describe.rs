@@ -0,0 +1,255 @@
+// Tests for NestedFieldExtractor::describe
+
+use serde::Serialize;
+use serde_evaluate::error::EvaluateError;
+use serde_evaluate::extractor::NestedFieldExtractor;
+use serde_evaluate::probe::{FieldKind, VariantShape};
+use serde_evaluate::value::ScalarKind;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Record {
+    id: u64,
+    profile: Profile,
+    renamed: Renamed,
+    scores: Vec<u32>,
+    pair: (u8, String),
+    point: Point,
+    wrapper: Wrapper,
+    attrs: BTreeMap<String, i32>,
+    maybe_profile: Option<Profile>,
+    nothing: Option<u8>,
+    some_none: Option<Option<u8>>,
+    unit: (),
+    marker: Marker,
+    status: Status,
+    payload: Payload,
+    coords: Coords,
+    event: Event,
+}
+
+#[derive(Serialize)]
+struct Profile {
+    name: String,
+    age: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "RenamedOnTheWire")]
+struct Renamed {
+    x: u8,
+}
+
+#[derive(Serialize)]
+struct Point(i32, i32);
+
+#[derive(Serialize)]
+struct Wrapper(u16);
+
+#[derive(Serialize)]
+struct Marker;
+
+#[derive(Serialize)]
+enum Status {
+    Active,
+}
+
+#[derive(Serialize)]
+enum Payload {
+    Text(String),
+}
+
+#[derive(Serialize)]
+enum Coords {
+    Xy(i32, i32),
+}
+
+#[derive(Serialize)]
+enum Event {
+    Login { user: String, at: u64 },
+}
+
+fn sample_record() -> Record {
+    let profile = || Profile {
+        name: "alice".to_string(),
+        age: 30,
+        email: None,
+    };
+
+    Record {
+        id: 1,
+        profile: profile(),
+        renamed: Renamed { x: 1 },
+        scores: vec![1, 2, 3],
+        pair: (1, "a".to_string()),
+        point: Point(1, 2),
+        wrapper: Wrapper(9),
+        attrs: BTreeMap::new(),
+        maybe_profile: Some(profile()),
+        nothing: None,
+        some_none: Some(None),
+        unit: (),
+        marker: Marker,
+        status: Status::Active,
+        payload: Payload::Text("hi".to_string()),
+        coords: Coords::Xy(1, 2),
+        event: Event::Login {
+            user: "bob".to_string(),
+            at: 0,
+        },
+    }
+}
+
+fn describe(path: &[&str]) -> Result<FieldKind, EvaluateError> {
+    NestedFieldExtractor::new_from_path(path)
+        .unwrap()
+        .describe(&sample_record())
+}
+
+// =============================================================================
+// Scalars
+// =============================================================================
+
+#[test]
+fn test_describe_scalars() {
+    assert_eq!(describe(&["id"]), Ok(FieldKind::Scalar(ScalarKind::U64)));
+    assert_eq!(
+        describe(&["profile", "name"]),
+        Ok(FieldKind::Scalar(ScalarKind::String))
+    );
+    assert_eq!(describe(&["unit"]), Ok(FieldKind::Scalar(ScalarKind::Unit)));
+    assert_eq!(
+        describe(&["marker"]),
+        Ok(FieldKind::Scalar(ScalarKind::Unit))
+    );
+}
+
+#[test]
+fn test_describe_newtype_struct_is_transparent() {
+    assert_eq!(
+        describe(&["wrapper"]),
+        Ok(FieldKind::Scalar(ScalarKind::U16))
+    );
+}
+
+// =============================================================================
+// Compound types
+// =============================================================================
+
+#[test]
+fn test_describe_struct_counts_serialized_fields() {
+    // `email` is skipped, so Serde reports two fields.
+    assert_eq!(
+        describe(&["profile"]),
+        Ok(FieldKind::Struct {
+            name: "Profile",
+            fields: 2
+        })
+    );
+}
+
+#[test]
+fn test_describe_struct_uses_serde_name() {
+    assert_eq!(
+        describe(&["renamed"]),
+        Ok(FieldKind::Struct {
+            name: "RenamedOnTheWire",
+            fields: 1
+        })
+    );
+}
+
+#[test]
+fn test_describe_seq_and_map() {
+    assert_eq!(describe(&["scores"]), Ok(FieldKind::Seq { len: Some(3) }));
+    assert_eq!(describe(&["attrs"]), Ok(FieldKind::Map { len: Some(0) }));
+}
+
+#[test]
+fn test_describe_tuples() {
+    assert_eq!(describe(&["pair"]), Ok(FieldKind::Tuple { len: 2 }));
+    assert_eq!(
+        describe(&["point"]),
+        Ok(FieldKind::TupleStruct {
+            name: "Point",
+            len: 2
+        })
+    );
+}
+
+// =============================================================================
+// Enum variants
+// =============================================================================
+
+#[test]
+fn test_describe_variants() {
+    assert_eq!(
+        describe(&["status"]),
+        Ok(FieldKind::Variant {
+            name: "Status",
+            variant: "Active",
+            shape: VariantShape::Unit
+        })
+    );
+    assert_eq!(
+        describe(&["payload"]),
+        Ok(FieldKind::Variant {
+            name: "Payload",
+            variant: "Text",
+            shape: VariantShape::Newtype
+        })
+    );
+    assert_eq!(
+        describe(&["coords"]),
+        Ok(FieldKind::Variant {
+            name: "Coords",
+            variant: "Xy",
+            shape: VariantShape::Tuple { len: 2 }
+        })
+    );
+    assert_eq!(
+        describe(&["event"]),
+        Ok(FieldKind::Variant {
+            name: "Event",
+            variant: "Login",
+            shape: VariantShape::Struct { fields: 2 }
+        })
+    );
+}
+
+// =============================================================================
+// Options
+// =============================================================================
+
+#[test]
+fn test_describe_options() {
+    assert_eq!(
+        describe(&["maybe_profile"]),
+        Ok(FieldKind::Option(Some(Box::new(FieldKind::Struct {
+            name: "Profile",
+            fields: 2
+        }))))
+    );
+    assert_eq!(describe(&["nothing"]), Ok(FieldKind::Option(None)));
+    assert_eq!(
+        describe(&["some_none"]),
+        Ok(FieldKind::Option(Some(Box::new(FieldKind::Option(None)))))
+    );
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+#[test]
+fn test_describe_missing() {
+    let result = describe(&["profile", "nonexistent"]);
+    assert!(
+        matches!(result, Err(EvaluateError::NestedFieldNotFound { ref path, .. })
+            if path == &vec!["profile".to_string(), "nonexistent".to_string()]),
+        "Expected NestedFieldNotFound, got {:?}",
+        result
+    );
+}
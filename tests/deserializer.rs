@@ -0,0 +1,272 @@
+// Tests for extraction from encoded records (evaluate_from and PathSeed)
+
+use serde::de::DeserializeSeed;
+use serde::Serialize;
+use serde_evaluate::deserializer::PathSeed;
+use serde_evaluate::error::{EvaluateError, NotFoundReason};
+use serde_evaluate::extractor::{FieldExtractor, NestedFieldExtractor};
+use serde_evaluate::value::FieldScalarValue;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Order {
+    id: u32,
+    status: String,
+    total: f64,
+    discount: Option<i32>,
+    note: Option<String>,
+    items: Vec<Item>,
+    customer: Customer,
+    #[serde(with = "serde_bytes")]
+    checksum: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct Item {
+    sku: String,
+}
+
+#[derive(Serialize)]
+struct Customer {
+    region: String,
+    labels: BTreeMap<String, String>,
+}
+
+fn sample_order() -> Order {
+    let mut labels = BTreeMap::new();
+    labels.insert("tier".to_string(), "gold".to_string());
+    Order {
+        id: 7,
+        status: "paid".to_string(),
+        total: 12.5,
+        discount: Some(-3),
+        note: None,
+        items: vec![Item {
+            sku: "A-1".to_string(),
+        }],
+        customer: Customer {
+            region: "eu".to_string(),
+            labels,
+        },
+        checksum: vec![0xde, 0xad],
+    }
+}
+
+fn json_bytes() -> Vec<u8> {
+    serde_json::to_vec(&sample_order()).unwrap()
+}
+
+fn msgpack_bytes() -> Vec<u8> {
+    rmp_serde::to_vec_named(&sample_order()).unwrap()
+}
+
+fn from_json(path: &[&str]) -> Result<FieldScalarValue, EvaluateError> {
+    let bytes = json_bytes();
+    let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+    NestedFieldExtractor::new_from_path(path)?.evaluate_from(&mut deserializer)
+}
+
+fn from_msgpack(path: &[&str]) -> Result<FieldScalarValue, EvaluateError> {
+    let bytes = msgpack_bytes();
+    let mut deserializer = rmp_serde::Deserializer::from_read_ref(&bytes);
+    NestedFieldExtractor::new_from_path(path)?.evaluate_from(&mut deserializer)
+}
+
+// =============================================================================
+// JSON
+// =============================================================================
+
+#[test]
+fn test_from_json_scalars() {
+    assert_eq!(from_json(&["id"]), Ok(FieldScalarValue::U64(7)));
+    assert_eq!(
+        from_json(&["status"]),
+        Ok(FieldScalarValue::String("paid".to_string()))
+    );
+    assert_eq!(from_json(&["total"]), Ok(FieldScalarValue::F64(12.5)));
+}
+
+#[test]
+fn test_from_json_nested_and_map_keys() {
+    assert_eq!(
+        from_json(&["customer", "region"]),
+        Ok(FieldScalarValue::String("eu".to_string()))
+    );
+    assert_eq!(
+        from_json(&["customer", "labels", "tier"]),
+        Ok(FieldScalarValue::String("gold".to_string()))
+    );
+}
+
+#[test]
+fn test_from_json_options() {
+    // JSON does not mark `Some`, so a present value is reported unwrapped
+    assert_eq!(from_json(&["discount"]), Ok(FieldScalarValue::I64(-3)));
+    assert_eq!(from_json(&["note"]), Ok(FieldScalarValue::Unit));
+}
+
+#[test]
+fn test_from_json_matches_serializer_for_json_values() {
+    let value = serde_json::to_value(sample_order()).unwrap();
+    for path in [
+        &["id"][..],
+        &["status"],
+        &["total"],
+        &["discount"],
+        &["customer", "region"],
+    ] {
+        let extractor = NestedFieldExtractor::new_from_path(path).unwrap();
+        assert_eq!(
+            extractor.evaluate_from(&value),
+            extractor.evaluate(&value),
+            "{:?}",
+            path
+        );
+    }
+}
+
+#[test]
+fn test_from_json_field_extractor() {
+    let bytes = json_bytes();
+    let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+    assert_eq!(
+        FieldExtractor::new("status").evaluate_from(&mut deserializer),
+        Ok(FieldScalarValue::String("paid".to_string()))
+    );
+
+    let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+    assert_eq!(
+        FieldExtractor::new("missing").evaluate_from(&mut deserializer),
+        Err(EvaluateError::FieldNotFound {
+            field_name: "missing".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_from_json_consumes_whole_value() {
+    let bytes = json_bytes();
+    let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+    NestedFieldExtractor::new_from_path(&["id"])
+        .unwrap()
+        .evaluate_from(&mut deserializer)
+        .unwrap();
+    assert!(deserializer.end().is_ok());
+}
+
+// =============================================================================
+// MessagePack
+// =============================================================================
+
+#[test]
+fn test_from_msgpack() {
+    // MessagePack stores integers in the smallest type that fits
+    assert_eq!(from_msgpack(&["id"]), Ok(FieldScalarValue::U8(7)));
+    assert_eq!(
+        from_msgpack(&["customer", "labels", "tier"]),
+        Ok(FieldScalarValue::String("gold".to_string()))
+    );
+    assert_eq!(
+        from_msgpack(&["checksum"]),
+        Ok(FieldScalarValue::Bytes(vec![0xde, 0xad]))
+    );
+    assert_eq!(from_msgpack(&["note"]), Ok(FieldScalarValue::Unit));
+}
+
+#[test]
+fn test_from_msgpack_integer_keys_match_their_text() {
+    let mut record = BTreeMap::new();
+    record.insert(1u8, "one");
+    let bytes = rmp_serde::to_vec(&record).unwrap();
+    let from_msgpack = |path: &[&str]| {
+        NestedFieldExtractor::new_from_path(path)
+            .unwrap()
+            .evaluate_from(&mut rmp_serde::Deserializer::from_read_ref(&bytes))
+    };
+
+    assert_eq!(
+        from_msgpack(&["1"]),
+        Ok(FieldScalarValue::String("one".to_string()))
+    );
+    assert!(matches!(
+        from_msgpack(&["01"]),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+#[test]
+fn test_from_json_missing_path_reports_segment() {
+    assert!(matches!(
+        from_json(&["customer", "country"]),
+        Err(EvaluateError::NestedFieldNotFound {
+            path,
+            failed_at_index: 1,
+            reason: NotFoundReason::MissingKey,
+            ..
+        }) if path == ["customer", "country"]
+    ));
+    assert!(matches!(
+        from_json(&["status", "code"]),
+        Err(EvaluateError::NestedFieldNotFound {
+            path,
+            failed_at_index: 1,
+            reason: NotFoundReason::NotTraversable {
+                type_name: "scalar"
+            },
+            ..
+        }) if path == ["status", "code"]
+    ));
+}
+
+#[test]
+fn test_from_json_unsupported_targets() {
+    assert!(matches!(
+        from_json(&["items"]),
+        Err(EvaluateError::UnsupportedType {
+            type_name: "sequence",
+            rust_type: None,
+            expected: "scalar",
+            path,
+            ..
+        }) if path == ["items"]
+    ));
+    assert!(matches!(
+        from_json(&["customer"]),
+        Err(EvaluateError::UnsupportedType {
+            type_name: "map",
+            rust_type: None,
+            expected: "scalar",
+            path,
+            ..
+        }) if path == ["customer"]
+    ));
+}
+
+#[test]
+fn test_from_json_malformed_input() {
+    let mut deserializer = serde_json::Deserializer::from_slice(br#"{"id": 7, "status": "#);
+    let result = NestedFieldExtractor::new_from_path(&["status"])
+        .unwrap()
+        .evaluate_from(&mut deserializer);
+    assert!(
+        matches!(result, Err(EvaluateError::DeserializationError { .. })),
+        "Expected DeserializationError, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_path_seed_inner_result() {
+    let path = vec!["missing".to_string()];
+    let bytes = json_bytes();
+    let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+    let outcome = PathSeed::new(&path).deserialize(&mut deserializer).unwrap();
+    assert!(matches!(
+        outcome,
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+}
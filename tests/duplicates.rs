@@ -0,0 +1,312 @@
+use serde::de::DeserializeSeed;
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::{Serialize, Serializer};
+use serde_evaluate::{
+    CompositeFieldExtractor, DuplicateKeyPolicy, EvaluateError, FieldExtractor, FieldScalarRef,
+    FieldScalarValue, NestedFieldExtractor, PathProbe, PathSeed,
+};
+
+#[derive(Serialize)]
+struct Config {
+    name: String,
+    timeout: u32,
+    #[serde(flatten)]
+    defaults: Defaults,
+}
+
+#[derive(Serialize)]
+struct Defaults {
+    timeout: u32,
+    retries: u8,
+}
+
+fn shadowed_config() -> Config {
+    Config {
+        name: "svc".to_string(),
+        timeout: 5,
+        defaults: Defaults {
+            timeout: 30,
+            retries: 3,
+        },
+    }
+}
+
+/// A struct whose hand-written `Serialize` impl emits `level` twice.
+struct TwiceLevel;
+
+impl Serialize for TwiceLevel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("TwiceLevel", 3)?;
+        state.serialize_field("level", &1u8)?;
+        state.serialize_field("mode", "fast")?;
+        state.serialize_field("level", &2u8)?;
+        state.end()
+    }
+}
+
+/// A map whose hand-written `Serialize` impl emits the `settings` key twice.
+struct TwiceSettings;
+
+impl Serialize for TwiceSettings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("id", &7u64)?;
+        map.serialize_entry("settings", &TwiceLevel)?;
+        map.serialize_entry("settings", &TwiceLevel)?;
+        map.end()
+    }
+}
+
+/// The fields of an `EvaluateError::DuplicateKey`, which only the crate can build.
+#[derive(Debug, Clone, PartialEq)]
+struct Duplicate {
+    path: Vec<String>,
+    failed_at_index: usize,
+    first_entry: usize,
+    second_entry: usize,
+}
+
+fn duplicate(path: &[&str], index: usize, first: usize, second: usize) -> Duplicate {
+    Duplicate {
+        path: path.iter().map(|s| s.to_string()).collect(),
+        failed_at_index: index,
+        first_entry: first,
+        second_entry: second,
+    }
+}
+
+#[track_caller]
+fn as_duplicate(err: EvaluateError) -> Duplicate {
+    match err {
+        EvaluateError::DuplicateKey {
+            path,
+            failed_at_index,
+            first_entry,
+            second_entry,
+            ..
+        } => Duplicate {
+            path,
+            failed_at_index,
+            first_entry,
+            second_entry,
+        },
+        other => panic!("expected DuplicateKey, got {:?}", other),
+    }
+}
+
+fn as_duplicates<T>(results: Vec<Result<T, EvaluateError>>) -> Vec<Result<T, Duplicate>> {
+    results
+        .into_iter()
+        .map(|result| result.map_err(as_duplicate))
+        .collect()
+}
+
+fn detecting(path: &[&str]) -> NestedFieldExtractor {
+    NestedFieldExtractor::new_from_path(path)
+        .unwrap()
+        .with_duplicate_keys(DuplicateKeyPolicy::Error)
+}
+
+// =============================================================================
+// FirstWins policy
+// =============================================================================
+
+#[test]
+fn first_match_wins_by_default() {
+    assert_eq!(
+        FieldExtractor::new("timeout").evaluate(&shadowed_config()),
+        Ok(FieldScalarValue::U32(5))
+    );
+    assert_eq!(
+        NestedFieldExtractor::new_from_path(&["settings", "level"])
+            .unwrap()
+            .evaluate(&TwiceSettings),
+        Ok(FieldScalarValue::U8(1))
+    );
+}
+
+// =============================================================================
+// Error policy
+// =============================================================================
+
+#[test]
+fn flattened_key_shadowing_a_field_is_an_error() {
+    let extractor = FieldExtractor::new("timeout").with_duplicate_keys(DuplicateKeyPolicy::Error);
+    let err = extractor.evaluate(&shadowed_config()).unwrap_err();
+    assert_eq!(as_duplicate(err.clone()), duplicate(&["timeout"], 0, 1, 2));
+    assert_eq!(
+        err.to_string(),
+        "Duplicate key in path 'timeout' (segment 0: 'timeout' matches entries 1 and 2 \
+         of the same struct or map)"
+    );
+}
+
+#[test]
+fn duplicate_struct_field_is_an_error() {
+    assert_eq!(
+        detecting(&["level"])
+            .evaluate(&TwiceLevel)
+            .map_err(as_duplicate),
+        Err(duplicate(&["level"], 0, 0, 2))
+    );
+    assert_eq!(
+        detecting(&["level"])
+            .evaluate_with(&TwiceLevel, |v| v == FieldScalarRef::U8(1))
+            .map_err(as_duplicate),
+        Err(duplicate(&["level"], 0, 0, 2))
+    );
+}
+
+#[test]
+fn duplicate_intermediate_key_is_an_error() {
+    assert_eq!(
+        detecting(&["settings", "mode"])
+            .evaluate(&TwiceSettings)
+            .map_err(as_duplicate),
+        Err(duplicate(&["settings", "mode"], 0, 1, 2))
+    );
+}
+
+#[test]
+fn duplicate_below_the_target_path_is_an_error() {
+    // The first `settings` entry already holds a duplicate `level`.
+    assert_eq!(
+        detecting(&["settings", "level"])
+            .evaluate(&TwiceSettings)
+            .map_err(as_duplicate),
+        Err(duplicate(&["settings", "level"], 1, 0, 2))
+    );
+}
+
+#[test]
+fn unique_keys_are_extracted() {
+    let config = shadowed_config();
+    assert_eq!(
+        detecting(&["retries"]).evaluate(&config),
+        Ok(FieldScalarValue::U8(3))
+    );
+    assert_eq!(
+        detecting(&["id"]).evaluate(&TwiceSettings),
+        Ok(FieldScalarValue::U64(7))
+    );
+    assert_eq!(
+        detecting(&["mode"]).evaluate(&TwiceLevel),
+        Ok(FieldScalarValue::String("fast".to_string()))
+    );
+}
+
+#[test]
+fn describe_and_exists_detect_duplicates() {
+    assert_eq!(
+        detecting(&["timeout"])
+            .exists(&shadowed_config())
+            .map_err(as_duplicate),
+        Err(duplicate(&["timeout"], 0, 1, 2))
+    );
+    assert_eq!(
+        detecting(&["email"]).exists(&shadowed_config()),
+        Ok(PathProbe::Missing)
+    );
+}
+
+// =============================================================================
+// Deserialized input
+// =============================================================================
+
+#[test]
+fn deserialized_input_detects_duplicates() {
+    let json = r#"{"id": 7, "level": 1, "settings": {"level": 1}, "level": 2}"#;
+    let from_json = |extractor: NestedFieldExtractor| {
+        let mut de = serde_json::Deserializer::from_str(json);
+        extractor.evaluate_from(&mut de)
+    };
+
+    let first_wins = NestedFieldExtractor::new_from_path(&["level"]).unwrap();
+    assert_eq!(from_json(first_wins), Ok(FieldScalarValue::U64(1)));
+    assert_eq!(
+        from_json(detecting(&["level"])).map_err(as_duplicate),
+        Err(duplicate(&["level"], 0, 1, 3))
+    );
+    assert_eq!(
+        from_json(detecting(&["settings", "level"])),
+        Ok(FieldScalarValue::U64(1))
+    );
+
+    let json = r#"{"settings": {"level": 1}, "settings": {"level": 2}}"#;
+    let path = ["settings".to_string(), "level".to_string()];
+    let mut de = serde_json::Deserializer::from_str(json);
+    let seed = PathSeed::new(&path).with_duplicate_keys(DuplicateKeyPolicy::Error);
+    assert_eq!(
+        seed.deserialize(&mut de).unwrap().map_err(as_duplicate),
+        Err(duplicate(&["settings", "level"], 0, 0, 1))
+    );
+}
+
+// =============================================================================
+// Composite and compiled extractors
+// =============================================================================
+
+#[test]
+fn composite_and_compiled_detect_duplicates() {
+    let config = shadowed_config();
+    let composite = CompositeFieldExtractor::new(&["name", "timeout", "retries"])
+        .unwrap()
+        .with_duplicate_keys(DuplicateKeyPolicy::Error);
+
+    let expected = vec![
+        Ok(FieldScalarValue::String("svc".to_string())),
+        Err(duplicate(&["timeout"], 0, 1, 2)),
+        Ok(FieldScalarValue::U8(3)),
+    ];
+    assert_eq!(
+        composite.evaluate_all(&config).map(as_duplicates),
+        Ok(expected.clone())
+    );
+    assert_eq!(
+        composite.compile().evaluate_all(&config).map(as_duplicates),
+        Ok(expected)
+    );
+    assert_eq!(
+        composite.compile().evaluate(&config).map_err(as_duplicate),
+        Err(duplicate(&["timeout"], 0, 1, 2))
+    );
+}
+
+#[test]
+fn compiled_applies_the_policy_per_path() {
+    let composite = CompositeFieldExtractor::from_extractors(vec![
+        NestedFieldExtractor::new_from_path(&["settings", "mode"]).unwrap(),
+        detecting(&["settings", "mode"]),
+        detecting(&["id"]),
+    ])
+    .unwrap();
+
+    let expected = vec![
+        Ok(FieldScalarValue::String("fast".to_string())),
+        Err(duplicate(&["settings", "mode"], 0, 1, 2)),
+        Ok(FieldScalarValue::U64(7)),
+    ];
+    assert_eq!(
+        composite.evaluate_all(&TwiceSettings).map(as_duplicates),
+        Ok(expected.clone())
+    );
+    assert_eq!(
+        composite
+            .compile()
+            .evaluate_all(&TwiceSettings)
+            .map(as_duplicates),
+        Ok(expected)
+    );
+}
+
+#[test]
+fn compiled_detects_duplicates_after_every_path_is_filled() {
+    let compiled = CompositeFieldExtractor::new(&["level"])
+        .unwrap()
+        .compile()
+        .with_duplicate_keys(DuplicateKeyPolicy::Error);
+    assert_eq!(
+        compiled.evaluate(&TwiceLevel).map_err(as_duplicate),
+        Err(duplicate(&["level"], 0, 0, 2))
+    );
+}
@@ -0,0 +1,177 @@
+// Tests for borrowed evaluation through `evaluate_with`.
+
+use serde::Serialize;
+use serde_evaluate::error::EvaluateError;
+use serde_evaluate::extractor::{FieldExtractor, NestedFieldExtractor};
+use serde_evaluate::value::{FieldScalarRef, FieldScalarValue};
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Record {
+    id: u32,
+    name: String,
+    #[serde(with = "serde_bytes")]
+    payload: Vec<u8>,
+    nickname: Option<String>,
+    missing: Option<String>,
+    nested_opt: Option<Option<i32>>,
+    inner: Inner,
+    attrs: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+struct Inner {
+    level: u8,
+}
+
+fn sample_record() -> Record {
+    let mut attrs = BTreeMap::new();
+    attrs.insert("region".to_string(), "eu-west-1".to_string());
+
+    Record {
+        id: 7,
+        name: "Alice".to_string(),
+        payload: vec![1, 2, 3],
+        nickname: Some("Al".to_string()),
+        missing: None,
+        nested_opt: Some(Some(5)),
+        inner: Inner { level: 3 },
+        attrs,
+    }
+}
+
+// =============================================================================
+// FieldExtractor::evaluate_with
+// =============================================================================
+
+#[test]
+fn test_borrowed_string_points_into_record() {
+    let record = sample_record();
+    let extractor = FieldExtractor::new("name");
+    let same_buffer = extractor
+        .evaluate_with(&record, |v| match v {
+            FieldScalarRef::String(s) => std::ptr::eq(s.as_ptr(), record.name.as_ptr()),
+            _ => false,
+        })
+        .unwrap();
+    assert!(
+        same_buffer,
+        "expected the string to be borrowed, not copied"
+    );
+}
+
+#[test]
+fn test_borrowed_bytes() {
+    let record = sample_record();
+    let extractor = FieldExtractor::new("payload");
+    let result = extractor.evaluate_with(&record, |v| v == FieldScalarRef::Bytes(&[1, 2, 3]));
+    assert_eq!(result, Ok(true));
+}
+
+#[test]
+fn test_borrowed_scalar() {
+    let record = sample_record();
+    let extractor = FieldExtractor::new("id");
+    let result = extractor.evaluate_with(&record, |v| v == FieldScalarRef::U32(7));
+    assert_eq!(result, Ok(true));
+}
+
+#[test]
+fn test_borrowed_option_some() {
+    let record = sample_record();
+    let extractor = FieldExtractor::new("nickname");
+    let result = extractor.evaluate_with(&record, |v| {
+        v == FieldScalarRef::Option(Some(&FieldScalarRef::String("Al")))
+    });
+    assert_eq!(result, Ok(true));
+}
+
+#[test]
+fn test_borrowed_option_none() {
+    let record = sample_record();
+    let extractor = FieldExtractor::new("missing");
+    let result = extractor.evaluate_with(&record, |v| v == FieldScalarRef::Option(None));
+    assert_eq!(result, Ok(true));
+}
+
+#[test]
+fn test_borrowed_nested_option_matches_owned() {
+    let record = sample_record();
+    let extractor = FieldExtractor::new("nested_opt");
+    let borrowed = extractor
+        .evaluate_with(&record, |v| v.to_owned_value())
+        .unwrap();
+    assert_eq!(borrowed, extractor.evaluate(&record).unwrap());
+    assert_eq!(
+        borrowed,
+        FieldScalarValue::Option(Some(Box::new(FieldScalarValue::Option(Some(Box::new(
+            FieldScalarValue::I32(5)
+        ))))))
+    );
+}
+
+#[test]
+fn test_borrowed_field_not_found_skips_callback() {
+    let record = sample_record();
+    let extractor = FieldExtractor::new("nonexistent");
+    let mut called = false;
+    let result = extractor.evaluate_with(&record, |_| called = true);
+    assert!(matches!(
+        result,
+        Err(EvaluateError::FieldNotFound { ref field_name }) if field_name == "nonexistent"
+    ));
+    assert!(!called);
+}
+
+#[test]
+fn test_borrowed_unsupported_type() {
+    let record = sample_record();
+    let extractor = FieldExtractor::new("inner");
+    let result = extractor.evaluate_with(&record, |_| ());
+    assert!(matches!(result, Err(EvaluateError::UnsupportedType { .. })));
+}
+
+// =============================================================================
+// NestedFieldExtractor::evaluate_with
+// =============================================================================
+
+#[test]
+fn test_nested_borrowed_struct_field() {
+    let record = sample_record();
+    let extractor = NestedFieldExtractor::new_from_path(&["inner", "level"]).unwrap();
+    let result = extractor.evaluate_with(&record, |v| v.to_owned_value());
+    assert_eq!(result, Ok(FieldScalarValue::U8(3)));
+}
+
+#[test]
+fn test_nested_borrowed_map_value() {
+    let record = sample_record();
+    let extractor = NestedFieldExtractor::new_from_path(&["attrs", "region"]).unwrap();
+    let result = extractor.evaluate_with(
+        &record,
+        |v| matches!(v, FieldScalarRef::String(s) if s.starts_with("eu-")),
+    );
+    assert_eq!(result, Ok(true));
+}
+
+#[test]
+fn test_nested_borrowed_not_found() {
+    let record = sample_record();
+    let extractor = NestedFieldExtractor::new_from_path(&["attrs", "zone"]).unwrap();
+    let result = extractor.evaluate_with(&record, |_| ());
+    assert!(matches!(
+        result,
+        Err(EvaluateError::NestedFieldNotFound { ref path, .. })
+            if path == &vec!["attrs".to_string(), "zone".to_string()]
+    ));
+}
+
+#[test]
+fn test_into_owned_conversion() {
+    let inner = FieldScalarRef::String("x");
+    let value: FieldScalarValue = FieldScalarRef::Option(Some(&inner)).into();
+    assert_eq!(
+        value,
+        FieldScalarValue::Option(Some(Box::new(FieldScalarValue::String("x".to_string()))))
+    );
+}
@@ -0,0 +1,379 @@
+// Tests for computed Expressions and the FunctionRegistry
+
+use serde::Serialize;
+use serde_evaluate::error::EvaluateError;
+use serde_evaluate::expr::{ExprValue, Expression, FunctionRegistry, Predicate};
+use serde_evaluate::value::FieldScalarValue;
+
+#[derive(Serialize)]
+struct Event {
+    user_id: u64,
+    email: String,
+    first: String,
+    last: String,
+    initial: char,
+    status: String,
+    ts: u64,
+    offset: i64,
+    latency: f64,
+    tags: Vec<String>,
+    nickname: Option<String>,
+    deleted_at: Option<u64>,
+    payload: serde_bytes::ByteBuf,
+}
+
+fn sample_event() -> Event {
+    Event {
+        user_id: 42,
+        email: "Ada@Example.COM".to_string(),
+        first: "Ada".to_string(),
+        last: "Lovelace".to_string(),
+        initial: 'a',
+        status: "Active".to_string(),
+        ts: 7_250,
+        offset: -7,
+        latency: 12.75,
+        tags: vec!["vip".to_string(), "beta".to_string()],
+        nickname: None,
+        deleted_at: None,
+        payload: serde_bytes::ByteBuf::from(vec![1, 2, 3]),
+    }
+}
+
+fn eval(expression: &str) -> Result<FieldScalarValue, EvaluateError> {
+    Expression::new(expression)?.evaluate(&sample_event())
+}
+
+fn string(s: &str) -> FieldScalarValue {
+    FieldScalarValue::String(s.to_string())
+}
+
+fn assert_function_error(expression: &str, expected_function: &str) {
+    match eval(expression) {
+        Err(EvaluateError::ExpressionFailed {
+            expression: failed,
+            source,
+            ..
+        }) => {
+            assert_eq!(failed, expression);
+            assert!(
+                matches!(*source, EvaluateError::FunctionError { ref function, .. } if function == expected_function),
+                "Expected FunctionError from {}, got {:?}",
+                expected_function,
+                source
+            );
+        }
+        other => panic!(
+            "Expected ExpressionFailed for {}, got {:?}",
+            expression, other
+        ),
+    }
+}
+
+// =============================================================================
+// Built-in functions
+// =============================================================================
+
+#[test]
+fn test_expression_lower_upper() {
+    assert_eq!(eval("lower(email)"), Ok(string("ada@example.com")));
+    assert_eq!(eval("upper(status)"), Ok(string("ACTIVE")));
+    assert_eq!(eval("upper(initial)"), Ok(string("A")));
+    assert_eq!(eval("lower(nickname)"), Ok(FieldScalarValue::Option(None)));
+}
+
+#[test]
+fn test_expression_truncate_integers() {
+    assert_eq!(eval("truncate(ts, 3600)"), Ok(FieldScalarValue::U64(7_200)));
+    assert_eq!(eval("truncate(ts, 1)"), Ok(FieldScalarValue::U64(7_250)));
+    // Rounds down, not towards zero
+    assert_eq!(eval("truncate(offset, 5)"), Ok(FieldScalarValue::I64(-10)));
+    assert_eq!(
+        eval("truncate(deleted_at, 60)"),
+        Ok(FieldScalarValue::Option(None))
+    );
+}
+
+#[test]
+fn test_expression_truncate_floats() {
+    assert_eq!(
+        eval("truncate(latency, 5)"),
+        Ok(FieldScalarValue::F64(10.0))
+    );
+    assert_eq!(
+        eval("truncate(latency, 0.5)"),
+        Ok(FieldScalarValue::F64(12.5))
+    );
+}
+
+#[test]
+fn test_expression_truncate_float_by_any_integer_step() {
+    #[derive(Serialize)]
+    struct Price {
+        amount: f64,
+        by_u8: u8,
+        by_u16: u16,
+        by_i16: i16,
+        by_u128: u128,
+    }
+
+    let price = Price {
+        amount: 12.75,
+        by_u8: 5,
+        by_u16: 4,
+        by_i16: 3,
+        by_u128: 10,
+    };
+    for (expression, expected) in [
+        ("truncate(amount, by_u8)", 10.0),
+        ("truncate(amount, by_u16)", 12.0),
+        ("truncate(amount, by_i16)", 12.0),
+        ("truncate(amount, by_u128)", 10.0),
+    ] {
+        assert_eq!(
+            Expression::new(expression).unwrap().evaluate(&price),
+            Ok(FieldScalarValue::F64(expected)),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn test_expression_truncate_invalid_step() {
+    assert_function_error("truncate(ts, 0)", "truncate");
+    assert_function_error("truncate(ts, -60)", "truncate");
+    assert_function_error("truncate(ts, 1.5)", "truncate");
+    assert_function_error("truncate(email, 10)", "truncate");
+}
+
+#[test]
+fn test_expression_len() {
+    assert_eq!(eval("len(tags)"), Ok(FieldScalarValue::U64(2)));
+    assert_eq!(eval("len(first)"), Ok(FieldScalarValue::U64(3)));
+    assert_eq!(eval("len(payload)"), Ok(FieldScalarValue::U64(3)));
+    assert_eq!(
+        eval(r#"len(["a", "b", "c"])"#),
+        Ok(FieldScalarValue::U64(3))
+    );
+    assert_eq!(eval("len(nickname)"), Ok(FieldScalarValue::Option(None)));
+    assert_function_error("len(ts)", "len");
+}
+
+#[test]
+fn test_expression_concat() {
+    assert_eq!(
+        eval(r#"concat(first, " ", last)"#),
+        Ok(string("Ada Lovelace"))
+    );
+    assert_eq!(
+        eval(r#"concat(status, ":", user_id)"#),
+        Ok(string("Active:42"))
+    );
+    assert_eq!(eval("concat()"), Ok(string("")));
+    assert_eq!(
+        eval("concat(first, nickname)"),
+        Ok(FieldScalarValue::Option(None))
+    );
+    assert_function_error("concat(first, tags)", "concat");
+}
+
+#[test]
+fn test_expression_coalesce() {
+    assert_eq!(eval("coalesce(nickname, first)"), Ok(string("Ada")));
+    assert_eq!(
+        eval("coalesce(nickname, deleted_at)"),
+        Ok(FieldScalarValue::Option(None))
+    );
+    assert_eq!(
+        eval("coalesce(deleted_at, 0)"),
+        Ok(FieldScalarValue::I64(0))
+    );
+}
+
+#[test]
+fn test_expression_coalesce_skipped_and_missing_paths() {
+    #[derive(Serialize)]
+    struct User {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nick: Option<String>,
+        name: String,
+    }
+
+    let coalesce = Expression::new("coalesce(nick, name)").unwrap();
+    let user = User {
+        nick: None,
+        name: "Ada".to_string(),
+    };
+    assert_eq!(coalesce.evaluate(&user), Ok(string("Ada")));
+    let user = User {
+        nick: Some("ace".to_string()),
+        ..user
+    };
+    assert_eq!(coalesce.evaluate(&user), Ok(string("ace")));
+
+    // Absent JSON keys and nulls mid-path count as null too
+    let json = serde_json::json!({"name": "Ada", "profile": null});
+    let coalesce = Expression::new("coalesce(nick, profile.nick, name)").unwrap();
+    assert_eq!(coalesce.evaluate(&json), Ok(string("Ada")));
+
+    // Paths through a non-container, and other functions, still fail
+    assert!(matches!(
+        Expression::new("coalesce(name.first, name)")
+            .unwrap()
+            .evaluate(&json),
+        Err(EvaluateError::ExpressionFailed { .. })
+    ));
+    assert!(matches!(
+        Expression::new("concat(nick, name)")
+            .unwrap()
+            .evaluate(&json),
+        Err(EvaluateError::ExpressionFailed { .. })
+    ));
+
+    // Only the built-in coalesce passes missing paths as null
+    let mut registry = FunctionRegistry::new();
+    registry.register("coalesce", |_: &[ExprValue]| Ok(string("custom")));
+    assert!(matches!(
+        Expression::with_registry("coalesce(nick, name)", &registry)
+            .unwrap()
+            .evaluate(&json),
+        Err(EvaluateError::ExpressionFailed { .. })
+    ));
+}
+
+#[test]
+fn test_expression_hash64_is_stable() {
+    let hash = eval("hash64(user_id)").unwrap();
+    assert_eq!(hash, FieldScalarValue::U64(0x14c1_5884_5e02_890c));
+    assert_eq!(eval("hash64(user_id)"), Ok(hash));
+    assert_ne!(eval("hash64(ts)"), eval("hash64(user_id)"));
+    assert_eq!(
+        eval("hash64(deleted_at)"),
+        Ok(FieldScalarValue::Option(None))
+    );
+}
+
+#[test]
+fn test_expression_nested_calls() {
+    assert_eq!(
+        eval(r#"upper(concat(coalesce(nickname, first), "!"))"#),
+        Ok(string("ADA!"))
+    );
+}
+
+// =============================================================================
+// Custom functions
+// =============================================================================
+
+#[test]
+fn test_expression_custom_function() {
+    let mut registry = FunctionRegistry::new();
+    registry.register("count_args", |args: &[ExprValue]| {
+        Ok(FieldScalarValue::U64(args.len() as u64))
+    });
+
+    let expression = Expression::with_registry("count_args(ts, tags, 1)", &registry).unwrap();
+    assert_eq!(
+        expression.evaluate(&sample_event()),
+        Ok(FieldScalarValue::U64(3))
+    );
+
+    // The default registry does not know about it
+    assert!(matches!(
+        Expression::new("count_args(ts)"),
+        Err(EvaluateError::ExpressionSyntax { position: 0, .. })
+    ));
+}
+
+#[test]
+fn test_expression_custom_function_overrides_builtin() {
+    let mut registry = FunctionRegistry::new();
+    registry.register("lower", |_: &[ExprValue]| Ok(string("overridden")));
+
+    let expression = Expression::with_registry("lower(email)", &registry).unwrap();
+    assert_eq!(
+        expression.evaluate(&sample_event()),
+        Ok(string("overridden"))
+    );
+}
+
+#[test]
+fn test_expression_functions_in_predicate() {
+    let predicate = Predicate::new(r#"lower(status) == "active" && len(tags) >= 2"#).unwrap();
+    assert_eq!(predicate.evaluate(&sample_event()), Ok(true));
+
+    let mut registry = FunctionRegistry::new();
+    registry.register("is_vip", |args: &[ExprValue]| match args {
+        [ExprValue::List(tags)] => Ok(FieldScalarValue::Bool(tags.contains(&string("vip")))),
+        _ => Err(EvaluateError::FunctionError {
+            function: "is_vip".to_string(),
+            message: "expected a list".to_string(),
+        }),
+    });
+    let predicate = Predicate::with_registry("is_vip(tags)", &registry).unwrap();
+    assert_eq!(predicate.evaluate(&sample_event()), Ok(true));
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+#[test]
+fn test_expression_unknown_function_is_syntax_error() {
+    match Expression::new("ts > 1 && nope(ts)") {
+        Err(EvaluateError::ExpressionSyntax { position, message }) => {
+            assert_eq!(position, 10);
+            assert!(message.contains("nope"), "{}", message);
+        }
+        other => panic!("Expected ExpressionSyntax, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_expression_wrong_argument_count() {
+    assert_function_error("lower(first, last)", "lower");
+    assert_function_error("truncate(ts)", "truncate");
+    assert_function_error("hash64()", "hash64");
+}
+
+#[test]
+fn test_expression_argument_errors_name_the_argument() {
+    match eval("lower(missing)") {
+        Err(EvaluateError::ExpressionFailed {
+            expression, source, ..
+        }) => {
+            assert_eq!(expression, "missing");
+            assert!(matches!(*source, EvaluateError::NestedFieldNotFound { .. }));
+        }
+        other => panic!("Expected ExpressionFailed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_expression_list_result_is_type_mismatch() {
+    match eval("tags") {
+        Err(EvaluateError::ExpressionFailed {
+            expression, source, ..
+        }) => {
+            assert_eq!(expression, "tags");
+            assert!(matches!(*source, EvaluateError::TypeMismatch { .. }));
+        }
+        other => panic!("Expected ExpressionFailed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_expression_function_error_display() {
+    let err = eval("upper(ts)").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expression 'upper(ts)' failed: Function 'upper' failed: expected a string, found U64"
+    );
+}
+
+#[test]
+fn test_expression_as_str() {
+    let expression = Expression::new("lower(email)").unwrap();
+    assert_eq!(expression.as_str(), "lower(email)");
+}
@@ -266,7 +266,7 @@ fn test_extract_option_non_scalar_vec() {
     let result = extractor.evaluate(&record);
     // Similar to the struct case, expect UnsupportedType for the sequence.
     assert!(
-        matches!(result, Err(EvaluateError::UnsupportedType { type_name }) if type_name == "sequence"),
+        matches!(result, Err(EvaluateError::UnsupportedType { type_name, .. }) if type_name == "sequence"),
         "Expected UnsupportedType for Option<Vec>, got {:?}",
         result
     );
@@ -0,0 +1,155 @@
+use serde::Serialize;
+use serde_evaluate::hash::{hash128, hash64, jump_consistent_hash, modulo_bucket};
+use serde_evaluate::{CompositeFieldExtractor, EvaluateError, Expression, FieldScalarValue};
+use std::num::NonZeroU32;
+
+#[derive(Serialize)]
+struct Event {
+    tenant_id: u64,
+    region: String,
+    note: Option<String>,
+}
+
+fn event(tenant_id: u64, region: &str) -> Event {
+    Event {
+        tenant_id,
+        region: region.to_string(),
+        note: None,
+    }
+}
+
+fn buckets(n: u32) -> NonZeroU32 {
+    NonZeroU32::new(n).unwrap()
+}
+
+fn string(s: &str) -> FieldScalarValue {
+    FieldScalarValue::String(s.to_string())
+}
+
+// =============================================================================
+// Stable values
+// =============================================================================
+
+// These values are part of the format: changing them breaks every shard assignment
+// persisted by users.
+#[test]
+fn hashes_are_stable() {
+    let key = [FieldScalarValue::U64(42), string("eu")];
+    assert_eq!(hash64(&key), 0x9e24_427a_b07f_c866);
+    assert_eq!(hash128(&key), 0xb6bb_349a_5476_931e_d5e2_98b3_b447_2b86);
+
+    assert_eq!(hash64(&[]), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(hash128(&[]), 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d);
+    assert_eq!(hash64(&[FieldScalarValue::U32(42)]), 0x6391_4582_0a46_368f);
+    assert_eq!(
+        hash64(&[FieldScalarValue::Option(None)]),
+        0x086f_b407_b51f_68cf
+    );
+}
+
+#[test]
+fn encoding_is_type_tagged_and_unambiguous() {
+    assert_ne!(
+        hash64(&[FieldScalarValue::U32(1)]),
+        hash64(&[FieldScalarValue::U64(1)])
+    );
+    assert_ne!(hash64(&[string("1")]), hash64(&[FieldScalarValue::U8(1)]));
+    assert_ne!(
+        hash64(&[string("ab"), string("c")]),
+        hash64(&[string("a"), string("bc")])
+    );
+    assert_ne!(
+        hash64(&[FieldScalarValue::Option(None)]),
+        hash64(&[FieldScalarValue::Unit])
+    );
+}
+
+// =============================================================================
+// Extractors
+// =============================================================================
+
+#[test]
+fn extractors_hash_the_extracted_values() {
+    let routing = CompositeFieldExtractor::new(&["tenant_id", "region"]).unwrap();
+    let record = event(42, "eu");
+    let expected = [FieldScalarValue::U64(42), string("eu")];
+
+    assert_eq!(routing.hash64(&record), Ok(hash64(&expected)));
+    assert_eq!(routing.hash128(&record), Ok(hash128(&expected)));
+
+    let compiled = routing.compile();
+    assert_eq!(compiled.hash64(&record), Ok(hash64(&expected)));
+    assert_eq!(compiled.hash128(&record), Ok(hash128(&expected)));
+}
+
+#[test]
+fn hash_ignores_other_fields() {
+    let routing = CompositeFieldExtractor::new(&["tenant_id", "region"]).unwrap();
+    let noted = Event {
+        note: Some("retry".to_string()),
+        ..event(42, "eu")
+    };
+    assert_eq!(routing.hash64(&event(42, "eu")), routing.hash64(&noted));
+    assert_ne!(
+        routing.hash64(&event(42, "eu")),
+        routing.hash64(&event(42, "us"))
+    );
+}
+
+#[test]
+fn hash_fails_like_evaluate() {
+    let routing = CompositeFieldExtractor::new(&["tenant_id", "zone"]).unwrap();
+    assert!(matches!(
+        routing.hash64(&event(42, "eu")),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+    assert!(matches!(
+        routing.compile().hash128(&event(42, "eu")),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+}
+
+#[test]
+fn hash64_function_agrees_with_extractor() {
+    let expression = Expression::new("hash64(tenant_id)").unwrap();
+    let routing = CompositeFieldExtractor::new(&["tenant_id"]).unwrap();
+    let record = event(42, "eu");
+    assert_eq!(
+        expression.evaluate(&record),
+        Ok(FieldScalarValue::U64(routing.hash64(&record).unwrap()))
+    );
+}
+
+// =============================================================================
+// Bucket assignment
+// =============================================================================
+
+#[test]
+fn jump_consistent_hash_matches_reference_values() {
+    assert_eq!(jump_consistent_hash(1, buckets(1)), 0);
+    assert_eq!(jump_consistent_hash(42, buckets(57)), 43);
+    assert_eq!(jump_consistent_hash(0xDEAD_10CC, buckets(1)), 0);
+    assert_eq!(jump_consistent_hash(0xDEAD_10CC, buckets(666)), 361);
+    assert_eq!(jump_consistent_hash(256, buckets(1024)), 520);
+}
+
+#[test]
+fn jump_consistent_hash_only_moves_keys_to_the_new_bucket() {
+    for key in 0..1000u64 {
+        let key = hash64(&[FieldScalarValue::U64(key)]);
+        for n in 1..32 {
+            let before = jump_consistent_hash(key, buckets(n));
+            let after = jump_consistent_hash(key, buckets(n + 1));
+            assert!(before < n);
+            assert!(after == before || after == n);
+        }
+    }
+}
+
+#[test]
+fn modulo_bucket_uses_the_remainder() {
+    assert_eq!(modulo_bucket(0, buckets(7)), 0);
+    assert_eq!(modulo_bucket(22, buckets(7)), 1);
+    assert_eq!(modulo_bucket(u64::MAX, buckets(1)), 0);
+    assert_eq!(modulo_bucket(u64::MAX, buckets(u32::MAX)), 0);
+}
@@ -0,0 +1,339 @@
+// Tests for IndexDefinition and the key encoding
+
+use serde::Serialize;
+use serde_evaluate::encoding::encode_key;
+use serde_evaluate::error::EvaluateError;
+use serde_evaluate::index::IndexDefinition;
+use serde_evaluate::value::FieldScalarValue;
+use std::cmp::Ordering;
+
+#[derive(Serialize)]
+struct Article {
+    id: u64,
+    author: String,
+    tags: Vec<String>,
+    regions: Vec<u8>,
+    stats: Stats,
+}
+
+#[derive(Serialize)]
+struct Stats {
+    views: u32,
+}
+
+fn article(tags: &[&str], regions: &[u8]) -> Article {
+    Article {
+        id: 7,
+        author: "alice".to_string(),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        regions: regions.to_vec(),
+        stats: Stats { views: 100 },
+    }
+}
+
+fn string(s: &str) -> FieldScalarValue {
+    FieldScalarValue::String(s.to_string())
+}
+
+fn pk() -> Vec<FieldScalarValue> {
+    vec![FieldScalarValue::U64(7)]
+}
+
+// =============================================================================
+// Entries
+// =============================================================================
+
+#[test]
+fn test_index_scalar_key() {
+    let index = IndexDefinition::new("by_author")
+        .with_key_path(&["author"])
+        .unwrap()
+        .with_value_path(&["stats", "views"])
+        .unwrap();
+
+    let entries = index.entries(&article(&[], &[]), &pk()).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        entries[0].key_bytes,
+        encode_key(&[
+            string("by_author"),
+            string("alice"),
+            FieldScalarValue::U64(7)
+        ])
+    );
+    assert_eq!(
+        entries[0].value_bytes,
+        encode_key(&[FieldScalarValue::U32(100)])
+    );
+}
+
+#[test]
+fn test_index_unique_moves_primary_key_to_value() {
+    let index = IndexDefinition::new("by_author")
+        .with_key_path(&["author"])
+        .unwrap()
+        .with_value_path(&["stats", "views"])
+        .unwrap()
+        .with_unique(true);
+    assert!(index.is_unique());
+
+    let entries = index.entries(&article(&[], &[]), &pk()).unwrap();
+    assert_eq!(
+        entries[0].key_bytes,
+        encode_key(&[string("by_author"), string("alice")])
+    );
+    assert_eq!(
+        entries[0].value_bytes,
+        encode_key(&[FieldScalarValue::U64(7), FieldScalarValue::U32(100)])
+    );
+}
+
+#[test]
+fn test_index_fan_out_cartesian_product() {
+    let index = IndexDefinition::new("by_tag_region")
+        .with_fan_out_path(&["tags"])
+        .unwrap()
+        .with_fan_out_path(&["regions"])
+        .unwrap();
+
+    let entries = index
+        .entries(&article(&["b", "a"], &[1, 2]), &pk())
+        .unwrap();
+    let expected: Vec<Vec<u8>> = [("a", 1), ("a", 2), ("b", 1), ("b", 2)]
+        .iter()
+        .map(|(tag, region)| {
+            encode_key(&[
+                string("by_tag_region"),
+                string(tag),
+                FieldScalarValue::U8(*region),
+                FieldScalarValue::U64(7),
+            ])
+        })
+        .collect();
+    let keys: Vec<Vec<u8>> = entries.into_iter().map(|e| e.key_bytes).collect();
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn test_index_fan_out_duplicates_and_empty() {
+    let index = IndexDefinition::new("by_tag")
+        .with_fan_out_path(&["tags"])
+        .unwrap();
+
+    let duplicated = index.entries(&article(&["a", "a"], &[]), &pk()).unwrap();
+    assert_eq!(duplicated.len(), 1);
+
+    let empty = index.entries(&article(&[], &[]), &pk()).unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_index_errors() {
+    let no_keys = IndexDefinition::new("empty").entries(&article(&[], &[]), &pk());
+    assert!(matches!(no_keys, Err(EvaluateError::InvalidPath(_))));
+
+    let missing = IndexDefinition::new("by_missing")
+        .with_key_path(&["missing"])
+        .unwrap()
+        .entries(&article(&[], &[]), &pk());
+    assert!(matches!(
+        missing,
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+
+    let empty_path = IndexDefinition::new("bad").with_key_path::<&str>(&[]);
+    assert!(matches!(empty_path, Err(EvaluateError::InvalidPath(_))));
+}
+
+// =============================================================================
+// Key encoding
+// =============================================================================
+
+fn sample_values() -> Vec<FieldScalarValue> {
+    use FieldScalarValue as V;
+    vec![
+        V::Unit,
+        V::Bool(false),
+        V::Bool(true),
+        V::I8(-128),
+        V::I8(0),
+        V::I8(127),
+        V::I32(-70000),
+        V::I32(-1),
+        V::I32(0),
+        V::I32(65536),
+        V::I64(i64::MIN),
+        V::I64(i64::MAX),
+        V::I128(-1),
+        V::I128(1),
+        V::U16(1),
+        V::U16(256),
+        V::U64(0),
+        V::U64(u64::MAX),
+        V::F32(-1.5),
+        V::F32(0.0),
+        V::F64(f64::NEG_INFINITY),
+        V::F64(-2.0),
+        V::F64(-0.0),
+        V::F64(0.0),
+        V::F64(1e-10),
+        V::F64(3.5),
+        V::F64(f64::NAN),
+        V::Char('a'),
+        V::Char('é'),
+        V::String(String::new()),
+        V::String("a".to_string()),
+        V::String("a\0".to_string()),
+        V::String("a\0b".to_string()),
+        V::String("ab".to_string()),
+        V::String("b".to_string()),
+        V::Bytes(vec![]),
+        V::Bytes(vec![0]),
+        V::Bytes(vec![0, 0]),
+        V::Bytes(vec![1]),
+        V::Option(None),
+        V::Option(Some(Box::new(V::I32(-5)))),
+        V::Option(Some(Box::new(V::I32(5)))),
+    ]
+}
+
+#[test]
+fn test_encoding_preserves_total_order() {
+    let values = sample_values();
+    for a in &values {
+        for b in &values {
+            let encoded =
+                encode_key(std::slice::from_ref(a)).cmp(&encode_key(std::slice::from_ref(b)));
+            assert_eq!(encoded, a.total_cmp(b), "{:?} vs {:?}", a, b);
+        }
+    }
+}
+
+#[test]
+fn test_encoding_tuples_compare_element_wise() {
+    let a = encode_key(&[string("a"), FieldScalarValue::U8(2)]);
+    let b = encode_key(&[string("a\0"), FieldScalarValue::U8(1)]);
+    let c = encode_key(&[string("ab"), FieldScalarValue::U8(0)]);
+    assert_eq!(a.cmp(&b), Ordering::Less);
+    assert_eq!(b.cmp(&c), Ordering::Less);
+}
+
+// =============================================================================
+// Diff
+// =============================================================================
+
+fn by_tag() -> IndexDefinition {
+    IndexDefinition::new("by_tag")
+        .with_fan_out_path(&["tags"])
+        .unwrap()
+}
+
+#[test]
+fn test_diff_fan_out_changes() {
+    let index = by_tag();
+    let diff = index
+        .diff(
+            &article(&["a", "b"], &[]),
+            &article(&["b", "c"], &[]),
+            &pk(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        diff.removed,
+        index.entries(&article(&["a"], &[]), &pk()).unwrap()
+    );
+    assert_eq!(
+        diff.added,
+        index.entries(&article(&["c"], &[]), &pk()).unwrap()
+    );
+}
+
+#[test]
+fn test_diff_unchanged_is_empty() {
+    let index = by_tag();
+    let record = article(&["a"], &[]);
+    assert!(index.diff(&record, &record, &pk()).unwrap().is_empty());
+}
+
+#[test]
+fn test_diff_list_multiset() {
+    let index = by_tag();
+    // Dropping one of two duplicates keeps the entry.
+    let diff = index
+        .diff(
+            &article(&["a", "a", "b"], &[]),
+            &article(&["a", "b"], &[]),
+            &pk(),
+        )
+        .unwrap();
+    assert!(diff.is_empty());
+
+    // Reordering changes nothing either.
+    let diff = index
+        .diff(
+            &article(&["a", "b"], &[]),
+            &article(&["b", "a"], &[]),
+            &pk(),
+        )
+        .unwrap();
+    assert!(diff.is_empty());
+
+    // Dropping the last occurrence removes the entry.
+    let diff = index
+        .diff(
+            &article(&["a", "a", "b"], &[]),
+            &article(&["b"], &[]),
+            &pk(),
+        )
+        .unwrap();
+    assert_eq!(
+        diff.removed,
+        index.entries(&article(&["a"], &[]), &pk()).unwrap()
+    );
+    assert!(diff.added.is_empty());
+}
+
+#[test]
+fn test_diff_value_change_rewrites_without_delete() {
+    let index = IndexDefinition::new("by_author")
+        .with_key_path(&["author"])
+        .unwrap()
+        .with_value_path(&["stats", "views"])
+        .unwrap();
+
+    let old = article(&[], &[]);
+    let mut new = article(&[], &[]);
+    new.stats.views = 101;
+
+    let diff = index.diff(&old, &new, &pk()).unwrap();
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.added, index.entries(&new, &pk()).unwrap());
+}
+
+#[test]
+fn test_diff_scalar_key_change() {
+    let index = IndexDefinition::new("by_author")
+        .with_key_path(&["author"])
+        .unwrap();
+
+    let old = article(&[], &[]);
+    let mut new = article(&[], &[]);
+    new.author = "bob".to_string();
+
+    let diff = index.diff(&old, &new, &pk()).unwrap();
+    assert_eq!(diff.removed, index.entries(&old, &pk()).unwrap());
+    assert_eq!(diff.added, index.entries(&new, &pk()).unwrap());
+}
+
+#[test]
+fn test_diff_propagates_errors() {
+    let index = IndexDefinition::new("by_missing")
+        .with_key_path(&["missing"])
+        .unwrap();
+    let record = article(&[], &[]);
+    assert!(matches!(
+        index.diff(&record, &record, &pk()),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+}
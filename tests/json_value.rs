@@ -124,7 +124,8 @@ fn test_extract_json_value_array_unsupported() {
         matches!(
             result,
             Err(EvaluateError::UnsupportedType {
-                type_name: "sequence"
+                type_name: "sequence",
+                ..
             })
         ),
         "Expected UnsupportedType for JSON array, got {:?}",
@@ -145,7 +146,10 @@ fn test_extract_json_value_object_unsupported() {
     assert!(
         matches!(
             result,
-            Err(EvaluateError::UnsupportedType { type_name: "map" })
+            Err(EvaluateError::UnsupportedType {
+                type_name: "map",
+                ..
+            })
         ),
         "Expected UnsupportedType for JSON object, got {:?}",
         result
@@ -219,7 +223,10 @@ fn test_nested_extract_json_object_value_is_object() {
     assert!(
         matches!(
             result,
-            Err(EvaluateError::UnsupportedType { type_name: "map" })
+            Err(EvaluateError::UnsupportedType {
+                type_name: "map",
+                ..
+            })
         ),
         "Expected UnsupportedType for nested JSON object, got {:?}",
         result
@@ -174,7 +174,17 @@ fn test_extract_list_of_structs_fails() {
 
     assert!(matches!(
         result,
-        Err(EvaluateError::UnsupportedType { type_name }) if type_name == "struct"
+        Err(EvaluateError::UnsupportedType { type_name, .. }) if type_name == "struct"
+    ));
+    assert!(matches!(
+        result,
+        Err(EvaluateError::UnsupportedType {
+            type_name: "struct",
+            rust_type: Some("Item"),
+            expected: "scalar",
+            path,
+            ..
+        }) if path == ["items"]
     ));
 }
 
@@ -189,7 +199,7 @@ fn test_extract_nested_list_fails() {
 
     assert!(matches!(
         result,
-        Err(EvaluateError::UnsupportedType { type_name }) if type_name == "nested sequence"
+        Err(EvaluateError::UnsupportedType { type_name, .. }) if type_name == "nested sequence"
     ));
 }
 
@@ -0,0 +1,302 @@
+use serde::Serialize;
+use serde_evaluate::{
+    CompositeFieldExtractor, DuplicateKeyPolicy, EvaluateError, FieldExtractor, FieldScalarRef,
+    FieldScalarValue, KeyMatching, NestedFieldExtractor, NotFoundReason, SkippedFieldPolicy,
+};
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Account {
+    user_id: u64,
+    billing_address: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_name: Option<String>,
+    labels: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Address {
+    postal_code: String,
+    country: String,
+}
+
+fn account() -> Account {
+    Account {
+        user_id: 7,
+        billing_address: Address {
+            postal_code: "69001".to_string(),
+            country: "FR".to_string(),
+        },
+        display_name: None,
+        labels: BTreeMap::from([("Team-Name".to_string(), "core".to_string())]),
+    }
+}
+
+fn nested(path: &[&str]) -> NestedFieldExtractor {
+    NestedFieldExtractor::new_from_path(path).unwrap()
+}
+
+fn string(s: &str) -> FieldScalarValue {
+    FieldScalarValue::String(s.to_string())
+}
+
+// =============================================================================
+// Matching modes
+// =============================================================================
+
+#[test]
+fn exact_is_the_default() {
+    let account = account();
+    assert_eq!(
+        FieldExtractor::new("user_id").evaluate(&account),
+        Err(EvaluateError::FieldNotFound {
+            field_name: "user_id".to_string()
+        })
+    );
+    assert_eq!(
+        nested(&["userId"]).evaluate(&account),
+        Ok(FieldScalarValue::U64(7))
+    );
+}
+
+#[test]
+fn ascii_case_insensitive_ignores_case_only() {
+    let account = account();
+    let extractor = nested(&["USERID"]).with_key_matching(KeyMatching::AsciiCaseInsensitive);
+    assert_eq!(extractor.evaluate(&account), Ok(FieldScalarValue::U64(7)));
+
+    let extractor = nested(&["user_id"]).with_key_matching(KeyMatching::AsciiCaseInsensitive);
+    assert!(matches!(
+        extractor.evaluate(&account),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+}
+
+#[test]
+fn naming_convention_matches_across_cases() {
+    let account = account();
+    for path in [&["user_id"][..], &["UserId"], &["user-id"], &["USER_ID"]] {
+        let extractor = nested(path).with_key_matching(KeyMatching::NamingConvention);
+        assert_eq!(extractor.evaluate(&account), Ok(FieldScalarValue::U64(7)));
+    }
+
+    let extractor = nested(&["billing_address", "postal_code"])
+        .with_key_matching(KeyMatching::NamingConvention);
+    assert_eq!(extractor.evaluate(&account), Ok(string("69001")));
+    assert_eq!(
+        extractor.evaluate_with(&account, |v| v == FieldScalarRef::String("69001")),
+        Ok(true)
+    );
+}
+
+#[test]
+fn matching_applies_to_map_keys() {
+    let extractor =
+        nested(&["labels", "team_name"]).with_key_matching(KeyMatching::NamingConvention);
+    assert_eq!(extractor.evaluate(&account()), Ok(string("core")));
+}
+
+#[test]
+fn errors_report_the_configured_path() {
+    let extractor =
+        nested(&["billing_address", "zip"]).with_key_matching(KeyMatching::NamingConvention);
+    assert!(matches!(
+        extractor.evaluate(&account()),
+        Err(EvaluateError::NestedFieldNotFound {
+            path,
+            failed_at_index: 1,
+            reason: NotFoundReason::MissingField,
+            ..
+        }) if path == ["billing_address", "zip"]
+    ));
+}
+
+#[test]
+fn skipped_fields_are_matched_too() {
+    let extractor = FieldExtractor::new("display_name")
+        .with_key_matching(KeyMatching::NamingConvention)
+        .with_skipped_fields(SkippedFieldPolicy::AsNone);
+    assert_eq!(
+        extractor.evaluate(&account()),
+        Ok(FieldScalarValue::Option(None))
+    );
+}
+
+#[test]
+fn matching_applies_to_deserialized_input() {
+    let json = r#"{"userId": 7, "billingAddress": {"postal-code": "69001"}}"#;
+
+    let extractor = nested(&["billing_address", "postal_code"])
+        .with_key_matching(KeyMatching::NamingConvention);
+    let mut de = serde_json::Deserializer::from_str(json);
+    assert_eq!(extractor.evaluate_from(&mut de), Ok(string("69001")));
+
+    let extractor =
+        FieldExtractor::new("USERID").with_key_matching(KeyMatching::AsciiCaseInsensitive);
+    let mut de = serde_json::Deserializer::from_str(json);
+    assert_eq!(
+        extractor.evaluate_from(&mut de),
+        Ok(FieldScalarValue::U64(7))
+    );
+}
+
+#[test]
+fn loose_matching_can_find_duplicates() {
+    let labels = BTreeMap::from([
+        ("team_name".to_string(), "a".to_string()),
+        ("teamName".to_string(), "b".to_string()),
+    ]);
+    let extractor =
+        FieldExtractor::new("team-name").with_key_matching(KeyMatching::NamingConvention);
+    assert_eq!(extractor.evaluate(&labels), Ok(string("b")));
+
+    let extractor = extractor.with_duplicate_keys(DuplicateKeyPolicy::Error);
+    assert!(matches!(
+        extractor.evaluate(&labels),
+        Err(EvaluateError::DuplicateKey {
+            first_entry: 0,
+            second_entry: 1,
+            ..
+        })
+    ));
+}
+
+// =============================================================================
+// Aliases
+// =============================================================================
+
+#[test]
+fn aliases_match_alternative_names() {
+    let account = account();
+    let extractor = nested(&["billing", "zip"])
+        .with_aliases(0, &["billingAddress"])
+        .unwrap()
+        .with_aliases(1, &["zipCode", "postal-code"])
+        .unwrap();
+    assert_eq!(extractor.evaluate(&account), Ok(string("69001")));
+
+    let extractor = FieldExtractor::new("id")
+        .with_aliases(&["user_id"])
+        .unwrap()
+        .with_key_matching(KeyMatching::NamingConvention);
+    assert_eq!(extractor.evaluate(&account), Ok(FieldScalarValue::U64(7)));
+}
+
+#[test]
+fn aliases_replace_previous_aliases() {
+    let extractor = nested(&["id"])
+        .with_aliases(0, &["userId"])
+        .unwrap()
+        .with_aliases(0, &["accountId"])
+        .unwrap();
+    assert!(matches!(
+        extractor.evaluate(&account()),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+}
+
+#[test]
+fn invalid_aliases_are_rejected() {
+    assert!(matches!(
+        nested(&["id"]).with_aliases(1, &["userId"]),
+        Err(EvaluateError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        nested(&["id"]).with_aliases(0, &[""]),
+        Err(EvaluateError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        FieldExtractor::new("id").with_aliases(&[""]),
+        Err(EvaluateError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn aliases_apply_to_deserialized_input() {
+    let json = r#"{"uid": 7}"#;
+    let extractor = nested(&["user_id"]).with_aliases(0, &["uid"]).unwrap();
+    let mut de = serde_json::Deserializer::from_str(json);
+    assert_eq!(
+        extractor.evaluate_from(&mut de),
+        Ok(FieldScalarValue::U64(7))
+    );
+}
+
+// =============================================================================
+// Composite and compiled extractors
+// =============================================================================
+
+#[test]
+fn composite_and_compiled_apply_matching_to_all_paths() {
+    let account = account();
+    let expected = vec![FieldScalarValue::U64(7), string("FR")];
+
+    let composite = CompositeFieldExtractor::new_from_paths(&[
+        &["user_id"][..],
+        &["billing_address", "country"],
+    ])
+    .unwrap()
+    .with_key_matching(KeyMatching::NamingConvention);
+    assert_eq!(composite.evaluate(&account), Ok(expected.clone()));
+    assert_eq!(composite.compile().evaluate(&account), Ok(expected.clone()));
+
+    let compiled = CompositeFieldExtractor::new_from_paths(&[
+        &["user_id"][..],
+        &["billing_address", "country"],
+    ])
+    .unwrap()
+    .compile()
+    .with_key_matching(KeyMatching::NamingConvention);
+    assert_eq!(compiled.evaluate(&account), Ok(expected));
+}
+
+#[test]
+fn compiled_follows_every_segment_a_key_matches() {
+    let account = account();
+    let composite = CompositeFieldExtractor::from_extractors(vec![
+        nested(&["userId"]),
+        nested(&["user_id"]).with_key_matching(KeyMatching::NamingConvention),
+        nested(&["id"]).with_aliases(0, &["userId"]).unwrap(),
+        nested(&["billingAddress", "country"]),
+        nested(&["billing_address", "country"]).with_key_matching(KeyMatching::NamingConvention),
+        nested(&["labels", "TEAM-NAME"]).with_key_matching(KeyMatching::AsciiCaseInsensitive),
+        nested(&["labels", "team_name"]).with_key_matching(KeyMatching::NamingConvention),
+        nested(&["labels", "Team-Name"]),
+    ])
+    .unwrap();
+
+    let expected = vec![
+        FieldScalarValue::U64(7),
+        FieldScalarValue::U64(7),
+        FieldScalarValue::U64(7),
+        string("FR"),
+        string("FR"),
+        string("core"),
+        string("core"),
+        string("core"),
+    ];
+    assert_eq!(composite.evaluate(&account), Ok(expected.clone()));
+    assert_eq!(composite.compile().evaluate(&account), Ok(expected));
+}
+
+#[test]
+fn compiled_keeps_exact_paths_exact() {
+    let composite = CompositeFieldExtractor::from_extractors(vec![
+        nested(&["user_id"]).with_key_matching(KeyMatching::NamingConvention),
+        nested(&["user_id"]),
+    ])
+    .unwrap();
+    let results = composite.compile().evaluate_all(&account()).unwrap();
+    assert_eq!(results[0], Ok(FieldScalarValue::U64(7)));
+    assert!(matches!(
+        &results[1],
+        Err(EvaluateError::NestedFieldNotFound {
+            path,
+            failed_at_index: 0,
+            reason: NotFoundReason::MissingField,
+            ..
+        }) if path == &["user_id"]
+    ));
+}
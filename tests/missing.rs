@@ -0,0 +1,290 @@
+use serde::Serialize;
+use serde_evaluate::{
+    CompositeFieldExtractor, EvaluateError, FieldExtractor, FieldScalarRef, FieldScalarValue,
+    MissingPolicy, NestedFieldExtractor, NotFoundReason, SkippedFieldPolicy,
+};
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Order {
+    id: u64,
+    shipping: Option<Shipping>,
+    labels: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    coupon: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Shipping {
+    carrier: String,
+    tags: Vec<String>,
+}
+
+fn bare_order() -> Order {
+    Order {
+        id: 7,
+        shipping: None,
+        labels: BTreeMap::new(),
+        coupon: None,
+    }
+}
+
+fn nested(path: &[&str]) -> NestedFieldExtractor {
+    NestedFieldExtractor::new_from_path(path).unwrap()
+}
+
+fn string(s: &str) -> FieldScalarValue {
+    FieldScalarValue::String(s.to_string())
+}
+
+// =============================================================================
+// Error policy
+// =============================================================================
+
+#[test]
+fn error_is_the_default() {
+    let order = bare_order();
+    assert_eq!(
+        FieldExtractor::new("email").evaluate(&order),
+        Err(EvaluateError::FieldNotFound {
+            field_name: "email".to_string()
+        })
+    );
+    assert!(matches!(
+        nested(&["shipping", "carrier"])
+            .with_missing_paths(MissingPolicy::Error)
+            .evaluate(&order),
+        Err(EvaluateError::NestedFieldNotFound {
+            path,
+            failed_at_index: 1,
+            reason: NotFoundReason::Null,
+            ..
+        }) if path == ["shipping", "carrier"]
+    ));
+}
+
+// =============================================================================
+// ReturnNone and Default policies
+// =============================================================================
+
+#[test]
+fn return_none_replaces_missing_field() {
+    let order = bare_order();
+    let extractor = FieldExtractor::new("email").with_missing_paths(MissingPolicy::ReturnNone);
+    assert_eq!(
+        extractor.evaluate(&order),
+        Ok(FieldScalarValue::Option(None))
+    );
+    assert_eq!(
+        extractor.evaluate_with(&order, |v| v == FieldScalarRef::Option(None)),
+        Ok(true)
+    );
+}
+
+#[test]
+fn default_replaces_missing_nested_path() {
+    let order = bare_order();
+    let policy = MissingPolicy::Default(string("unknown"));
+
+    let extractor = nested(&["shipping", "carrier"]).with_missing_paths(policy.clone());
+    assert_eq!(extractor.evaluate(&order), Ok(string("unknown")));
+    assert_eq!(
+        extractor.evaluate_with(&order, |v| v == FieldScalarRef::String("unknown")),
+        Ok(true)
+    );
+
+    let extractor = nested(&["labels", "team"]).with_missing_paths(policy);
+    assert_eq!(extractor.evaluate(&order), Ok(string("unknown")));
+}
+
+#[test]
+fn default_keeps_present_values() {
+    let order = Order {
+        shipping: Some(Shipping {
+            carrier: "ups".to_string(),
+            tags: vec![],
+        }),
+        ..bare_order()
+    };
+    let extractor = nested(&["shipping", "carrier"])
+        .with_missing_paths(MissingPolicy::Default(string("unknown")));
+    assert_eq!(extractor.evaluate(&order), Ok(string("ups")));
+}
+
+#[test]
+fn policies_apply_to_deserialized_input() {
+    let json = r#"{"id": 7, "shipping": null}"#;
+
+    let extractor = nested(&["shipping", "carrier"])
+        .with_missing_paths(MissingPolicy::Default(string("unknown")));
+    let mut de = serde_json::Deserializer::from_str(json);
+    assert_eq!(extractor.evaluate_from(&mut de), Ok(string("unknown")));
+
+    let extractor = FieldExtractor::new("email").with_missing_paths(MissingPolicy::ReturnNone);
+    let mut de = serde_json::Deserializer::from_str(json);
+    assert_eq!(
+        extractor.evaluate_from(&mut de),
+        Ok(FieldScalarValue::Option(None))
+    );
+}
+
+#[test]
+fn null_intermediates_report_null() {
+    let null_at = |result: Result<FieldScalarValue, EvaluateError>| {
+        matches!(
+            result,
+            Err(EvaluateError::NestedFieldNotFound {
+                failed_at_index: 1,
+                reason: NotFoundReason::Null,
+                ..
+            })
+        )
+    };
+    let order = bare_order();
+    let extractor = nested(&["shipping", "carrier"]);
+    assert!(null_at(extractor.evaluate(&order)));
+
+    let compiled = CompositeFieldExtractor::from_extractors(vec![extractor.clone()])
+        .unwrap()
+        .compile();
+    assert!(null_at(
+        compiled.evaluate(&order).map(|mut values| values.remove(0))
+    ));
+
+    let json = serde_json::json!({"id": 7, "shipping": null});
+    assert!(null_at(extractor.evaluate(&json)));
+    let mut de = serde_json::Deserializer::from_str(r#"{"id": 7, "shipping": null}"#);
+    assert!(null_at(extractor.evaluate_from(&mut de)));
+}
+
+#[test]
+fn skipped_field_policy_takes_precedence() {
+    let extractor = FieldExtractor::new("coupon")
+        .with_skipped_fields(SkippedFieldPolicy::AsNone)
+        .with_missing_paths(MissingPolicy::Default(string("none")));
+    assert_eq!(
+        extractor.evaluate(&bare_order()),
+        Ok(FieldScalarValue::Option(None))
+    );
+
+    let extractor =
+        FieldExtractor::new("coupon").with_missing_paths(MissingPolicy::Default(string("none")));
+    assert_eq!(extractor.evaluate(&bare_order()), Ok(string("none")));
+}
+
+#[test]
+fn other_errors_are_not_recovered() {
+    let order = Order {
+        shipping: Some(Shipping {
+            carrier: "ups".to_string(),
+            tags: vec![],
+        }),
+        ..bare_order()
+    };
+    let extractor = nested(&["shipping", "tags"]).with_missing_paths(MissingPolicy::ReturnNone);
+    assert!(matches!(
+        extractor.evaluate(&order),
+        Err(EvaluateError::UnsupportedType { .. })
+    ));
+}
+
+#[test]
+fn paths_through_non_containers_are_not_recovered() {
+    let order = Order {
+        shipping: Some(Shipping {
+            carrier: "ups".to_string(),
+            tags: vec![],
+        }),
+        ..bare_order()
+    };
+    let not_traversable = |result: Result<FieldScalarValue, EvaluateError>| match result {
+        Err(EvaluateError::NestedFieldNotFound {
+            failed_at_index,
+            reason: NotFoundReason::NotTraversable { type_name },
+            ..
+        }) => (failed_at_index, type_name),
+        other => panic!("Expected NotTraversable, got {:?}", other),
+    };
+
+    for policy in [
+        MissingPolicy::ReturnNone,
+        MissingPolicy::Default(FieldScalarValue::U64(0)),
+    ] {
+        let extractor = nested(&["id", "x"]).with_missing_paths(policy.clone());
+        assert_eq!(not_traversable(extractor.evaluate(&order)), (1, "scalar"));
+
+        let mut de = serde_json::Deserializer::from_str(r#"{"id": 7}"#);
+        assert_eq!(
+            not_traversable(extractor.evaluate_from(&mut de)),
+            (1, "scalar")
+        );
+
+        let extractor = nested(&["shipping", "tags", "x"]).with_missing_paths(policy);
+        assert_eq!(not_traversable(extractor.evaluate(&order)), (2, "sequence"));
+    }
+}
+
+// =============================================================================
+// Composite and compiled extractors
+// =============================================================================
+
+#[test]
+fn composite_applies_policy_per_path() {
+    let order = bare_order();
+    let composite = CompositeFieldExtractor::from_extractors(vec![
+        nested(&["id"]),
+        nested(&["shipping", "carrier"]).with_missing_paths(MissingPolicy::Default(string("-"))),
+        nested(&["labels", "team"]).with_missing_paths(MissingPolicy::ReturnNone),
+    ])
+    .unwrap();
+
+    let expected = vec![
+        FieldScalarValue::U64(7),
+        string("-"),
+        FieldScalarValue::Option(None),
+    ];
+    assert_eq!(composite.evaluate(&order), Ok(expected.clone()));
+    assert_eq!(composite.compile().evaluate(&order), Ok(expected));
+}
+
+#[test]
+fn composite_policy_applies_to_all_paths() {
+    let order = bare_order();
+    let composite = CompositeFieldExtractor::new(&["id", "email", "coupon"])
+        .unwrap()
+        .with_missing_paths(MissingPolicy::ReturnNone);
+
+    let expected = vec![
+        FieldScalarValue::U64(7),
+        FieldScalarValue::Option(None),
+        FieldScalarValue::Option(None),
+    ];
+    assert_eq!(composite.evaluate(&order), Ok(expected.clone()));
+
+    let compiled = CompositeFieldExtractor::new(&["id", "email", "coupon"])
+        .unwrap()
+        .compile()
+        .with_missing_paths(MissingPolicy::ReturnNone);
+    assert_eq!(compiled.evaluate(&order), Ok(expected));
+}
+
+#[test]
+fn compiled_recovers_unreached_subtree() {
+    let compiled =
+        CompositeFieldExtractor::new_from_paths(&[&["shipping", "carrier"][..], &["id"]])
+            .unwrap()
+            .compile()
+            .with_missing_paths(MissingPolicy::Default(string("-")));
+    assert_eq!(
+        compiled.evaluate(&bare_order()),
+        Ok(vec![string("-"), FieldScalarValue::U64(7)])
+    );
+}
+
+#[test]
+fn empty_extractor_list_is_rejected() {
+    assert!(matches!(
+        CompositeFieldExtractor::from_extractors(vec![]),
+        Err(EvaluateError::InvalidPath(_))
+    ));
+}
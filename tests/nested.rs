@@ -1,6 +1,8 @@
 use serde::Serialize;
 use serde_evaluate::{
-    error::EvaluateError, extractor::NestedFieldExtractor, value::FieldScalarValue,
+    error::{EvaluateError, NotFoundReason},
+    extractor::NestedFieldExtractor,
+    value::FieldScalarValue,
 };
 use std::collections::BTreeMap;
 
@@ -186,7 +188,7 @@ fn test_extract_deeply_nested_field_comprehensive() {
     let result_non_scalar = extractor_non_scalar.evaluate(&data);
     assert!(matches!(
         result_non_scalar,
-        Err(EvaluateError::UnsupportedType { type_name: _ })
+        Err(EvaluateError::UnsupportedType { .. })
     ));
 
     // Test path parsing errors handled by new_from_path
@@ -307,3 +309,208 @@ fn test_extract_nested_map_field() {
         result_simple_missing
     );
 }
+
+#[test]
+fn test_nested_not_found_reports_failing_segment() {
+    let data = MapTestData {
+        id: "map_test_data".to_string(),
+        data_map: BTreeMap::from([(
+            "entry1".to_string(),
+            MapValueStruct {
+                value: 1,
+                description: None,
+            },
+        )]),
+        simple_map: BTreeMap::from([("simple1".to_string(), 100)]),
+    };
+
+    let not_found = |path: &[&str]| match NestedFieldExtractor::new_from_path(path)
+        .unwrap()
+        .evaluate(&data)
+    {
+        Err(EvaluateError::NestedFieldNotFound {
+            failed_at_index,
+            reason,
+            ..
+        }) => (failed_at_index, reason),
+        other => panic!(
+            "Expected NestedFieldNotFound for {:?}, got {:?}",
+            path, other
+        ),
+    };
+
+    // Missing top-level field
+    assert_eq!(
+        not_found(&["missing", "value"]),
+        (0, NotFoundReason::MissingField)
+    );
+    // Missing map key, at the end of the path and in the middle of it
+    assert_eq!(
+        not_found(&["simple_map", "missing"]),
+        (1, NotFoundReason::MissingKey)
+    );
+    assert_eq!(
+        not_found(&["data_map", "missing", "value"]),
+        (1, NotFoundReason::MissingKey)
+    );
+    // Missing field of a struct inside a map
+    assert_eq!(
+        not_found(&["data_map", "entry1", "bad_field"]),
+        (2, NotFoundReason::MissingField)
+    );
+    // Traversing into a scalar, directly and through a map value
+    assert_eq!(
+        not_found(&["id", "len"]),
+        (
+            1,
+            NotFoundReason::NotTraversable {
+                type_name: "scalar"
+            }
+        )
+    );
+    assert_eq!(
+        not_found(&["simple_map", "simple1", "x"]),
+        (
+            2,
+            NotFoundReason::NotTraversable {
+                type_name: "scalar"
+            }
+        )
+    );
+    // Traversing into a `None`
+    assert_eq!(
+        not_found(&["data_map", "entry1", "description", "x"]),
+        (3, NotFoundReason::Null)
+    );
+}
+
+#[test]
+fn test_nested_not_found_display() {
+    let extractor = NestedFieldExtractor::new_from_path(&["data_map", "missing", "value"]).unwrap();
+    let data = MapTestData {
+        id: "map_test_data".to_string(),
+        data_map: BTreeMap::new(),
+        simple_map: BTreeMap::new(),
+    };
+    assert_eq!(
+        extractor.evaluate(&data).unwrap_err().to_string(),
+        "Nested field path 'data_map.missing.value' not found \
+         (failed at segment 1: 'missing', no such map key)"
+    );
+}
+
+#[derive(Serialize)]
+struct Ids(Vec<u32>);
+
+#[derive(Serialize)]
+#[allow(dead_code)]
+enum Shape {
+    Circle { radius: f64 },
+}
+
+#[derive(Serialize)]
+struct ContextRecord {
+    middle: Middle,
+    ids: Ids,
+    shape: Shape,
+    by_number: BTreeMap<u32, String>,
+    by_pair: BTreeMap<(u8, u8), String>,
+}
+
+fn context_record() -> ContextRecord {
+    ContextRecord {
+        middle: Middle {
+            inner: Inner {
+                value: 1,
+                another_value: None,
+                nested_struct: InnerMost { deep_value: true },
+            },
+            primitive: 2,
+        },
+        ids: Ids(vec![1, 2]),
+        shape: Shape::Circle { radius: 1.0 },
+        by_number: BTreeMap::from([(1, "one".to_string())]),
+        by_pair: BTreeMap::from([((1, 2), "one-two".to_string())]),
+    }
+}
+
+#[test]
+fn test_unsupported_errors_carry_context() {
+    let record = context_record();
+    let evaluate = |path: &[&str]| {
+        NestedFieldExtractor::new_from_path(path)
+            .unwrap()
+            .evaluate(&record)
+            .unwrap_err()
+    };
+
+    // The struct's Rust name and the path to it
+    assert!(matches!(
+        evaluate(&["middle", "inner", "nested_struct"]),
+        EvaluateError::UnsupportedType {
+            type_name: "struct",
+            rust_type: Some("InnerMost"),
+            expected: "scalar",
+            path,
+            ..
+        } if path == ["middle", "inner", "nested_struct"]
+    ));
+
+    // A sequence is named after the newtype wrapping it
+    assert!(matches!(
+        evaluate(&["ids"]),
+        EvaluateError::UnsupportedType {
+            type_name: "sequence",
+            rust_type: Some("Ids"),
+            expected: "scalar",
+            path,
+            ..
+        } if path == ["ids"]
+    ));
+
+    // A variant in the middle of the path
+    assert!(matches!(
+        evaluate(&["shape", "radius"]),
+        EvaluateError::UnsupportedVariant {
+            variant_type: "struct",
+            rust_type: Some("Shape"),
+            variant: Some("Circle"),
+            expected: "struct or map",
+            path,
+            ..
+        } if path == ["shape"]
+    ));
+
+    // A map whose keys have no text form
+    assert!(matches!(
+        evaluate(&["by_pair", "1"]),
+        EvaluateError::UnsupportedType {
+            type_name: "tuple",
+            rust_type: None,
+            expected: "string map key",
+            path,
+            ..
+        } if path == ["by_pair"]
+    ));
+}
+
+#[test]
+fn test_unsupported_errors_display_context() {
+    let record = context_record();
+    let message = |path: &[&str]| {
+        NestedFieldExtractor::new_from_path(path)
+            .unwrap()
+            .evaluate(&record)
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_eq!(
+        message(&["middle", "inner"]),
+        "Unsupported type at 'middle.inner': expected scalar, found struct `Inner`"
+    );
+    assert_eq!(
+        message(&["shape"]),
+        "Unsupported variant at 'shape': expected scalar, found struct variant `Shape::Circle`"
+    );
+}
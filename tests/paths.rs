@@ -0,0 +1,309 @@
+// Tests for leaf enumeration with `paths`
+
+use serde::Serialize;
+use serde_evaluate::extractor::NestedFieldExtractor;
+use serde_evaluate::path::{join_path, paths, PathSegment};
+use serde_evaluate::value::FieldScalarValue;
+use std::collections::BTreeMap;
+
+fn key(k: &str) -> PathSegment {
+    PathSegment::Key(k.to_string())
+}
+
+fn flatten<T: Serialize>(record: &T) -> Vec<(String, FieldScalarValue)> {
+    paths(record)
+        .unwrap()
+        .into_iter()
+        .map(|(path, value)| (join_path(&path), value))
+        .collect()
+}
+
+fn string(s: &str) -> FieldScalarValue {
+    FieldScalarValue::String(s.to_string())
+}
+
+// =============================================================================
+// Structs and maps
+// =============================================================================
+
+#[derive(Serialize)]
+struct Profile {
+    id: u64,
+    name: String,
+    settings: Settings,
+    attrs: BTreeMap<String, i32>,
+}
+
+#[derive(Serialize)]
+struct Settings {
+    level: u8,
+    theme: Option<String>,
+}
+
+fn sample_profile() -> Profile {
+    let mut attrs = BTreeMap::new();
+    attrs.insert("a".to_string(), 1);
+    attrs.insert("b".to_string(), 2);
+
+    Profile {
+        id: 7,
+        name: "alice".to_string(),
+        settings: Settings {
+            level: 3,
+            theme: None,
+        },
+        attrs,
+    }
+}
+
+#[test]
+fn test_paths_nested_struct_and_map() {
+    let leaves = paths(&sample_profile()).unwrap();
+    assert_eq!(
+        leaves,
+        vec![
+            (vec![key("id")], FieldScalarValue::U64(7)),
+            (vec![key("name")], string("alice")),
+            (vec![key("settings"), key("level")], FieldScalarValue::U8(3)),
+            (
+                vec![key("settings"), key("theme")],
+                FieldScalarValue::Option(None)
+            ),
+            (vec![key("attrs"), key("a")], FieldScalarValue::I32(1)),
+            (vec![key("attrs"), key("b")], FieldScalarValue::I32(2)),
+        ]
+    );
+}
+
+#[test]
+fn test_paths_agree_with_nested_extractor() {
+    let profile = sample_profile();
+    for (path, value) in paths(&profile).unwrap() {
+        let segments: Vec<&str> = path.iter().map(|s| s.as_key().unwrap()).collect();
+        let extractor = NestedFieldExtractor::new_from_path(&segments).unwrap();
+        assert_eq!(extractor.evaluate(&profile), Ok(value), "path {:?}", path);
+    }
+}
+
+#[test]
+fn test_paths_integer_map_keys() {
+    let mut by_id = BTreeMap::new();
+    by_id.insert(10u32, "ten".to_string());
+    by_id.insert(20u32, "twenty".to_string());
+
+    assert_eq!(
+        flatten(&by_id),
+        vec![
+            ("10".to_string(), string("ten")),
+            ("20".to_string(), string("twenty")),
+        ]
+    );
+}
+
+#[test]
+fn test_paths_with_scalar_map_keys_can_be_extracted() {
+    #[derive(Serialize)]
+    struct Record {
+        by_id: BTreeMap<i64, String>,
+        by_initial: BTreeMap<char, u8>,
+        by_flag: BTreeMap<bool, u8>,
+        tags: Vec<String>,
+    }
+
+    let record = Record {
+        by_id: BTreeMap::from([(-1, "minus one".to_string()), (20, "twenty".to_string())]),
+        by_initial: BTreeMap::from([('a', 1)]),
+        by_flag: BTreeMap::from([(true, 1)]),
+        tags: vec!["x".to_string()],
+    };
+
+    let mut extracted = 0;
+    for (path, value) in paths(&record).unwrap() {
+        // Sequence positions cannot be followed by the extractors.
+        let Some(segments) = path
+            .iter()
+            .map(PathSegment::as_key)
+            .collect::<Option<Vec<_>>>()
+        else {
+            assert_eq!(path, vec![key("tags"), PathSegment::Index(0)]);
+            continue;
+        };
+        let extractor = NestedFieldExtractor::new_from_path(&segments).unwrap();
+        assert_eq!(extractor.evaluate(&record), Ok(value), "path {:?}", path);
+        extracted += 1;
+    }
+    assert_eq!(extracted, 4);
+}
+
+// =============================================================================
+// Sequences and tuples
+// =============================================================================
+
+#[test]
+fn test_paths_sequences() {
+    #[derive(Serialize)]
+    struct Record {
+        tags: Vec<String>,
+        items: Vec<Item>,
+        pair: (u8, bool),
+        empty: Vec<u32>,
+    }
+
+    #[derive(Serialize)]
+    struct Item {
+        sku: String,
+    }
+
+    let record = Record {
+        tags: vec!["x".to_string(), "y".to_string()],
+        items: vec![
+            Item {
+                sku: "A1".to_string(),
+            },
+            Item {
+                sku: "B2".to_string(),
+            },
+        ],
+        pair: (1, true),
+        empty: vec![],
+    };
+
+    assert_eq!(
+        flatten(&record),
+        vec![
+            ("tags[0]".to_string(), string("x")),
+            ("tags[1]".to_string(), string("y")),
+            ("items[0].sku".to_string(), string("A1")),
+            ("items[1].sku".to_string(), string("B2")),
+            ("pair[0]".to_string(), FieldScalarValue::U8(1)),
+            ("pair[1]".to_string(), FieldScalarValue::Bool(true)),
+        ]
+    );
+}
+
+// =============================================================================
+// Options
+// =============================================================================
+
+#[test]
+fn test_paths_options() {
+    #[derive(Serialize)]
+    struct Record {
+        some: Option<i32>,
+        nested: Option<Option<i32>>,
+        inner: Option<Inner>,
+        list: Option<Vec<Option<u8>>>,
+    }
+
+    #[derive(Serialize)]
+    struct Inner {
+        flag: Option<bool>,
+    }
+
+    let record = Record {
+        some: Some(1),
+        nested: Some(None),
+        inner: Some(Inner { flag: Some(true) }),
+        list: Some(vec![Some(1), None]),
+    };
+
+    let some = |v| FieldScalarValue::Option(Some(Box::new(v)));
+    assert_eq!(
+        flatten(&record),
+        vec![
+            ("some".to_string(), some(FieldScalarValue::I32(1))),
+            ("nested".to_string(), some(FieldScalarValue::Option(None))),
+            // `Some` around a struct is looked through.
+            ("inner.flag".to_string(), some(FieldScalarValue::Bool(true))),
+            ("list[0]".to_string(), some(FieldScalarValue::U8(1))),
+            ("list[1]".to_string(), FieldScalarValue::Option(None)),
+        ]
+    );
+}
+
+// =============================================================================
+// Enums
+// =============================================================================
+
+#[test]
+fn test_paths_enum_variants() {
+    #[derive(Serialize)]
+    enum Event {
+        Ping,
+        Message(String),
+        Move(i32, i32),
+        Login { user: String },
+    }
+
+    let events = vec![
+        Event::Ping,
+        Event::Message("hi".to_string()),
+        Event::Move(1, -1),
+        Event::Login {
+            user: "bob".to_string(),
+        },
+    ];
+
+    assert_eq!(
+        flatten(&events),
+        vec![
+            ("[0]".to_string(), FieldScalarValue::Unit),
+            ("[1].Message".to_string(), string("hi")),
+            ("[2].Move[0]".to_string(), FieldScalarValue::I32(1)),
+            ("[2].Move[1]".to_string(), FieldScalarValue::I32(-1)),
+            ("[3].Login.user".to_string(), string("bob")),
+        ]
+    );
+}
+
+// =============================================================================
+// serde_json::Value
+// =============================================================================
+
+#[test]
+fn test_paths_json_value() {
+    let value = serde_json::json!({
+        "user": {"name": "alice", "roles": ["admin", "dev"]},
+        "active": true,
+        "score": null
+    });
+
+    assert_eq!(
+        flatten(&value),
+        vec![
+            ("active".to_string(), FieldScalarValue::Bool(true)),
+            ("score".to_string(), FieldScalarValue::Unit),
+            ("user.name".to_string(), string("alice")),
+            ("user.roles[0]".to_string(), string("admin")),
+            ("user.roles[1]".to_string(), string("dev")),
+        ]
+    );
+}
+
+#[test]
+fn test_paths_top_level_scalar() {
+    assert_eq!(
+        paths(&42u8).unwrap(),
+        vec![(vec![], FieldScalarValue::U8(42))]
+    );
+}
+
+// =============================================================================
+// join_path
+// =============================================================================
+
+#[test]
+fn test_join_path() {
+    assert_eq!(join_path(&[]), "");
+    assert_eq!(join_path(&[key("a")]), "a");
+    assert_eq!(
+        join_path(&[
+            key("a"),
+            PathSegment::Index(0),
+            PathSegment::Index(2),
+            key("b")
+        ]),
+        "a[0][2].b"
+    );
+    assert_eq!(join_path(&[PathSegment::Index(1), key("x")]), "[1].x");
+}
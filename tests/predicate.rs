@@ -0,0 +1,284 @@
+// Tests for Predicate expressions
+
+use serde::Serialize;
+use serde_evaluate::error::EvaluateError;
+use serde_evaluate::expr::Predicate;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Customer {
+    id: u64,
+    status: String,
+    score: f64,
+    level: i32,
+    initial: char,
+    active: bool,
+    tags: Vec<String>,
+    nickname: Option<String>,
+    deleted_at: Option<u64>,
+    address: Address,
+    labels: BTreeMap<String, String>,
+    extra: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct Address {
+    city: String,
+    zip: Option<String>,
+}
+
+fn sample_customer() -> Customer {
+    let mut labels = BTreeMap::new();
+    labels.insert("team name".to_string(), "storage".to_string());
+
+    Customer {
+        id: 42,
+        status: "active".to_string(),
+        score: 12.5,
+        level: -3,
+        initial: 'a',
+        active: true,
+        tags: vec!["vip".to_string(), "beta".to_string()],
+        nickname: Some("ace".to_string()),
+        deleted_at: None,
+        address: Address {
+            city: "Paris".to_string(),
+            zip: None,
+        },
+        labels,
+        extra: serde_json::json!({"plan": "pro", "seats": 5, "note": null}),
+    }
+}
+
+fn eval(expression: &str) -> Result<bool, EvaluateError> {
+    Predicate::new(expression)?.evaluate(&sample_customer())
+}
+
+fn assert_true(expression: &str) {
+    assert_eq!(eval(expression), Ok(true), "{}", expression);
+}
+
+fn assert_false(expression: &str) {
+    assert_eq!(eval(expression), Ok(false), "{}", expression);
+}
+
+// =============================================================================
+// Comparisons
+// =============================================================================
+
+#[test]
+fn test_predicate_request_example() {
+    assert_true(r#"status == "active" && score >= 10.0 && tags contains "vip""#);
+}
+
+#[test]
+fn test_predicate_comparison_operators() {
+    assert_true("id == 42");
+    assert_true("id != 41");
+    assert_true("id > 41 && id >= 42 && id < 43 && id <= 42");
+    assert_false("id < 42");
+    assert_true("level < 0");
+    assert_true(r#"status < "b""#);
+}
+
+#[test]
+fn test_predicate_numbers_compare_across_types() {
+    assert_true("score > 12");
+    assert_true("id == 42.0");
+    assert_true("score == 1.25e1");
+    assert_true("level == -3");
+}
+
+#[test]
+fn test_predicate_char_compares_with_string() {
+    assert_true(r#"initial == "a""#);
+}
+
+#[test]
+fn test_predicate_bare_boolean_path() {
+    assert_true("active");
+    assert_false("!active");
+}
+
+#[test]
+fn test_predicate_nested_and_map_paths() {
+    assert_true(r#"address.city == "Paris""#);
+    assert_true(r#"labels["team name"] == "storage""#);
+    assert_true(r#"extra.plan == "pro" && extra.seats >= 5"#);
+}
+
+// =============================================================================
+// Membership
+// =============================================================================
+
+#[test]
+fn test_predicate_in() {
+    assert_true(r#"status in ["active", "pending"]"#);
+    assert_false(r#"status in ["closed"]"#);
+    assert_false("id in []");
+    assert_true(r#""beta" in tags"#);
+}
+
+#[test]
+fn test_predicate_contains() {
+    assert_true(r#"tags contains "beta""#);
+    assert_false(r#"tags contains "gold""#);
+    assert_true(r#"status contains "tiv""#);
+    assert_true(r#"["x", "y"] contains "y""#);
+}
+
+// =============================================================================
+// Nulls and presence
+// =============================================================================
+
+#[test]
+fn test_predicate_options() {
+    assert_true(r#"nickname == "ace""#);
+    assert_true("deleted_at == null");
+    assert_false("deleted_at != null");
+    assert_false("deleted_at > 0");
+    assert_false("deleted_at");
+    assert_true("extra.note == null");
+}
+
+#[test]
+fn test_predicate_exists_and_is_null() {
+    assert_true("exists(nickname)");
+    assert_true("exists(deleted_at)");
+    assert_false("exists(missing)");
+    assert_true("exists(address)");
+    assert_true("is_null(deleted_at)");
+    assert_true("is_null(address.zip)");
+    assert_true("is_null(extra.note)");
+    assert_false("is_null(nickname)");
+    assert_false("is_null(missing)");
+}
+
+#[test]
+fn test_predicate_unit_variants_compare_by_name() {
+    #[derive(Serialize)]
+    enum Status {
+        Active,
+        #[serde(rename = "on_hold")]
+        OnHold,
+    }
+
+    #[derive(Serialize)]
+    struct Account {
+        status: Status,
+        previous: Option<Status>,
+    }
+
+    let account = Account {
+        status: Status::Active,
+        previous: Some(Status::OnHold),
+    };
+    let eval = |expression: &str| Predicate::new(expression).unwrap().evaluate(&account);
+
+    assert_eq!(eval(r#"status == "Active""#), Ok(true));
+    assert_eq!(eval(r#"status != "Active""#), Ok(false));
+    assert_eq!(eval(r#"status in ["Active", "Closed"]"#), Ok(true));
+    assert_eq!(eval(r#"previous == "on_hold""#), Ok(true));
+    assert_eq!(eval("status == null"), Ok(false));
+    assert_eq!(eval("is_null(status)"), Ok(false));
+}
+
+#[test]
+fn test_predicate_short_circuit_guards_missing_paths() {
+    assert_false("exists(missing) && missing > 1");
+    assert_true("!exists(missing) || missing > 1");
+}
+
+// =============================================================================
+// Logic
+// =============================================================================
+
+#[test]
+fn test_predicate_precedence() {
+    // && binds tighter than ||
+    assert_true("id == 1 || id == 42 && active");
+    assert_false("(id == 1 || id == 42) && !active");
+    assert_true("!(id == 1)");
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+#[test]
+fn test_predicate_syntax_errors() {
+    let cases = [
+        ("", 0),
+        ("id ==", 5),
+        ("id == 1 &&", 10),
+        ("(id == 1", 8),
+        (r#"status == "open"#, 10),
+        ("id = 1", 3),
+        ("id == 1 2", 8),
+        ("id == @", 6),
+        ("a.", 2),
+        ("tags[0] == \"vip\"", 5),
+    ];
+    for (expression, expected_position) in cases {
+        match Predicate::new(expression) {
+            Err(EvaluateError::ExpressionSyntax { position, .. }) => {
+                assert_eq!(position, expected_position, "{}", expression)
+            }
+            other => panic!(
+                "Expected ExpressionSyntax for {:?}, got {:?}",
+                expression, other
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_predicate_missing_path_error_names_sub_expression() {
+    let result = eval(r#"status == "active" && address.country == "FR""#);
+    match result {
+        Err(EvaluateError::ExpressionFailed {
+            expression, source, ..
+        }) => {
+            assert_eq!(expression, "address.country");
+            assert!(matches!(*source, EvaluateError::NestedFieldNotFound { .. }));
+        }
+        other => panic!("Expected ExpressionFailed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_predicate_type_mismatch_error_names_sub_expression() {
+    let result = eval(r#"active && (status > 3)"#);
+    match result {
+        Err(EvaluateError::ExpressionFailed {
+            expression, source, ..
+        }) => {
+            assert_eq!(expression, "(status > 3)");
+            assert!(matches!(*source, EvaluateError::TypeMismatch { .. }));
+        }
+        other => panic!("Expected ExpressionFailed, got {:?}", other),
+    }
+
+    let result = eval("status");
+    assert!(
+        matches!(result, Err(EvaluateError::ExpressionFailed { ref expression, .. }) if expression == "status"),
+        "Expected ExpressionFailed, got {:?}",
+        result
+    );
+}
+
+#[test]
+fn test_predicate_error_display() {
+    let err = eval("missing == 1").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expression 'missing' failed: Nested field path 'missing' not found \
+         (failed at segment 0: 'missing', no such struct field)"
+    );
+}
+
+#[test]
+fn test_predicate_as_str() {
+    let predicate = Predicate::new("id == 42").unwrap();
+    assert_eq!(predicate.as_str(), "id == 42");
+}
@@ -0,0 +1,229 @@
+// Tests for NestedFieldExtractor::exists
+
+use serde::Serialize;
+use serde_evaluate::error::EvaluateError;
+use serde_evaluate::extractor::NestedFieldExtractor;
+use serde_evaluate::probe::{FieldKind, PathProbe, VariantShape};
+use serde_evaluate::value::ScalarKind;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Record {
+    id: u64,
+    name: String,
+    nickname: Option<String>,
+    deleted_at: Option<u64>,
+    double: Option<Option<i32>>,
+    metadata: Metadata,
+    tags: Vec<String>,
+    pair: (u8, u8),
+    labels: BTreeMap<String, String>,
+    owner: Option<Owner>,
+    status: Status,
+    shape: Shape,
+    json: serde_json::Value,
+}
+
+#[derive(Serialize)]
+struct Metadata {
+    owner: Option<Owner>,
+    revision: u32,
+}
+
+#[derive(Serialize)]
+struct Owner {
+    name: String,
+}
+
+#[derive(Serialize)]
+enum Status {
+    Active,
+}
+
+#[derive(Serialize)]
+enum Shape {
+    Circle { radius: f64 },
+}
+
+fn sample_record() -> Record {
+    let mut labels = BTreeMap::new();
+    labels.insert("team".to_string(), "storage".to_string());
+
+    Record {
+        id: 1,
+        name: "record".to_string(),
+        nickname: Some("rec".to_string()),
+        deleted_at: None,
+        double: Some(None),
+        metadata: Metadata {
+            owner: None,
+            revision: 3,
+        },
+        tags: vec!["a".to_string()],
+        pair: (1, 2),
+        labels,
+        owner: Some(Owner {
+            name: "alice".to_string(),
+        }),
+        status: Status::Active,
+        shape: Shape::Circle { radius: 1.0 },
+        json: serde_json::json!({"region": "eu", "nested": {"flag": true}, "nothing": null}),
+    }
+}
+
+fn probe(path: &[&str]) -> Result<PathProbe, EvaluateError> {
+    NestedFieldExtractor::new_from_path(path)
+        .unwrap()
+        .exists(&sample_record())
+}
+
+fn present(kind: FieldKind) -> Result<PathProbe, EvaluateError> {
+    Ok(PathProbe::Present { kind })
+}
+
+// =============================================================================
+// Present
+// =============================================================================
+
+#[test]
+fn test_probe_scalar() {
+    assert_eq!(probe(&["id"]), present(FieldKind::Scalar(ScalarKind::U64)));
+    assert_eq!(
+        probe(&["name"]),
+        present(FieldKind::Scalar(ScalarKind::String))
+    );
+}
+
+#[test]
+fn test_probe_some_is_looked_through() {
+    assert_eq!(
+        probe(&["nickname"]),
+        present(FieldKind::Scalar(ScalarKind::String))
+    );
+    assert_eq!(
+        probe(&["owner"]),
+        present(FieldKind::Struct {
+            name: "Owner",
+            fields: 1
+        })
+    );
+}
+
+#[test]
+fn test_probe_struct() {
+    assert_eq!(
+        probe(&["metadata"]),
+        present(FieldKind::Struct {
+            name: "Metadata",
+            fields: 2
+        })
+    );
+}
+
+#[test]
+fn test_probe_nested_scalar() {
+    assert_eq!(
+        probe(&["metadata", "revision"]),
+        present(FieldKind::Scalar(ScalarKind::U32))
+    );
+}
+
+#[test]
+fn test_probe_map() {
+    assert_eq!(probe(&["labels"]), present(FieldKind::Map { len: Some(1) }));
+    assert_eq!(
+        probe(&["labels", "team"]),
+        present(FieldKind::Scalar(ScalarKind::String))
+    );
+}
+
+#[test]
+fn test_probe_seq_and_tuple() {
+    assert_eq!(probe(&["tags"]), present(FieldKind::Seq { len: Some(1) }));
+    assert_eq!(probe(&["pair"]), present(FieldKind::Tuple { len: 2 }));
+}
+
+#[test]
+fn test_probe_enum_variants() {
+    assert_eq!(
+        probe(&["status"]),
+        present(FieldKind::Variant {
+            name: "Status",
+            variant: "Active",
+            shape: VariantShape::Unit
+        })
+    );
+    assert_eq!(
+        probe(&["shape"]),
+        present(FieldKind::Variant {
+            name: "Shape",
+            variant: "Circle",
+            shape: VariantShape::Struct { fields: 1 }
+        })
+    );
+}
+
+#[test]
+fn test_probe_json_value() {
+    assert_eq!(probe(&["json"]), present(FieldKind::Map { len: Some(3) }));
+    assert_eq!(
+        probe(&["json", "nested"]),
+        present(FieldKind::Map { len: Some(1) })
+    );
+    assert_eq!(
+        probe(&["json", "nested", "flag"]),
+        present(FieldKind::Scalar(ScalarKind::Bool))
+    );
+    // JSON null serializes as unit, not as Option::None.
+    assert_eq!(
+        probe(&["json", "nothing"]),
+        present(FieldKind::Scalar(ScalarKind::Unit))
+    );
+}
+
+// =============================================================================
+// Null
+// =============================================================================
+
+#[test]
+fn test_probe_none() {
+    assert_eq!(probe(&["deleted_at"]), Ok(PathProbe::Null));
+    assert_eq!(probe(&["metadata", "owner"]), Ok(PathProbe::Null));
+}
+
+#[test]
+fn test_probe_some_none() {
+    assert_eq!(probe(&["double"]), Ok(PathProbe::Null));
+}
+
+// =============================================================================
+// Missing
+// =============================================================================
+
+#[test]
+fn test_probe_missing_top_level() {
+    assert_eq!(probe(&["nonexistent"]), Ok(PathProbe::Missing));
+}
+
+#[test]
+fn test_probe_missing_struct_field() {
+    assert_eq!(probe(&["metadata", "team"]), Ok(PathProbe::Missing));
+}
+
+#[test]
+fn test_probe_missing_map_key() {
+    assert_eq!(probe(&["labels", "zone"]), Ok(PathProbe::Missing));
+    assert_eq!(probe(&["json", "nested", "other"]), Ok(PathProbe::Missing));
+}
+
+#[test]
+fn test_probe_through_scalar_is_missing() {
+    assert_eq!(probe(&["id", "inner"]), Ok(PathProbe::Missing));
+}
+
+#[test]
+fn test_probe_is_present() {
+    assert!(probe(&["metadata"]).unwrap().is_present());
+    assert!(!probe(&["deleted_at"]).unwrap().is_present());
+    assert!(!probe(&["nonexistent"]).unwrap().is_present());
+}
@@ -0,0 +1,194 @@
+// Tests for SchemaAggregator
+
+use serde::Serialize;
+use serde_evaluate::schema::{PathStats, SchemaAggregator};
+use serde_evaluate::value::{FieldScalarValue, ScalarKind};
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+#[derive(Serialize)]
+struct Order {
+    id: u64,
+    customer: Option<String>,
+    items: Vec<Item>,
+}
+
+#[derive(Serialize)]
+struct Item {
+    sku: String,
+    qty: u32,
+}
+
+fn order(id: u64, customer: Option<&str>, items: &[(&str, u32)]) -> Order {
+    Order {
+        id,
+        customer: customer.map(str::to_string),
+        items: items
+            .iter()
+            .map(|(sku, qty)| Item {
+                sku: sku.to_string(),
+                qty: *qty,
+            })
+            .collect(),
+    }
+}
+
+fn stats<'a>(report: &'a [PathStats], path: &str) -> &'a PathStats {
+    report
+        .iter()
+        .find(|stats| stats.path == path)
+        .unwrap_or_else(|| panic!("no stats for {}", path))
+}
+
+fn kinds(kinds: &[ScalarKind]) -> BTreeSet<ScalarKind> {
+    kinds.iter().copied().collect()
+}
+
+// =============================================================================
+// Aggregation
+// =============================================================================
+
+#[test]
+fn test_schema_paths_are_normalized() {
+    let mut schema = SchemaAggregator::new();
+    schema
+        .observe(&order(1, Some("a"), &[("x", 1), ("y", 2)]))
+        .unwrap();
+
+    let paths: Vec<String> = schema
+        .report()
+        .into_iter()
+        .map(|stats| stats.path)
+        .collect();
+    assert_eq!(
+        paths,
+        vec!["customer", "id", "items[*].qty", "items[*].sku"]
+    );
+    assert_eq!(schema.records(), 1);
+}
+
+#[test]
+fn test_schema_scalar_stats() {
+    let mut schema = SchemaAggregator::new();
+    for id in [5, 2, 9, 2] {
+        schema.observe(&order(id, Some("a"), &[])).unwrap();
+    }
+
+    let report = schema.report();
+    let id = stats(&report, "id");
+    assert_eq!(id.kinds, kinds(&[ScalarKind::U64]));
+    assert_eq!(id.values, 4);
+    assert_eq!(id.nulls, 0);
+    assert_eq!(id.missing, 0);
+    assert_eq!(id.min, Some(FieldScalarValue::U64(2)));
+    assert_eq!(id.max, Some(FieldScalarValue::U64(9)));
+    assert_eq!(id.distinct, 3);
+    assert_eq!(id.max_list_len, None);
+}
+
+#[test]
+fn test_schema_nulls_unwrap_options() {
+    let mut schema = SchemaAggregator::new();
+    schema.observe(&order(1, Some("bob"), &[])).unwrap();
+    schema.observe(&order(2, None, &[])).unwrap();
+    schema.observe(&order(3, Some("alice"), &[])).unwrap();
+
+    let report = schema.report();
+    let customer = stats(&report, "customer");
+    assert_eq!(customer.kinds, kinds(&[ScalarKind::String]));
+    assert_eq!(customer.values, 3);
+    assert_eq!(customer.nulls, 1);
+    assert_eq!(
+        customer.min,
+        Some(FieldScalarValue::String("alice".to_string()))
+    );
+    assert_eq!(
+        customer.max,
+        Some(FieldScalarValue::String("bob".to_string()))
+    );
+    assert_eq!(customer.distinct, 2);
+}
+
+#[test]
+fn test_schema_list_paths() {
+    let mut schema = SchemaAggregator::new();
+    schema
+        .observe(&order(1, None, &[("x", 1), ("y", 2), ("x", 3)]))
+        .unwrap();
+    schema.observe(&order(2, None, &[("z", 1)])).unwrap();
+    schema.observe(&order(3, None, &[])).unwrap();
+
+    let report = schema.report();
+    let sku = stats(&report, "items[*].sku");
+    assert_eq!(sku.values, 4);
+    assert_eq!(sku.distinct, 3);
+    assert_eq!(sku.max_list_len, Some(3));
+    // The empty list in the third order has no leaves.
+    assert_eq!(sku.missing, 1);
+}
+
+// =============================================================================
+// Schema drift
+// =============================================================================
+
+#[test]
+fn test_schema_drift_in_json_stream() {
+    let mut schema = SchemaAggregator::new();
+    schema
+        .observe(&serde_json::json!({"id": 1, "name": "a"}))
+        .unwrap();
+    schema
+        .observe(&serde_json::json!({"id": "two", "name": "b"}))
+        .unwrap();
+    schema
+        .observe(&serde_json::json!({"id": 3, "email": "c@example.com"}))
+        .unwrap();
+
+    let report = schema.report();
+    let id = stats(&report, "id");
+    assert_eq!(id.kinds, kinds(&[ScalarKind::U64, ScalarKind::String]));
+    assert_eq!(id.values, 3);
+
+    assert_eq!(stats(&report, "name").missing, 1);
+    // A path first seen late counts earlier records as missing.
+    assert_eq!(stats(&report, "email").missing, 2);
+}
+
+// =============================================================================
+// Distinct estimate
+// =============================================================================
+
+#[test]
+fn test_schema_distinct_estimate_large_cardinality() {
+    let mut schema = SchemaAggregator::new();
+    for id in 0..10_000u64 {
+        schema.observe(&order(id % 5_000, None, &[])).unwrap();
+    }
+
+    let report = schema.report();
+    let distinct = stats(&report, "id").distinct as f64;
+    let error = (distinct - 5_000.0).abs() / 5_000.0;
+    assert!(error < 0.2, "estimate {} too far from 5000", distinct);
+}
+
+// =============================================================================
+// total_cmp
+// =============================================================================
+
+#[test]
+fn test_total_cmp() {
+    use FieldScalarValue as V;
+    assert_eq!(V::I32(-1).total_cmp(&V::I32(1)), Ordering::Less);
+    assert_eq!(V::F64(f64::NAN).total_cmp(&V::F64(1.0)), Ordering::Greater);
+    assert_eq!(V::F64(-0.0).total_cmp(&V::F64(0.0)), Ordering::Less);
+    assert_eq!(
+        V::Option(None).total_cmp(&V::Option(Some(Box::new(V::Unit)))),
+        Ordering::Less
+    );
+    // Different variants are ordered by kind.
+    assert_eq!(V::Bool(true).total_cmp(&V::U8(0)), Ordering::Less);
+    assert_eq!(
+        V::String("a".to_string()).total_cmp(&V::I64(9)),
+        Ordering::Greater
+    );
+}
@@ -0,0 +1,200 @@
+use serde::Serialize;
+use serde_evaluate::{
+    CompositeFieldExtractor, EvaluateError, FieldExtractor, FieldScalarRef, FieldScalarValue,
+    NestedFieldExtractor, NotFoundReason, PathProbe, SkippedFieldPolicy,
+};
+
+#[derive(Serialize)]
+struct User {
+    id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nickname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<Address>,
+}
+
+#[derive(Serialize)]
+struct Address {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    zip: Option<String>,
+    city: String,
+}
+
+fn sparse_user() -> User {
+    User {
+        id: 1,
+        nickname: None,
+        address: None,
+    }
+}
+
+#[track_caller]
+fn assert_skipped_at<T: std::fmt::Debug>(
+    result: Result<T, EvaluateError>,
+    path: &[&str],
+    index: usize,
+) {
+    match result {
+        Err(EvaluateError::NestedFieldNotFound {
+            path: failed_path,
+            failed_at_index,
+            reason: NotFoundReason::Skipped,
+            ..
+        }) => {
+            assert_eq!(failed_path, path);
+            assert_eq!(failed_at_index, index);
+        }
+        other => panic!("expected a skipped field at {:?}, got {:?}", path, other),
+    }
+}
+
+fn nested(path: &[&str]) -> NestedFieldExtractor {
+    NestedFieldExtractor::new_from_path(path).unwrap()
+}
+
+// =============================================================================
+// Default policy
+// =============================================================================
+
+#[test]
+fn skipped_field_is_distinguished_from_typo() {
+    let user = sparse_user();
+
+    assert_skipped_at(
+        FieldExtractor::new("nickname").evaluate(&user),
+        &["nickname"],
+        0,
+    );
+    assert_eq!(
+        FieldExtractor::new("nick_name").evaluate(&user),
+        Err(EvaluateError::FieldNotFound {
+            field_name: "nick_name".to_string()
+        })
+    );
+}
+
+#[test]
+fn skipped_nested_field_reports_its_segment() {
+    let user = User {
+        address: Some(Address {
+            zip: None,
+            city: "Lyon".to_string(),
+        }),
+        ..sparse_user()
+    };
+
+    assert_skipped_at(
+        nested(&["address", "zip"]).evaluate(&user),
+        &["address", "zip"],
+        1,
+    );
+    assert_eq!(
+        nested(&["address", "zip"])
+            .evaluate(&user)
+            .unwrap_err()
+            .to_string(),
+        "Nested field path 'address.zip' not found \
+         (failed at segment 1: 'zip', field skipped during serialization)"
+    );
+}
+
+#[test]
+fn skipped_field_is_missing_for_exists() {
+    assert_eq!(
+        nested(&["nickname"]).exists(&sparse_user()).unwrap(),
+        PathProbe::Missing
+    );
+}
+
+// =============================================================================
+// AsNone policy
+// =============================================================================
+
+#[test]
+fn as_none_extracts_skipped_field() {
+    let user = sparse_user();
+
+    let extractor = FieldExtractor::new("nickname").with_skipped_fields(SkippedFieldPolicy::AsNone);
+    assert_eq!(
+        extractor.evaluate(&user),
+        Ok(FieldScalarValue::Option(None))
+    );
+    assert_eq!(
+        extractor.evaluate_with(&user, |v| v == FieldScalarRef::Option(None)),
+        Ok(true)
+    );
+
+    let extractor = nested(&["nickname"]).with_skipped_fields(SkippedFieldPolicy::AsNone);
+    assert_eq!(
+        extractor.evaluate(&user),
+        Ok(FieldScalarValue::Option(None))
+    );
+    assert_eq!(extractor.exists(&user), Ok(PathProbe::Null));
+}
+
+#[test]
+fn as_none_keeps_present_values() {
+    let user = User {
+        nickname: Some("ada".to_string()),
+        ..sparse_user()
+    };
+    let extractor = nested(&["nickname"]).with_skipped_fields(SkippedFieldPolicy::AsNone);
+    assert_eq!(
+        extractor.evaluate(&user),
+        Ok(FieldScalarValue::Option(Some(Box::new(
+            FieldScalarValue::String("ada".to_string())
+        ))))
+    );
+}
+
+#[test]
+fn as_none_does_not_traverse_skipped_field() {
+    let extractor = nested(&["address", "city"]).with_skipped_fields(SkippedFieldPolicy::AsNone);
+    assert_skipped_at(extractor.evaluate(&sparse_user()), &["address", "city"], 0);
+}
+
+#[test]
+fn as_none_does_not_hide_typos() {
+    let extractor =
+        FieldExtractor::new("nick_name").with_skipped_fields(SkippedFieldPolicy::AsNone);
+    assert!(matches!(
+        extractor.evaluate(&sparse_user()),
+        Err(EvaluateError::FieldNotFound { .. })
+    ));
+}
+
+// =============================================================================
+// Composite and compiled extractors
+// =============================================================================
+
+#[test]
+fn composite_and_compiled_apply_the_policy() {
+    let user = sparse_user();
+    let composite =
+        CompositeFieldExtractor::new_from_paths(&[&["id"][..], &["nickname"], &["address", "zip"]])
+            .unwrap();
+
+    assert_skipped_at(composite.evaluate(&user), &["nickname"], 0);
+    assert_skipped_at(composite.compile().evaluate(&user), &["nickname"], 0);
+
+    let composite = CompositeFieldExtractor::new(&["id", "nickname"])
+        .unwrap()
+        .with_skipped_fields(SkippedFieldPolicy::AsNone);
+    let expected = vec![FieldScalarValue::U32(1), FieldScalarValue::Option(None)];
+    assert_eq!(composite.evaluate(&user), Ok(expected.clone()));
+    assert_eq!(composite.compile().evaluate(&user), Ok(expected.clone()));
+
+    let compiled = CompositeFieldExtractor::new(&["id", "nickname"])
+        .unwrap()
+        .compile()
+        .with_skipped_fields(SkippedFieldPolicy::AsNone);
+    assert_eq!(compiled.evaluate(&user), Ok(expected));
+}
+
+#[test]
+fn compiled_reports_skipped_subtree() {
+    let compiled = CompositeFieldExtractor::new_from_paths(&[&["id"][..], &["address", "city"]])
+        .unwrap()
+        .compile();
+    assert_skipped_at(compiled.evaluate(&sparse_user()), &["address", "city"], 0);
+}
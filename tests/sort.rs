@@ -0,0 +1,249 @@
+// Tests for PathComparator
+
+use serde::Serialize;
+use serde_evaluate::error::EvaluateError;
+use serde_evaluate::sort::{NullOrder, PathComparator, SortOrder::*};
+use serde_evaluate::value::FieldScalarValue;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Debug)]
+struct Order {
+    id: u32,
+    customer: Customer,
+    total: f64,
+    created_at: u64,
+    shipped_at: Option<u64>,
+    labels: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Debug)]
+struct Customer {
+    region: String,
+}
+
+fn order(id: u32, region: &str, total: f64, created_at: u64, shipped_at: Option<u64>) -> Order {
+    let mut labels = BTreeMap::new();
+    labels.insert("sales rep".to_string(), format!("rep-{}", id % 2));
+    Order {
+        id,
+        customer: Customer {
+            region: region.to_string(),
+        },
+        total,
+        created_at,
+        shipped_at,
+        labels,
+    }
+}
+
+fn sample_orders() -> Vec<Order> {
+    vec![
+        order(1, "us", 10.0, 300, Some(5)),
+        order(2, "eu", 5.0, 200, None),
+        order(3, "eu", 20.0, 100, Some(2)),
+        order(4, "us", 10.0, 50, None),
+        order(5, "eu", 20.0, 400, Some(9)),
+    ]
+}
+
+fn ids(orders: &[Order]) -> Vec<u32> {
+    orders.iter().map(|o| o.id).collect()
+}
+
+// =============================================================================
+// Sorting
+// =============================================================================
+
+#[test]
+fn test_sort_by_multiple_paths() {
+    let mut orders = sample_orders();
+    let comparator = PathComparator::new(&[
+        ("customer.region", Asc),
+        ("total", Desc),
+        ("created_at", Asc),
+    ])
+    .unwrap();
+    comparator.sort(&mut orders).unwrap();
+    assert_eq!(ids(&orders), vec![3, 5, 2, 4, 1]);
+}
+
+#[test]
+fn test_sort_is_stable() {
+    let mut orders = sample_orders();
+    let comparator = PathComparator::new(&[("customer.region", Desc)]).unwrap();
+    comparator.sort(&mut orders).unwrap();
+    assert_eq!(ids(&orders), vec![1, 4, 2, 3, 5]);
+}
+
+#[test]
+fn test_sort_by_map_key_path() {
+    let mut orders = sample_orders();
+    let comparator = PathComparator::new(&[(r#"labels["sales rep"]"#, Asc), ("id", Desc)]).unwrap();
+    comparator.sort(&mut orders).unwrap();
+    assert_eq!(ids(&orders), vec![4, 2, 5, 3, 1]);
+}
+
+#[test]
+fn test_sort_empty_slice() {
+    let mut orders: Vec<Order> = Vec::new();
+    let comparator = PathComparator::new(&[("id", Asc)]).unwrap();
+    assert_eq!(comparator.sort(&mut orders), Ok(()));
+}
+
+// =============================================================================
+// Nulls
+// =============================================================================
+
+#[test]
+fn test_sort_nulls_last_by_default() {
+    let mut orders = sample_orders();
+    PathComparator::new(&[("shipped_at", Asc)])
+        .unwrap()
+        .sort(&mut orders)
+        .unwrap();
+    assert_eq!(ids(&orders), vec![3, 1, 5, 2, 4]);
+
+    // Descending does not move nulls
+    PathComparator::new(&[("shipped_at", Desc)])
+        .unwrap()
+        .sort(&mut orders)
+        .unwrap();
+    assert_eq!(ids(&orders), vec![5, 1, 3, 2, 4]);
+}
+
+#[test]
+fn test_sort_nulls_first() {
+    let mut orders = sample_orders();
+    PathComparator::new(&[("shipped_at", Desc), ("id", Desc)])
+        .unwrap()
+        .with_nulls(NullOrder::First)
+        .sort(&mut orders)
+        .unwrap();
+    assert_eq!(ids(&orders), vec![4, 2, 5, 1, 3]);
+}
+
+#[test]
+fn test_sort_json_nulls() {
+    let mut records = vec![
+        serde_json::json!({"id": 1, "rank": 2}),
+        serde_json::json!({"id": 2, "rank": null}),
+        serde_json::json!({"id": 3, "rank": 1}),
+    ];
+    PathComparator::new(&[("rank", Asc)])
+        .unwrap()
+        .with_nulls(NullOrder::First)
+        .sort(&mut records)
+        .unwrap();
+    let ids: Vec<_> = records.iter().map(|r| r["id"].as_u64().unwrap()).collect();
+    assert_eq!(ids, vec![2, 3, 1]);
+}
+
+#[test]
+fn test_sort_json_numbers_by_value() {
+    let mut records = vec![
+        serde_json::json!({"id": 1, "rank": 3}),
+        serde_json::json!({"id": 2, "rank": 2.5}),
+        serde_json::json!({"id": 3, "rank": -1}),
+        serde_json::json!({"id": 4, "rank": -7.5}),
+        serde_json::json!({"id": 5, "rank": 2}),
+    ];
+    let comparator = PathComparator::new(&[("rank", Asc)]).unwrap();
+    comparator.sort(&mut records).unwrap();
+    let ids: Vec<_> = records.iter().map(|r| r["id"].as_u64().unwrap()).collect();
+    assert_eq!(ids, vec![4, 3, 5, 2, 1]);
+
+    // Equal values of different kinds are ordered by kind
+    let (i, u, f) = (
+        [FieldScalarValue::I64(2)],
+        [FieldScalarValue::U64(2)],
+        [FieldScalarValue::F64(2.0)],
+    );
+    assert_eq!(comparator.compare_keys(&i, &u), Ordering::Less);
+    assert_eq!(comparator.compare_keys(&u, &f), Ordering::Less);
+}
+
+// =============================================================================
+// Keys
+// =============================================================================
+
+#[test]
+fn test_sort_key_and_compare_keys() {
+    let comparator = PathComparator::new(&[("customer.region", Asc), ("total", Desc)]).unwrap();
+    let orders = sample_orders();
+
+    let key = comparator.sort_key(&orders[2]).unwrap();
+    assert_eq!(
+        key,
+        vec![
+            FieldScalarValue::String("eu".to_string()),
+            FieldScalarValue::F64(20.0)
+        ]
+    );
+
+    let mut keys: Vec<_> = orders
+        .iter()
+        .map(|o| comparator.sort_key(o).unwrap())
+        .collect();
+    keys.sort_by(|a, b| comparator.compare_keys(a, b));
+    assert_eq!(keys[0], key);
+}
+
+#[test]
+fn test_compare_records() {
+    let comparator = PathComparator::new(&[("customer.region", Asc), ("total", Desc)]).unwrap();
+    let mut orders = sample_orders();
+    let mut expected = sample_orders();
+    comparator.sort(&mut expected).unwrap();
+
+    orders.sort_by(|a, b| comparator.compare(a, b).unwrap());
+    assert_eq!(ids(&orders), ids(&expected));
+    assert_eq!(
+        comparator.compare(&orders[0], &orders[0]),
+        Ok(Ordering::Equal)
+    );
+
+    let comparator = PathComparator::new(&[("customer.country", Asc)]).unwrap();
+    assert!(matches!(
+        comparator.compare(&orders[0], &orders[1]),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+#[test]
+fn test_sort_missing_path_leaves_records_untouched() {
+    let mut orders = sample_orders();
+    let comparator = PathComparator::new(&[("id", Desc), ("customer.country", Asc)]).unwrap();
+    let result = comparator.sort(&mut orders);
+    assert!(
+        matches!(result, Err(EvaluateError::NestedFieldNotFound { .. })),
+        "Expected NestedFieldNotFound, got {:?}",
+        result
+    );
+    assert_eq!(ids(&orders), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_sort_invalid_paths() {
+    let no_keys: &[(&str, _)] = &[];
+    assert!(matches!(
+        PathComparator::new(no_keys),
+        Err(EvaluateError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        PathComparator::new(&[("customer.", Asc)]),
+        Err(EvaluateError::ExpressionSyntax { position: 9, .. })
+    ));
+    assert!(matches!(
+        PathComparator::new(&[("total desc", Asc)]),
+        Err(EvaluateError::ExpressionSyntax { position: 6, .. })
+    ));
+    assert!(matches!(
+        PathComparator::new(&[("items[0].sku", Asc)]),
+        Err(EvaluateError::ExpressionSyntax { position: 6, .. })
+    ));
+}
@@ -0,0 +1,166 @@
+use serde::{Serialize, Serializer};
+use serde_evaluate::{
+    CompositeFieldExtractor, EvaluateError, ExtractionState, FieldExtractor, FieldScalarValue,
+    MissingPolicy, NestedFieldExtractor, PathProbe,
+};
+use std::collections::BTreeMap;
+
+/// A value whose `Serialize` impl always fails.
+struct Broken;
+
+impl Serialize for Broken {
+    fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+        Err(serde::ser::Error::custom("broken payload"))
+    }
+}
+
+#[derive(Serialize)]
+struct Record {
+    id: u64,
+    meta: Meta,
+    payload: Broken,
+}
+
+#[derive(Serialize)]
+struct Meta {
+    labels: BTreeMap<String, Broken>,
+    owner: String,
+}
+
+fn sample_record() -> Record {
+    Record {
+        id: 7,
+        meta: Meta {
+            labels: BTreeMap::from([("team".to_string(), Broken)]),
+            owner: "ada".to_string(),
+        },
+        payload: Broken,
+    }
+}
+
+fn broken() -> EvaluateError {
+    EvaluateError::SerializationError {
+        message: "broken payload".to_string(),
+    }
+}
+
+fn nested(path: &[&str]) -> NestedFieldExtractor {
+    NestedFieldExtractor::new_from_path(path).unwrap()
+}
+
+// =============================================================================
+// Default mode
+// =============================================================================
+
+#[test]
+fn off_path_errors_are_not_seen_by_default() {
+    let record = sample_record();
+    assert_eq!(
+        nested(&["id"]).evaluate(&record),
+        Ok(FieldScalarValue::U64(7))
+    );
+    assert_eq!(
+        nested(&["meta", "owner"]).evaluate(&record),
+        Ok(FieldScalarValue::String("ada".to_string()))
+    );
+}
+
+#[test]
+fn on_path_errors_are_always_returned() {
+    let record = sample_record();
+    assert_eq!(nested(&["payload"]).evaluate(&record), Err(broken()));
+    assert_eq!(
+        nested(&["meta", "labels", "team"]).evaluate(&record),
+        Err(broken())
+    );
+}
+
+// =============================================================================
+// Strict mode
+// =============================================================================
+
+#[test]
+fn strict_mode_surfaces_off_path_errors() {
+    let record = sample_record();
+    assert_eq!(
+        FieldExtractor::new("id")
+            .with_strict_mode(true)
+            .evaluate(&record),
+        Err(broken())
+    );
+
+    let extractor = nested(&["meta", "owner"]).with_strict_mode(true);
+    assert_eq!(extractor.evaluate(&record), Err(broken()));
+    assert_eq!(extractor.evaluate_with(&record, |_| ()), Err(broken()));
+    assert_eq!(extractor.exists(&record), Err(broken()));
+    assert_eq!(extractor.describe(&record), Err(broken()));
+}
+
+#[test]
+fn strict_mode_separates_broken_records_from_missing_paths() {
+    let extractor = nested(&["meta", "email"])
+        .with_strict_mode(true)
+        .with_missing_paths(MissingPolicy::ReturnNone);
+    assert_eq!(extractor.evaluate(&sample_record()), Err(broken()));
+}
+
+#[test]
+fn strict_mode_accepts_valid_records() {
+    #[derive(Serialize)]
+    struct Valid {
+        id: u64,
+        tags: Vec<String>,
+        labels: BTreeMap<String, (u8, Option<char>)>,
+    }
+
+    let record = Valid {
+        id: 1,
+        tags: vec!["a".to_string()],
+        labels: BTreeMap::from([("x".to_string(), (1, Some('c')))]),
+    };
+    assert_eq!(
+        nested(&["id"]).with_strict_mode(true).evaluate(&record),
+        Ok(FieldScalarValue::U64(1))
+    );
+    assert_eq!(
+        nested(&["email"]).with_strict_mode(true).exists(&record),
+        Ok(PathProbe::Missing)
+    );
+}
+
+// =============================================================================
+// Composite and compiled extractors
+// =============================================================================
+
+#[test]
+fn composite_and_compiled_apply_strict_mode() {
+    let record = sample_record();
+    let composite = CompositeFieldExtractor::new_from_paths(&[&["id"][..], &["meta", "owner"]])
+        .unwrap()
+        .with_strict_mode(true);
+
+    assert_eq!(composite.evaluate(&record), Err(broken()));
+    assert_eq!(composite.evaluate_all(&record), Err(broken()));
+
+    let compiled = composite.compile();
+    assert_eq!(compiled.evaluate(&record), Err(broken()));
+    assert_eq!(
+        compiled.evaluate_into(&record, &mut ExtractionState::new()),
+        Err(broken())
+    );
+    assert_eq!(compiled.evaluate_all(&record), Err(broken()));
+    assert!(compiled.with_strict_mode(false).evaluate(&record).is_ok());
+}
+
+#[test]
+fn one_strict_path_makes_the_record_strict() {
+    let record = sample_record();
+    let composite = CompositeFieldExtractor::from_extractors(vec![
+        nested(&["id"]),
+        nested(&["meta", "owner"]).with_strict_mode(true),
+    ])
+    .unwrap();
+
+    assert_eq!(composite.evaluate(&record), Err(broken()));
+    assert_eq!(composite.compile().evaluate(&record), Err(broken()));
+}
@@ -0,0 +1,335 @@
+// Tests for enum representations and #[serde(flatten)] across extraction paths
+
+use serde::{Deserialize, Serialize};
+use serde_evaluate::path::{join_path, paths};
+use serde_evaluate::value::ScalarKind;
+use serde_evaluate::{
+    CompositeFieldExtractor, EvaluateError, FieldKind, FieldScalarValue, NestedFieldExtractor,
+    NotFoundReason,
+};
+
+#[derive(Serialize, Deserialize)]
+enum External {
+    Circle { r: f64 },
+    Empty,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Internal {
+    Circle { r: f64 },
+    Empty,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+    Circle { r: f64 },
+    Empty,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    Circle { r: f64 },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Drawing<S> {
+    id: u32,
+    shape: S,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Page {
+    title: String,
+    #[serde(flatten)]
+    meta: Meta,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Meta {
+    author: String,
+    version: u16,
+}
+
+fn nested(path: &[&str]) -> NestedFieldExtractor {
+    NestedFieldExtractor::new_from_path(path).unwrap()
+}
+
+fn string(s: &str) -> FieldScalarValue {
+    FieldScalarValue::String(s.to_string())
+}
+
+/// Evaluates `path` on `record` with the nested, compiled and deserializing extractors,
+/// asserting that all three agree.
+fn evaluate_everywhere<T: Serialize>(
+    record: &T,
+    path: &[&str],
+) -> Result<FieldScalarValue, EvaluateError> {
+    let from_record = nested(path).evaluate(record);
+
+    let compiled = CompositeFieldExtractor::new_from_paths(&[path])
+        .unwrap()
+        .compile();
+    let from_compiled = compiled.evaluate(record).map(|mut v| v.remove(0));
+    assert_eq!(from_record, from_compiled, "compiled extractor disagrees");
+
+    let json = serde_json::to_string(record).unwrap();
+    let mut de = serde_json::Deserializer::from_str(&json);
+    let from_json = nested(path).evaluate_from(&mut de);
+    assert_eq!(from_record, from_json, "deserializing extractor disagrees");
+
+    from_record
+}
+
+fn leaves<T: Serialize>(record: &T) -> Vec<(String, FieldScalarValue)> {
+    paths(record)
+        .unwrap()
+        .into_iter()
+        .map(|(path, value)| (join_path(&path), value))
+        .collect()
+}
+
+// =============================================================================
+// Internally tagged enums
+// =============================================================================
+
+#[test]
+fn internal_tag_and_payload_are_addressable() {
+    let drawing = Drawing {
+        id: 1,
+        shape: Internal::Circle { r: 2.0 },
+    };
+    assert_eq!(
+        evaluate_everywhere(&drawing, &["shape", "type"]),
+        Ok(string("Circle"))
+    );
+    assert_eq!(
+        evaluate_everywhere(&drawing, &["shape", "r"]),
+        Ok(FieldScalarValue::F64(2.0))
+    );
+    assert_eq!(
+        nested(&["shape", "type"]).describe(&drawing),
+        Ok(FieldKind::Scalar(ScalarKind::String))
+    );
+}
+
+#[test]
+fn internal_unit_variant_has_only_a_tag() {
+    let drawing = Drawing {
+        id: 1,
+        shape: Internal::Empty,
+    };
+    assert_eq!(
+        evaluate_everywhere(&drawing, &["shape", "type"]),
+        Ok(string("Empty"))
+    );
+    assert!(matches!(
+        nested(&["shape", "r"]).evaluate(&drawing),
+        Err(EvaluateError::NestedFieldNotFound { .. })
+    ));
+}
+
+// =============================================================================
+// Adjacently tagged enums
+// =============================================================================
+
+#[test]
+fn adjacent_tag_extracts_variant_name() {
+    let drawing = Drawing {
+        id: 1,
+        shape: Adjacent::Circle { r: 2.0 },
+    };
+    assert_eq!(
+        evaluate_everywhere(&drawing, &["shape", "t"]),
+        Ok(string("Circle"))
+    );
+    assert_eq!(
+        evaluate_everywhere(&drawing, &["shape", "c", "r"]),
+        Ok(FieldScalarValue::F64(2.0))
+    );
+    assert_eq!(
+        nested(&["shape", "t"]).describe(&drawing),
+        Ok(FieldKind::Scalar(ScalarKind::String))
+    );
+
+    let drawing = Drawing {
+        id: 1,
+        shape: Adjacent::Empty,
+    };
+    assert_eq!(
+        evaluate_everywhere(&drawing, &["shape", "t"]),
+        Ok(string("Empty"))
+    );
+}
+
+#[test]
+fn adjacent_tag_is_a_string_leaf() {
+    let drawing = Drawing {
+        id: 1,
+        shape: Adjacent::Circle { r: 2.0 },
+    };
+    assert_eq!(
+        leaves(&drawing),
+        vec![
+            ("id".to_string(), FieldScalarValue::U32(1)),
+            ("shape.t".to_string(), string("Circle")),
+            ("shape.c.r".to_string(), FieldScalarValue::F64(2.0)),
+        ]
+    );
+}
+
+mod modes {
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    pub enum Mode {
+        Fast,
+    }
+
+    /// Named like the enum of its first field, but with three fields.
+    #[derive(Serialize)]
+    #[serde(rename = "Mode")]
+    pub struct Wide {
+        pub mode: Mode,
+        pub id: u32,
+        pub note: &'static str,
+    }
+}
+
+/// Named like the enum of its second field.
+#[derive(Serialize)]
+struct Mode {
+    id: u32,
+    mode: modes::Mode,
+}
+
+/// Asserts that the `mode` field of `record` is a plain unit variant to every extractor.
+fn assert_mode_is_unit<T: Serialize>(record: &T) {
+    assert_eq!(
+        nested(&["mode"]).evaluate(record),
+        Ok(FieldScalarValue::Unit)
+    );
+    let compiled = CompositeFieldExtractor::new(&["mode"]).unwrap().compile();
+    assert_eq!(compiled.evaluate(record), Ok(vec![FieldScalarValue::Unit]));
+    assert!(leaves(record).contains(&("mode".to_string(), FieldScalarValue::Unit)));
+}
+
+#[test]
+fn same_named_struct_is_not_an_adjacent_tag() {
+    assert_mode_is_unit(&Mode {
+        id: 1,
+        mode: modes::Mode::Fast,
+    });
+    assert_mode_is_unit(&modes::Wide {
+        mode: modes::Mode::Fast,
+        id: 1,
+        note: "n",
+    });
+}
+
+// =============================================================================
+// Untagged and externally tagged enums
+// =============================================================================
+
+#[test]
+fn untagged_payload_is_addressable() {
+    let drawing = Drawing {
+        id: 1,
+        shape: Untagged::Circle { r: 2.0 },
+    };
+    assert_eq!(
+        evaluate_everywhere(&drawing, &["shape", "r"]),
+        Ok(FieldScalarValue::F64(2.0))
+    );
+}
+
+#[test]
+fn external_variant_is_not_traversable_in_records() {
+    let drawing = Drawing {
+        id: 1,
+        shape: External::Circle { r: 2.0 },
+    };
+    assert!(matches!(
+        nested(&["shape", "Circle", "r"]).evaluate(&drawing),
+        Err(EvaluateError::UnsupportedVariant { .. })
+    ));
+
+    let json = serde_json::to_string(&drawing).unwrap();
+    let mut de = serde_json::Deserializer::from_str(&json);
+    assert_eq!(
+        nested(&["shape", "Circle", "r"]).evaluate_from(&mut de),
+        Ok(FieldScalarValue::F64(2.0))
+    );
+}
+
+#[test]
+fn external_unit_variant_is_unit() {
+    let drawing = Drawing {
+        id: 1,
+        shape: External::Empty,
+    };
+    assert_eq!(
+        nested(&["shape"]).evaluate(&drawing),
+        Ok(FieldScalarValue::Unit)
+    );
+    assert_eq!(
+        leaves(&drawing),
+        vec![
+            ("id".to_string(), FieldScalarValue::U32(1)),
+            ("shape".to_string(), FieldScalarValue::Unit),
+        ]
+    );
+}
+
+// =============================================================================
+// Flattened structs
+// =============================================================================
+
+fn page() -> Page {
+    Page {
+        title: "Intro".to_string(),
+        meta: Meta {
+            author: "ada".to_string(),
+            version: 3,
+        },
+    }
+}
+
+#[test]
+fn flattened_fields_belong_to_the_outer_struct() {
+    let page = page();
+    assert_eq!(evaluate_everywhere(&page, &["author"]), Ok(string("ada")));
+    assert_eq!(
+        nested(&["version"]).evaluate(&page),
+        Ok(FieldScalarValue::U16(3))
+    );
+    assert_eq!(
+        leaves(&page),
+        vec![
+            ("title".to_string(), string("Intro")),
+            ("author".to_string(), string("ada")),
+            ("version".to_string(), FieldScalarValue::U16(3)),
+        ]
+    );
+}
+
+#[test]
+fn flattened_struct_is_serialized_as_a_map() {
+    let page = page();
+    assert!(matches!(
+        nested(&["editor"]).evaluate(&page),
+        Err(EvaluateError::NestedFieldNotFound {
+            path,
+            failed_at_index: 0,
+            reason: NotFoundReason::MissingKey,
+            ..
+        }) if path == ["editor"]
+    ));
+    let drawing = Drawing { id: 1, shape: page };
+    assert!(matches!(
+        nested(&["shape"]).describe(&drawing),
+        Ok(FieldKind::Map { len: None })
+    ));
+}